@@ -0,0 +1,231 @@
+//! Clipping of item shapes to a rectangle, see [`super::Line::clip_data`].
+
+use epaint::{Color32, Mesh, PathShape, Pos2, Rect, Shape, Stroke, Vertex};
+
+/// Clips the shapes to `rect` and appends the result to `out`.
+///
+/// Lines are split where they leave the rectangle and filled areas are intersected with it.
+/// Shapes at a single position, e.g. markers and texts, are kept if their position is inside.
+pub(super) fn clip_shapes(shapes: Vec<Shape>, rect: Rect, out: &mut Vec<Shape>) {
+    for shape in shapes {
+        match shape {
+            Shape::Noop => {}
+            Shape::Vec(shapes) => clip_shapes(shapes, rect, out),
+            Shape::Circle(circle) => {
+                if rect.contains(circle.center) {
+                    out.push(Shape::Circle(circle));
+                }
+            }
+            Shape::Text(text) => {
+                if rect.contains(text.pos) {
+                    out.push(Shape::Text(text));
+                }
+            }
+            Shape::LineSegment { points, stroke } => clip_polyline(&points, stroke, rect, out),
+            Shape::Path(path) => {
+                if path.closed {
+                    let points = clip_polygon(&path.points, rect);
+                    if points.len() >= 3 {
+                        out.push(Shape::Path(PathShape { points, ..path }));
+                    }
+                } else {
+                    clip_polyline(&path.points, path.stroke, rect, out);
+                }
+            }
+            Shape::Rect(mut rect_shape) => {
+                rect_shape.rect = rect_shape.rect.intersect(rect);
+                if rect_shape.rect.is_positive() {
+                    out.push(Shape::Rect(rect_shape));
+                }
+            }
+            Shape::Mesh(mesh) => out.push(Shape::Mesh(clip_mesh(&mesh, rect))),
+            // Not used by plot items.
+            Shape::QuadraticBezier(_) | Shape::CubicBezier(_) => out.push(shape),
+        }
+    }
+}
+
+/// Splits the polyline into the runs that are inside `rect`.
+fn clip_polyline(points: &[Pos2], stroke: Stroke, rect: Rect, out: &mut Vec<Shape>) {
+    let mut run: Vec<Pos2> = Vec::new();
+    let mut flush = |run: &mut Vec<Pos2>| {
+        if run.len() >= 2 {
+            out.push(Shape::line(std::mem::take(run), stroke));
+        }
+        run.clear();
+    };
+    for w in points.windows(2) {
+        if let Some((a, b)) = clip_segment(w[0], w[1], rect) {
+            if run.last() != Some(&a) {
+                flush(&mut run);
+                run.push(a);
+            }
+            run.push(b);
+            if b != w[1] {
+                // The line leaves the rectangle here.
+                flush(&mut run);
+            }
+        } else {
+            flush(&mut run);
+        }
+    }
+    flush(&mut run);
+}
+
+/// Clips the segment from `a` to `b` to `rect` (Liang–Barsky). Unclipped end points are
+/// returned unchanged.
+fn clip_segment(a: Pos2, b: Pos2, rect: Rect) -> Option<(Pos2, Pos2)> {
+    let d = b - a;
+    let (mut t0, mut t1) = (0.0_f32, 1.0_f32);
+    for (p, q) in [
+        (-d.x, a.x - rect.min.x),
+        (d.x, rect.max.x - a.x),
+        (-d.y, a.y - rect.min.y),
+        (d.y, rect.max.y - a.y),
+    ] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else if p < 0.0 {
+            t0 = t0.max(q / p);
+        } else {
+            t1 = t1.min(q / p);
+        }
+    }
+    (t0 <= t1).then(|| {
+        let start = if t0 > 0.0 { a + t0 * d } else { a };
+        let end = if t1 < 1.0 { a + t1 * d } else { b };
+        (start, end)
+    })
+}
+
+/// Intersects the polygon with `rect` (Sutherland–Hodgman). Since the rectangle is convex, this
+/// works for any simple polygon, convex or not. Where a concave polygon leaves and re-enters the
+/// rectangle, its parts stay connected by edges along the border that enclose no area.
+fn clip_polygon(points: &[Pos2], rect: Rect) -> Vec<Pos2> {
+    let mut polygon = points.to_vec();
+    // Each edge of the rectangle as the axis it bounds, its coordinate and whether the inside is
+    // above that coordinate.
+    let edges = [
+        (0, rect.min.x, true),
+        (0, rect.max.x, false),
+        (1, rect.min.y, true),
+        (1, rect.max.y, false),
+    ];
+    for (axis, bound, above) in edges {
+        let inside = |p: Pos2| {
+            if above {
+                p[axis] >= bound
+            } else {
+                p[axis] <= bound
+            }
+        };
+        let intersection = |a: Pos2, b: Pos2| a + (bound - a[axis]) / (b[axis] - a[axis]) * (b - a);
+
+        let input = std::mem::take(&mut polygon);
+        for (i, &current) in input.iter().enumerate() {
+            let previous = input[(i + input.len() - 1) % input.len()];
+            match (inside(previous), inside(current)) {
+                (true, true) => polygon.push(current),
+                (true, false) => polygon.push(intersection(previous, current)),
+                (false, true) => {
+                    polygon.push(intersection(previous, current));
+                    polygon.push(current);
+                }
+                (false, false) => {}
+            }
+        }
+    }
+    polygon
+}
+
+/// Clips each triangle of the mesh to `rect`, interpolating the colors and texture coordinates
+/// of the new vertices.
+fn clip_mesh(mesh: &Mesh, rect: Rect) -> Mesh {
+    let mut clipped = Mesh::with_texture(mesh.texture_id);
+    for triangle in mesh.indices.chunks_exact(3) {
+        let v = [
+            mesh.vertices[triangle[0] as usize],
+            mesh.vertices[triangle[1] as usize],
+            mesh.vertices[triangle[2] as usize],
+        ];
+        let base = clipped.vertices.len() as u32;
+        if v.iter().all(|v| rect.contains(v.pos)) {
+            clipped.vertices.extend_from_slice(&v);
+            clipped.add_triangle(base, base + 1, base + 2);
+            continue;
+        }
+
+        // Twice the signed area of the triangle (a, b, c).
+        let edge =
+            |a: Pos2, b: Pos2, c: Pos2| (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x);
+        let area = edge(v[0].pos, v[1].pos, v[2].pos);
+        if area == 0.0 {
+            continue;
+        }
+        let polygon = clip_polygon(&[v[0].pos, v[1].pos, v[2].pos], rect);
+        if polygon.len() < 3 {
+            continue;
+        }
+        for pos in polygon.iter().copied() {
+            let w = [
+                edge(v[1].pos, v[2].pos, pos) / area,
+                edge(v[2].pos, v[0].pos, pos) / area,
+                edge(v[0].pos, v[1].pos, pos) / area,
+            ];
+            let uv = w[0] * v[0].uv.to_vec2() + w[1] * v[1].uv.to_vec2() + w[2] * v[2].uv.to_vec2();
+            let channel = |i: usize| {
+                (w[0] * v[0].color[i] as f32
+                    + w[1] * v[1].color[i] as f32
+                    + w[2] * v[2].color[i] as f32)
+                    .round() as u8
+            };
+            clipped.vertices.push(Vertex {
+                pos,
+                uv: uv.to_pos2(),
+                color: Color32::from_rgba_premultiplied(
+                    channel(0),
+                    channel(1),
+                    channel(2),
+                    channel(3),
+                ),
+            });
+        }
+        for i in 1..polygon.len() as u32 - 1 {
+            clipped.add_triangle(base, base + i, base + i + 1);
+        }
+    }
+    clipped
+}
+
+#[test]
+fn test_clip_polyline() {
+    use epaint::{pos2, Color32};
+
+    let rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(10.0, 10.0));
+    let points = [
+        pos2(-5.0, 5.0),
+        pos2(5.0, 5.0),
+        pos2(15.0, 5.0),
+        pos2(15.0, 8.0),
+        pos2(5.0, 8.0),
+    ];
+    let mut out = Vec::new();
+    clip_polyline(&points, Stroke::new(1.0, Color32::RED), rect, &mut out);
+
+    let runs: Vec<Vec<Pos2>> = out
+        .into_iter()
+        .map(|shape| match shape {
+            Shape::Path(path) => path.points,
+            _ => panic!("expected a path"),
+        })
+        .collect();
+    assert_eq!(
+        runs,
+        vec![
+            vec![pos2(0.0, 5.0), pos2(5.0, 5.0), pos2(10.0, 5.0)],
+            vec![pos2(10.0, 8.0), pos2(5.0, 8.0)],
+        ]
+    );
+}
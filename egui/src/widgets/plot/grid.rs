@@ -0,0 +1,162 @@
+use crate::*;
+
+use super::{HoverSyncGroup, Legend, LinkedAxisGroup, Plot, PlotResponse, PlotUi};
+
+/// Lays out several plots in a grid of equally sized cells, e.g. for dashboards.
+///
+/// All plots share the same configuration, linked axes and legend, so that only the content of
+/// each cell needs to be given.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::plot::{LinkedAxisGroup, Line, PlotGrid, Value, Values};
+/// let group = LinkedAxisGroup::x();
+/// PlotGrid::new("my_grid", 2, 2)
+///     .height(300.0)
+///     .link_axis(group)
+///     .configure(|plot| plot.show_background(false))
+///     .show(ui, |row, column, plot_ui| {
+///         let slope = (row * 2 + column) as f64;
+///         let values = (0..10).map(|i| Value::new(i as f64, slope * i as f64));
+///         plot_ui.line(Line::new(Values::from_values_iter(values)));
+///     });
+/// # });
+/// ```
+pub struct PlotGrid {
+    id_source: Id,
+    rows: usize,
+    columns: usize,
+    spacing: Option<Vec2>,
+    height: Option<f32>,
+    linked_axes: Option<LinkedAxisGroup>,
+    hover_sync: Option<HoverSyncGroup>,
+    legend: Option<Legend>,
+    configure: Option<Box<dyn Fn(Plot) -> Plot>>,
+}
+
+impl PlotGrid {
+    /// A grid with the given number of rows and columns. Give a unique id for each grid within
+    /// the same `Ui`.
+    pub fn new(id_source: impl std::hash::Hash, rows: usize, columns: usize) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            rows,
+            columns,
+            spacing: None,
+            height: None,
+            linked_axes: None,
+            hover_sync: None,
+            legend: None,
+            configure: None,
+        }
+    }
+
+    /// The space between neighboring cells. Default: `Spacing::item_spacing` of the style.
+    pub fn spacing(mut self, spacing: impl Into<Vec2>) -> Self {
+        self.spacing = Some(spacing.into());
+        self
+    }
+
+    /// Total height of the grid. By default the grid fills the available height, or has square cells
+    /// if that is unbounded. The grid always fills the available width.
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// Link the axes of all plots of the grid, see [`Plot::link_axis`].
+    pub fn link_axis(mut self, group: LinkedAxisGroup) -> Self {
+        self.linked_axes = Some(group);
+        self
+    }
+
+    /// Synchronize the hover crosshair of all plots of the grid, see [`Plot::link_hover`].
+    pub fn link_hover(mut self, group: HoverSyncGroup) -> Self {
+        self.hover_sync = Some(group);
+        self
+    }
+
+    /// Show the same legend in each plot, see [`Plot::legend`].
+    pub fn legend(mut self, legend: Legend) -> Self {
+        self.legend = Some(legend);
+        self
+    }
+
+    /// Apply further configuration to each plot, e.g. `|plot| plot.show_axes([false, true])`.
+    pub fn configure(mut self, configure: impl Fn(Plot) -> Plot + 'static) -> Self {
+        self.configure = Some(Box::new(configure));
+        self
+    }
+
+    /// Show the plots, calling `build_fn` with the row and column of each cell to add its items.
+    /// Returns the responses of the plots, row by row.
+    pub fn show<R>(
+        self,
+        ui: &mut Ui,
+        mut build_fn: impl FnMut(usize, usize, &mut PlotUi) -> R,
+    ) -> Vec<PlotResponse<R>> {
+        let Self {
+            id_source,
+            rows,
+            columns,
+            spacing,
+            height,
+            linked_axes,
+            hover_sync,
+            legend,
+            configure,
+        } = self;
+
+        if rows == 0 || columns == 0 {
+            return Vec::new();
+        }
+
+        let spacing = spacing.unwrap_or(ui.spacing().item_spacing);
+        let available = ui.available_size_before_wrap();
+        let cell_width =
+            ((available.x - spacing.x * (columns - 1) as f32) / columns as f32).at_least(0.0);
+        let height = height.unwrap_or_else(|| {
+            if available.y.is_finite() {
+                available.y
+            } else {
+                // E.g. in an auto-sized layout: make the cells square.
+                cell_width * rows as f32 + spacing.y * (rows - 1) as f32
+            }
+        });
+        let cell_size = vec2(
+            cell_width,
+            ((height - spacing.y * (rows - 1) as f32) / rows as f32).at_least(0.0),
+        );
+        let origin = ui.available_rect_before_wrap().min;
+        ui.allocate_rect(
+            Rect::from_min_size(origin, vec2(available.x, height)),
+            Sense::hover(),
+        );
+
+        let mut responses = Vec::with_capacity(rows * columns);
+        for row in 0..rows {
+            for column in 0..columns {
+                let min = origin + vec2(column as f32, row as f32) * (cell_size + spacing);
+                let rect = Rect::from_min_size(min, cell_size);
+
+                let mut plot = Plot::new(id_source.with((row, column)));
+                if let Some(group) = &linked_axes {
+                    plot = plot.link_axis(group.clone());
+                }
+                if let Some(group) = &hover_sync {
+                    plot = plot.link_hover(group.clone());
+                }
+                if let Some(legend) = &legend {
+                    plot = plot.legend(legend.clone());
+                }
+                if let Some(configure) = &configure {
+                    plot = configure(plot);
+                }
+
+                responses
+                    .push(plot.show_in_rect(ui, rect, |plot_ui| build_fn(row, column, plot_ui)));
+            }
+        }
+        responses
+    }
+}
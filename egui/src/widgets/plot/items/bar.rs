@@ -1,7 +1,10 @@
 use crate::epaint::{Color32, RectShape, Shape, Stroke};
 use crate::plot::items::num_decimals_with_max_digits;
 
-use super::{add_rulers_and_text, highlighted_color, Orientation, PlotConfig, RectElement};
+use super::{
+    add_rulers_and_text, highlighted_color, FillPattern, HighlightStyle, Orientation, PlotConfig,
+    RectElement,
+};
 use crate::plot::{BarChart, ScreenTransform, Value};
 
 /// One bar in a [`BarChart`]. Potentially floating, allowing stacked bar charts.
@@ -31,6 +34,13 @@ pub struct Bar {
 
     /// Fill color
     pub fill: Color32,
+
+    /// Fill pattern, drawn in the stroke color
+    pub fill_pattern: FillPattern,
+
+    /// Whether the stroke or fill color was set explicitly, in which case the colors of the
+    /// [`BarChart`] are not applied to this bar
+    pub explicit_color: bool,
 }
 
 impl Bar {
@@ -50,6 +60,8 @@ impl Bar {
             bar_width: 0.5,
             stroke: Stroke::new(1.0, Color32::TRANSPARENT),
             fill: Color32::TRANSPARENT,
+            fill_pattern: FillPattern::Solid,
+            explicit_color: false,
         }
     }
 
@@ -63,12 +75,20 @@ impl Bar {
     /// Add a custom stroke.
     pub fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
         self.stroke = stroke.into();
+        self.explicit_color = true;
         self
     }
 
     /// Add a custom fill color.
     pub fn fill(mut self, color: impl Into<Color32>) -> Self {
         self.fill = color.into();
+        self.explicit_color = true;
+        self
+    }
+
+    /// Fill the bar with a pattern instead of a solid color. Default is `FillPattern::Solid`.
+    pub fn fill_pattern(mut self, pattern: FillPattern) -> Self {
+        self.fill_pattern = pattern;
         self
     }
 
@@ -117,28 +137,40 @@ impl Bar {
     pub(super) fn add_shapes(
         &self,
         transform: &ScreenTransform,
-        highlighted: bool,
+        highlight: Option<&HighlightStyle>,
         shapes: &mut Vec<Shape>,
     ) {
-        let (stroke, fill) = if highlighted {
-            highlighted_color(self.stroke, self.fill)
+        let (stroke, fill) = if let Some(highlight) = highlight {
+            highlighted_color(self.stroke, self.fill, highlight)
         } else {
             (self.stroke, self.fill)
         };
 
         let rect = transform.rect_from_values(&self.bounds_min(), &self.bounds_max());
-        let rect = Shape::Rect(RectShape {
-            rect,
-            corner_radius: 0.0,
-            fill,
-            stroke,
-        });
-
-        shapes.push(rect);
+        if self.fill_pattern == FillPattern::Solid {
+            shapes.push(Shape::Rect(RectShape {
+                rect,
+                corner_radius: 0.0,
+                fill,
+                stroke,
+            }));
+        } else {
+            let corners = [
+                rect.left_top(),
+                rect.right_top(),
+                rect.right_bottom(),
+                rect.left_bottom(),
+            ];
+            self.fill_pattern
+                .hatch(&corners, Stroke::new(1.0, stroke.color), shapes);
+            shapes.push(Shape::rect_stroke(rect, 0.0, stroke));
+        }
     }
 
+    /// The formatter of the `parent` gets the `original` bar, of which `self` is the displayed one.
     pub(super) fn add_rulers_and_text(
         &self,
+        original: &Bar,
         parent: &BarChart,
         plot: &PlotConfig<'_>,
         shapes: &mut Vec<Shape>,
@@ -146,7 +178,7 @@ impl Bar {
         let text: Option<String> = parent
             .element_formatter
             .as_ref()
-            .map(|fmt| fmt(self, parent));
+            .map(|fmt| fmt(original, parent));
 
         add_rulers_and_text(self, plot, text, shapes);
     }
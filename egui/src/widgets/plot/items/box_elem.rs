@@ -1,7 +1,9 @@
 use crate::epaint::{Color32, RectShape, Shape, Stroke};
 use crate::plot::items::num_decimals_with_max_digits;
 
-use super::{add_rulers_and_text, highlighted_color, Orientation, PlotConfig, RectElement};
+use super::{
+    add_rulers_and_text, highlighted_color, HighlightStyle, Orientation, PlotConfig, RectElement,
+};
 use crate::plot::{BoxPlot, ScreenTransform, Value};
 
 /// Contains the values of a single box in a box plot.
@@ -137,11 +139,11 @@ impl BoxElem {
     pub(super) fn add_shapes(
         &self,
         transform: &ScreenTransform,
-        highlighted: bool,
+        highlight: Option<&HighlightStyle>,
         shapes: &mut Vec<Shape>,
     ) {
-        let (stroke, fill) = if highlighted {
-            highlighted_color(self.stroke, self.fill)
+        let (stroke, fill) = if let Some(highlight) = highlight {
+            highlighted_color(self.stroke, self.fill, highlight)
         } else {
             (self.stroke, self.fill)
         };
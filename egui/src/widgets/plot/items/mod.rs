@@ -1,6 +1,9 @@
 //! Contains items that can be added to a plot.
 
+use std::borrow::Cow;
+use std::cell::RefCell;
 use std::ops::RangeInclusive;
+use std::sync::Arc;
 
 use epaint::util::FloatOrd;
 use epaint::Mesh;
@@ -9,11 +12,14 @@ use crate::*;
 
 use super::{HoverFormatter, HoverLine, PlotBounds, ScreenTransform};
 use rect_elem::*;
-use values::{ClosestElem, PlotGeometry};
+use values::PlotGeometry;
 
 pub use bar::Bar;
 pub use box_elem::{BoxElem, BoxSpread};
-pub use values::{LineStyle, MarkerShape, Orientation, Value, Values};
+pub use values::{
+    ClosestElem, FillPattern, FillSide, HighlightStyle, LineCap, LineStyle, MarkerShape,
+    Orientation, StepMode, Value, Values, ZeroLengthMode,
+};
 
 mod bar;
 mod box_elem;
@@ -21,13 +27,24 @@ mod rect_elem;
 mod values;
 
 const DEFAULT_FILL_ALPHA: f32 = 0.05;
+const DEFAULT_SPAN_ALPHA: f32 = 0.15;
+const DEFAULT_STACK_ALPHA: f32 = 0.5;
+
+/// Maps a relative value between `0.0` and `1.0` to a color.
+type ColormapFn = dyn Fn(f64) -> Color32;
+
+/// Draws a marker from its center, radius and color.
+type CustomMarkerFn = dyn Fn(Pos2, f32, Color32) -> Vec<Shape>;
 
 /// Container to pass-through several parameters related to plot visualization
 pub(super) struct PlotConfig<'a> {
     pub ui: &'a Ui,
     pub transform: &'a ScreenTransform,
+    /// The position of the pointer, in screen coordinates.
+    pub pointer: Pos2,
     pub hover_config: HoverConfig,
     pub hover_formatter: &'a HoverFormatter,
+    pub highlight_style: &'a HighlightStyle,
 }
 
 pub struct HoverConfig {
@@ -37,28 +54,49 @@ pub struct HoverConfig {
 
 /// Trait shared by things that can be drawn in the plot.
 pub(super) trait PlotItem {
-    fn get_shapes(&self, ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>);
+    fn get_shapes(
+        &self,
+        ui: &mut Ui,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+    );
+
+    /// Like [`Self::get_shapes`], but adds at most `max_shapes` shapes. Items that add shapes for
+    /// each value stop generating them once the limit is reached, see [`super::Plot::max_shapes`].
+    fn get_shapes_limited(
+        &self,
+        ui: &mut Ui,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+        max_shapes: usize,
+    ) {
+        let start = shapes.len();
+        self.get_shapes(ui, transform, highlight_style, shapes);
+        shapes.truncate(start.saturating_add(max_shapes));
+    }
+
     fn initialize(&mut self, x_range: RangeInclusive<f64>);
     fn name(&self) -> &str;
+    fn show_in_legend(&self) -> bool;
+    fn clip_data(&self) -> Option<PlotBounds>;
     fn color(&self) -> Color32;
     fn highlight(&mut self);
     fn highlighted(&self) -> bool;
     fn geometry(&self) -> PlotGeometry<'_>;
     fn get_bounds(&self) -> PlotBounds;
 
+    /// The values of items backed by [`Values`], e.g. lines and points.
+    fn values(&self) -> Option<&[Value]> {
+        None
+    }
+
     fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
         match self.geometry() {
             PlotGeometry::None => None,
 
-            PlotGeometry::Points(points) => points
-                .iter()
-                .enumerate()
-                .map(|(index, value)| {
-                    let pos = transform.position_from_value(value);
-                    let dist_sq = point.distance_sq(pos);
-                    ClosestElem { index, dist_sq }
-                })
-                .min_by_key(|e| e.dist_sq.ord()),
+            PlotGeometry::Points(points) => find_closest_value(points, point, transform),
 
             PlotGeometry::Rects => {
                 panic!("If the PlotItem is made of rects, it should implement find_closest()")
@@ -90,6 +128,61 @@ pub(super) trait PlotItem {
 
         rulers_at_value(pointer, value, self.name(), plot, shapes);
     }
+
+    /// The value of the element at the given screen x-coordinate, if any. Elements made of points
+    /// must be at most `max_dx` away. Used when listing all items under the pointer for
+    /// [`super::Plot::stacked_hover`].
+    fn value_at_x(&self, x: f32, max_dx: f32, transform: &ScreenTransform) -> Option<Value> {
+        match self.geometry() {
+            PlotGeometry::Points(points) => points
+                .iter()
+                .map(|value| (value, (transform.position_from_value(value).x - x).abs()))
+                .filter(|(_, dx)| *dx <= max_dx)
+                .min_by_key(|(_, dx)| dx.ord())
+                .map(|(value, _)| *value),
+            PlotGeometry::None | PlotGeometry::Rects => None,
+        }
+    }
+
+    /// Whether the value lies within an area marked by this item, e.g. a [`HSpan`]. If no item is
+    /// hovered, the names of such items are shown for the pointer.
+    fn contains_value(&self, _value: &Value) -> bool {
+        false
+    }
+
+    /// Whether the item is drawn behind all other items, e.g. a [`HSpan`].
+    fn in_background(&self) -> bool {
+        false
+    }
+
+    /// Whether the item is plotted against the secondary y axis, see [`super::YAxis`].
+    fn on_secondary_axis(&self) -> bool {
+        false
+    }
+
+    /// A hash of everything that decides the shapes of the item, if known. Items with a hash may
+    /// reuse their shapes from the last frame, see [`super::Plot::cache_shapes`].
+    fn content_hash(&self) -> Option<u64> {
+        None
+    }
+
+    /// The id reported for the element at `index`, see [`ClosestElem::user_id`].
+    fn user_id(&self, index: usize) -> u64 {
+        index as u64
+    }
+
+    /// The value of the element at `index` that is reported when it is hovered or clicked, see
+    /// [`super::PlotResponse::hovered_value`]. By default, the value at `index` of
+    /// [`Self::values`].
+    fn element_value(&self, index: usize) -> Option<Value> {
+        self.values()?.get(index).copied()
+    }
+
+    /// The screen rect of items that label a position, e.g. a [`Text`], for
+    /// [`super::Plot::declutter_labels`].
+    fn label_rect(&self, _ui: &Ui, _transform: &ScreenTransform) -> Option<Rect> {
+        None
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -100,6 +193,8 @@ pub struct HLine {
     pub(super) y: f64,
     pub(super) stroke: Stroke,
     pub(super) name: String,
+    pub(super) show_in_legend: bool,
+    pub(super) clip_data: Option<PlotBounds>,
     pub(super) highlight: bool,
     pub(super) style: LineStyle,
 }
@@ -110,6 +205,8 @@ impl HLine {
             y: y.into(),
             stroke: Stroke::new(1.0, Color32::TRANSPARENT),
             name: String::default(),
+            show_in_legend: true,
+            clip_data: None,
             highlight: false,
             style: LineStyle::Solid,
         }
@@ -156,10 +253,30 @@ impl HLine {
         self.name = name.to_string();
         self
     }
+
+    /// Whether to show this item in the legend. Items that are not shown can still have a name,
+    /// which is used when hovering them. Default: `true`.
+    pub fn show_in_legend(mut self, show: bool) -> Self {
+        self.show_in_legend = show;
+        self
+    }
+
+    /// Only draw and hover the parts of this item within the given bounds in plot coordinates, in
+    /// addition to clipping it to the plot frame, e.g. to mask out a region. Default: `None`.
+    pub fn clip_data(mut self, bounds: Option<PlotBounds>) -> Self {
+        self.clip_data = bounds;
+        self
+    }
 }
 
 impl PlotItem for HLine {
-    fn get_shapes(&self, _ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
+    fn get_shapes(
+        &self,
+        _ui: &mut Ui,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+    ) {
         let HLine {
             y,
             stroke,
@@ -171,7 +288,7 @@ impl PlotItem for HLine {
             transform.position_from_value(&Value::new(transform.bounds().min[0], *y)),
             transform.position_from_value(&Value::new(transform.bounds().max[0], *y)),
         ];
-        style.style_line(points, *stroke, *highlight, shapes);
+        style.style_line(points, *stroke, highlight.then(|| highlight_style), shapes);
     }
 
     fn initialize(&mut self, _x_range: RangeInclusive<f64>) {}
@@ -180,6 +297,14 @@ impl PlotItem for HLine {
         &self.name
     }
 
+    fn show_in_legend(&self) -> bool {
+        self.show_in_legend
+    }
+
+    fn clip_data(&self) -> Option<PlotBounds> {
+        self.clip_data
+    }
+
     fn color(&self) -> Color32 {
         self.stroke.color
     }
@@ -210,6 +335,8 @@ pub struct VLine {
     pub(super) x: f64,
     pub(super) stroke: Stroke,
     pub(super) name: String,
+    pub(super) show_in_legend: bool,
+    pub(super) clip_data: Option<PlotBounds>,
     pub(super) highlight: bool,
     pub(super) style: LineStyle,
 }
@@ -220,6 +347,8 @@ impl VLine {
             x: x.into(),
             stroke: Stroke::new(1.0, Color32::TRANSPARENT),
             name: String::default(),
+            show_in_legend: true,
+            clip_data: None,
             highlight: false,
             style: LineStyle::Solid,
         }
@@ -266,10 +395,30 @@ impl VLine {
         self.name = name.to_string();
         self
     }
+
+    /// Whether to show this item in the legend. Items that are not shown can still have a name,
+    /// which is used when hovering them. Default: `true`.
+    pub fn show_in_legend(mut self, show: bool) -> Self {
+        self.show_in_legend = show;
+        self
+    }
+
+    /// Only draw and hover the parts of this item within the given bounds in plot coordinates, in
+    /// addition to clipping it to the plot frame, e.g. to mask out a region. Default: `None`.
+    pub fn clip_data(mut self, bounds: Option<PlotBounds>) -> Self {
+        self.clip_data = bounds;
+        self
+    }
 }
 
 impl PlotItem for VLine {
-    fn get_shapes(&self, _ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
+    fn get_shapes(
+        &self,
+        _ui: &mut Ui,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+    ) {
         let VLine {
             x,
             stroke,
@@ -281,7 +430,7 @@ impl PlotItem for VLine {
             transform.position_from_value(&Value::new(*x, transform.bounds().min[1])),
             transform.position_from_value(&Value::new(*x, transform.bounds().max[1])),
         ];
-        style.style_line(points, *stroke, *highlight, shapes);
+        style.style_line(points, *stroke, highlight.then(|| highlight_style), shapes);
     }
 
     fn initialize(&mut self, _x_range: RangeInclusive<f64>) {}
@@ -290,6 +439,14 @@ impl PlotItem for VLine {
         &self.name
     }
 
+    fn show_in_legend(&self) -> bool {
+        self.show_in_legend
+    }
+
+    fn clip_data(&self) -> Option<PlotBounds> {
+        self.clip_data
+    }
+
     fn color(&self) -> Color32 {
         self.stroke.color
     }
@@ -314,67 +471,63 @@ impl PlotItem for VLine {
     }
 }
 
-/// A series of values forming a path.
-pub struct Line {
-    pub(super) series: Values,
+/// A horizontal band between two y-coordinates, filling the full width, e.g. to mark a range of
+/// acceptable values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HSpan {
+    pub(super) range: [f64; 2],
     pub(super) stroke: Stroke,
+    pub(super) fill_alpha: f32,
     pub(super) name: String,
+    pub(super) show_in_legend: bool,
+    pub(super) clip_data: Option<PlotBounds>,
     pub(super) highlight: bool,
-    pub(super) fill: Option<f32>,
-    pub(super) style: LineStyle,
 }
 
-impl Line {
-    pub fn new(series: Values) -> Self {
+impl HSpan {
+    /// A band between the two y-coordinates, in any order.
+    pub fn new(y0: impl Into<f64>, y1: impl Into<f64>) -> Self {
+        let (y0, y1) = (y0.into(), y1.into());
         Self {
-            series,
-            stroke: Stroke::new(1.0, Color32::TRANSPARENT),
-            name: Default::default(),
+            range: [y0.min(y1), y0.max(y1)],
+            stroke: Stroke::new(0.0, Color32::TRANSPARENT),
+            fill_alpha: DEFAULT_SPAN_ALPHA,
+            name: String::default(),
+            show_in_legend: true,
+            clip_data: None,
             highlight: false,
-            fill: None,
-            style: LineStyle::Solid,
         }
     }
 
-    /// Highlight this line in the plot by scaling up the line.
+    /// Highlight this band in the plot by making it more opaque.
     pub fn highlight(mut self, highlight: bool) -> Self {
         self.highlight = highlight;
         self
     }
 
-    /// Add a stroke.
+    /// Add a stroke along the edges of the band. By default, there is none.
     pub fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
         self.stroke = stroke.into();
         self
     }
 
-    /// Stroke width. A high value means the plot thickens.
-    pub fn width(mut self, width: impl Into<f32>) -> Self {
-        self.stroke.width = width.into();
-        self
-    }
-
-    /// Stroke color. Default is `Color32::TRANSPARENT` which means a color will be auto-assigned.
+    /// Color of the band and its edges. Default is `Color32::TRANSPARENT` which means a color will
+    /// be auto-assigned.
     pub fn color(mut self, color: impl Into<Color32>) -> Self {
         self.stroke.color = color.into();
         self
     }
 
-    /// Fill the area between this line and a given horizontal reference line.
-    pub fn fill(mut self, y_reference: impl Into<f32>) -> Self {
-        self.fill = Some(y_reference.into());
-        self
-    }
-
-    /// Set the line's style. Default is `LineStyle::Solid`.
-    pub fn style(mut self, style: LineStyle) -> Self {
-        self.style = style;
+    /// Alpha of the filled band. Default: `0.15`.
+    pub fn fill_alpha(mut self, alpha: impl Into<f32>) -> Self {
+        self.fill_alpha = alpha.into();
         self
     }
 
-    /// Name of this line.
+    /// Name of this band.
     ///
-    /// This name will show up in the plot legend, if legends are turned on.
+    /// This name will show up in the plot legend, if legends are turned on, and when hovering
+    /// the band.
     ///
     /// Multiple plot items may share the same name, in which case they will also share an entry in
     /// the legend.
@@ -383,81 +536,59 @@ impl Line {
         self.name = name.to_string();
         self
     }
-}
 
-/// Returns the x-coordinate of a possible intersection between a line segment from `p1` to `p2` and
-/// a horizontal line at the given y-coordinate.
-fn y_intersection(p1: &Pos2, p2: &Pos2, y: f32) -> Option<f32> {
-    ((p1.y > y && p2.y < y) || (p1.y < y && p2.y > y))
-        .then(|| ((y * (p1.x - p2.x)) - (p1.x * p2.y - p1.y * p2.x)) / (p1.y - p2.y))
+    /// Whether to show this item in the legend. Items that are not shown can still have a name,
+    /// which is used when hovering them. Default: `true`.
+    pub fn show_in_legend(mut self, show: bool) -> Self {
+        self.show_in_legend = show;
+        self
+    }
+
+    /// Only draw and hover the parts of this item within the given bounds in plot coordinates, in
+    /// addition to clipping it to the plot frame, e.g. to mask out a region. Default: `None`.
+    pub fn clip_data(mut self, bounds: Option<PlotBounds>) -> Self {
+        self.clip_data = bounds;
+        self
+    }
 }
 
-impl PlotItem for Line {
-    fn get_shapes(&self, _ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
-        let Self {
-            series,
-            stroke,
-            highlight,
-            mut fill,
-            style,
-            ..
-        } = self;
+impl PlotItem for HSpan {
+    fn get_shapes(
+        &self,
+        _ui: &mut Ui,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+    ) {
+        let mut rect = *transform.frame();
+        let (min, max) = (
+            transform.position_from_value(&Value::new(0.0, self.range[0])),
+            transform.position_from_value(&Value::new(0.0, self.range[1])),
+        );
+        rect.min.y = min.y.min(max.y);
+        rect.max.y = min.y.max(max.y);
+        span_shapes(
+            rect,
+            1,
+            self.stroke,
+            self.fill_alpha,
+            self.highlight.then(|| highlight_style),
+            shapes,
+        );
+    }
 
-        let values_tf: Vec<_> = series
-            .values
-            .iter()
-            .map(|v| transform.position_from_value(v))
-            .collect();
-        let n_values = values_tf.len();
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {}
 
-        // Fill the area between the line and a reference line, if required.
-        if n_values < 2 {
-            fill = None;
-        }
-        if let Some(y_reference) = fill {
-            let mut fill_alpha = DEFAULT_FILL_ALPHA;
-            if *highlight {
-                fill_alpha = (2.0 * fill_alpha).at_most(1.0);
-            }
-            let y = transform
-                .position_from_value(&Value::new(0.0, y_reference))
-                .y;
-            let fill_color = Rgba::from(stroke.color)
-                .to_opaque()
-                .multiply(fill_alpha)
-                .into();
-            let mut mesh = Mesh::default();
-            let expected_intersections = 20;
-            mesh.reserve_triangles((n_values - 1) * 2);
-            mesh.reserve_vertices(n_values * 2 + expected_intersections);
-            values_tf[0..n_values - 1].windows(2).for_each(|w| {
-                let i = mesh.vertices.len() as u32;
-                mesh.colored_vertex(w[0], fill_color);
-                mesh.colored_vertex(pos2(w[0].x, y), fill_color);
-                if let Some(x) = y_intersection(&w[0], &w[1], y) {
-                    let point = pos2(x, y);
-                    mesh.colored_vertex(point, fill_color);
-                    mesh.add_triangle(i, i + 1, i + 2);
-                    mesh.add_triangle(i + 2, i + 3, i + 4);
-                } else {
-                    mesh.add_triangle(i, i + 1, i + 2);
-                    mesh.add_triangle(i + 1, i + 2, i + 3);
-                }
-            });
-            let last = values_tf[n_values - 1];
-            mesh.colored_vertex(last, fill_color);
-            mesh.colored_vertex(pos2(last.x, y), fill_color);
-            shapes.push(Shape::Mesh(mesh));
-        }
-        style.style_line(values_tf, *stroke, *highlight, shapes);
+    fn name(&self) -> &str {
+        &self.name
     }
 
-    fn initialize(&mut self, x_range: RangeInclusive<f64>) {
-        self.series.generate_points(x_range);
+    fn show_in_legend(&self) -> bool {
+        self.show_in_legend
     }
 
-    fn name(&self) -> &str {
-        self.name.as_str()
+    fn clip_data(&self) -> Option<PlotBounds> {
+        self.clip_data
     }
 
     fn color(&self) -> Color32 {
@@ -473,76 +604,82 @@ impl PlotItem for Line {
     }
 
     fn geometry(&self) -> PlotGeometry<'_> {
-        PlotGeometry::Points(&self.series.values)
+        PlotGeometry::None
     }
 
     fn get_bounds(&self) -> PlotBounds {
-        self.series.get_bounds()
+        let mut bounds = PlotBounds::NOTHING;
+        bounds.min[1] = self.range[0];
+        bounds.max[1] = self.range[1];
+        bounds
+    }
+
+    fn contains_value(&self, value: &Value) -> bool {
+        (self.range[0]..=self.range[1]).contains(&value.y)
+    }
+
+    fn in_background(&self) -> bool {
+        true
     }
 }
 
-/// A convex polygon.
-pub struct Polygon {
-    pub(super) series: Values,
+/// A vertical band between two x-coordinates, filling the full height, e.g. to mark a period of
+/// time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VSpan {
+    pub(super) range: [f64; 2],
     pub(super) stroke: Stroke,
+    pub(super) fill_alpha: f32,
     pub(super) name: String,
+    pub(super) show_in_legend: bool,
+    pub(super) clip_data: Option<PlotBounds>,
     pub(super) highlight: bool,
-    pub(super) fill_alpha: f32,
-    pub(super) style: LineStyle,
 }
 
-impl Polygon {
-    pub fn new(series: Values) -> Self {
+impl VSpan {
+    /// A band between the two x-coordinates, in any order.
+    pub fn new(x0: impl Into<f64>, x1: impl Into<f64>) -> Self {
+        let (x0, x1) = (x0.into(), x1.into());
         Self {
-            series,
-            stroke: Stroke::new(1.0, Color32::TRANSPARENT),
-            name: Default::default(),
+            range: [x0.min(x1), x0.max(x1)],
+            stroke: Stroke::new(0.0, Color32::TRANSPARENT),
+            fill_alpha: DEFAULT_SPAN_ALPHA,
+            name: String::default(),
+            show_in_legend: true,
+            clip_data: None,
             highlight: false,
-            fill_alpha: DEFAULT_FILL_ALPHA,
-            style: LineStyle::Solid,
         }
     }
 
-    /// Highlight this polygon in the plot by scaling up the stroke and reducing the fill
-    /// transparency.
+    /// Highlight this band in the plot by making it more opaque.
     pub fn highlight(mut self, highlight: bool) -> Self {
         self.highlight = highlight;
         self
     }
 
-    /// Add a custom stroke.
+    /// Add a stroke along the edges of the band. By default, there is none.
     pub fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
         self.stroke = stroke.into();
         self
     }
 
-    /// Set the stroke width.
-    pub fn width(mut self, width: impl Into<f32>) -> Self {
-        self.stroke.width = width.into();
-        self
-    }
-
-    /// Stroke color. Default is `Color32::TRANSPARENT` which means a color will be auto-assigned.
+    /// Color of the band and its edges. Default is `Color32::TRANSPARENT` which means a color will
+    /// be auto-assigned.
     pub fn color(mut self, color: impl Into<Color32>) -> Self {
         self.stroke.color = color.into();
         self
     }
 
-    /// Alpha of the filled area.
+    /// Alpha of the filled band. Default: `0.15`.
     pub fn fill_alpha(mut self, alpha: impl Into<f32>) -> Self {
         self.fill_alpha = alpha.into();
         self
     }
 
-    /// Set the outline's style. Default is `LineStyle::Solid`.
-    pub fn style(mut self, style: LineStyle) -> Self {
-        self.style = style;
-        self
-    }
-
-    /// Name of this polygon.
+    /// Name of this band.
     ///
-    /// This name will show up in the plot legend, if legends are turned on.
+    /// This name will show up in the plot legend, if legends are turned on, and when hovering
+    /// the band.
     ///
     /// Multiple plot items may share the same name, in which case they will also share an entry in
     /// the legend.
@@ -551,43 +688,59 @@ impl Polygon {
         self.name = name.to_string();
         self
     }
-}
 
-impl PlotItem for Polygon {
-    fn get_shapes(&self, _ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
-        let Self {
-            series,
-            stroke,
-            highlight,
-            mut fill_alpha,
-            style,
-            ..
-        } = self;
+    /// Whether to show this item in the legend. Items that are not shown can still have a name,
+    /// which is used when hovering them. Default: `true`.
+    pub fn show_in_legend(mut self, show: bool) -> Self {
+        self.show_in_legend = show;
+        self
+    }
 
-        if *highlight {
-            fill_alpha = (2.0 * fill_alpha).at_most(1.0);
-        }
+    /// Only draw and hover the parts of this item within the given bounds in plot coordinates, in
+    /// addition to clipping it to the plot frame, e.g. to mask out a region. Default: `None`.
+    pub fn clip_data(mut self, bounds: Option<PlotBounds>) -> Self {
+        self.clip_data = bounds;
+        self
+    }
+}
 
-        let mut values_tf: Vec<_> = series
-            .values
-            .iter()
-            .map(|v| transform.position_from_value(v))
-            .collect();
+impl PlotItem for VSpan {
+    fn get_shapes(
+        &self,
+        _ui: &mut Ui,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+    ) {
+        let mut rect = *transform.frame();
+        let (min, max) = (
+            transform.position_from_value(&Value::new(self.range[0], 0.0)),
+            transform.position_from_value(&Value::new(self.range[1], 0.0)),
+        );
+        rect.min.x = min.x.min(max.x);
+        rect.max.x = min.x.max(max.x);
+        span_shapes(
+            rect,
+            0,
+            self.stroke,
+            self.fill_alpha,
+            self.highlight.then(|| highlight_style),
+            shapes,
+        );
+    }
 
-        let fill = Rgba::from(stroke.color).to_opaque().multiply(fill_alpha);
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {}
 
-        let shape = Shape::convex_polygon(values_tf.clone(), fill, Stroke::none());
-        shapes.push(shape);
-        values_tf.push(*values_tf.first().unwrap());
-        style.style_line(values_tf, *stroke, *highlight, shapes);
+    fn name(&self) -> &str {
+        &self.name
     }
 
-    fn initialize(&mut self, x_range: RangeInclusive<f64>) {
-        self.series.generate_points(x_range);
+    fn show_in_legend(&self) -> bool {
+        self.show_in_legend
     }
 
-    fn name(&self) -> &str {
-        self.name.as_str()
+    fn clip_data(&self) -> Option<PlotBounds> {
+        self.clip_data
     }
 
     fn color(&self) -> Color32 {
@@ -603,38 +756,1951 @@ impl PlotItem for Polygon {
     }
 
     fn geometry(&self) -> PlotGeometry<'_> {
-        PlotGeometry::Points(&self.series.values)
+        PlotGeometry::None
     }
 
     fn get_bounds(&self) -> PlotBounds {
-        self.series.get_bounds()
+        let mut bounds = PlotBounds::NOTHING;
+        bounds.min[0] = self.range[0];
+        bounds.max[0] = self.range[1];
+        bounds
+    }
+
+    fn contains_value(&self, value: &Value) -> bool {
+        (self.range[0]..=self.range[1]).contains(&value.x)
+    }
+
+    fn in_background(&self) -> bool {
+        true
     }
 }
 
-/// Text inside the plot.
-pub struct Text {
-    pub(super) text: WidgetText,
-    pub(super) position: Value,
+/// Draws a band filling `rect`, with a stroke along the two edges that bound it on `axis`.
+fn span_shapes(
+    rect: Rect,
+    axis: usize,
+    mut stroke: Stroke,
+    mut fill_alpha: f32,
+    highlight: Option<&HighlightStyle>,
+    shapes: &mut Vec<Shape>,
+) {
+    if let Some(highlight_style) = highlight {
+        fill_alpha = highlight_style.fill_alpha(fill_alpha);
+        stroke = highlight_style.stroke(stroke);
+    }
+    let fill = Rgba::from(stroke.color).to_opaque().multiply(fill_alpha);
+    shapes.push(Shape::rect_filled(rect, 0.0, fill));
+    if stroke.width > 0.0 {
+        let (mut a, mut b) = (rect.min, rect.max);
+        a[1 - axis] = rect.max[1 - axis];
+        b[1 - axis] = rect.min[1 - axis];
+        shapes.push(Shape::line_segment([rect.min, a], stroke));
+        shapes.push(Shape::line_segment([b, rect.max], stroke));
+    }
+}
+
+/// A series of values forming a path.
+pub struct Line {
+    pub(super) series: Values,
+    pub(super) stroke: Stroke,
     pub(super) name: String,
+    pub(super) show_in_legend: bool,
+    pub(super) clip_data: Option<PlotBounds>,
     pub(super) highlight: bool,
-    pub(super) color: Color32,
-    pub(super) anchor: Align2,
+    pub(super) fill: Option<f32>,
+    pub(super) fill_alpha: (f32, f32),
+    pub(super) signed_fill_colors: Option<(Color32, Color32)>,
+    pub(super) fill_where: Vec<(f64, FillSide, Color32)>,
+    pub(super) style: LineStyle,
+    pub(super) cap: LineCap,
+    pub(super) allow_vertical: bool,
+    pub(super) step_mode: StepMode,
+    pub(super) clip_to_range: bool,
+    pub(super) ghost: Option<(Values, f32)>,
+    pub(super) ghost_in_bounds: bool,
+    pub(super) value_transform: Option<Box<dyn Fn(Value) -> Value>>,
+    pub(super) secondary_axis: bool,
+    pub(super) downsample: bool,
+    pub(super) simplify: f32,
+    pub(super) content_hash: Option<u64>,
+    /// Whether the values are ordered by x, as determined by `initialize`.
+    pub(super) sorted_by_x: bool,
 }
 
-impl Text {
-    pub fn new(position: Value, text: impl Into<WidgetText>) -> Self {
+impl Line {
+    pub fn new(series: Values) -> Self {
         Self {
-            text: text.into(),
-            position,
+            series,
+            stroke: Stroke::new(1.0, Color32::TRANSPARENT),
             name: Default::default(),
+            show_in_legend: true,
+            clip_data: None,
             highlight: false,
-            color: Color32::TRANSPARENT,
-            anchor: Align2::CENTER_CENTER,
+            fill: None,
+            fill_alpha: (DEFAULT_FILL_ALPHA, DEFAULT_FILL_ALPHA),
+            signed_fill_colors: None,
+            fill_where: Vec::new(),
+            style: LineStyle::Solid,
+            cap: LineCap::Butt,
+            allow_vertical: true,
+            step_mode: StepMode::None,
+            clip_to_range: false,
+            ghost: None,
+            ghost_in_bounds: false,
+            value_transform: None,
+            secondary_axis: false,
+            downsample: false,
+            simplify: 0.0,
+            content_hash: None,
+            sorted_by_x: false,
         }
     }
 
-    /// Highlight this text in the plot by drawing a rectangle around it.
-    pub fn highlight(mut self, highlight: bool) -> Self {
+    /// A line through complex samples given as `(re, im)`, e.g. for a Nyquist diagram, see
+    /// [`Values::from_complex_reim`].
+    pub fn from_complex(samples: &[(f64, f64)]) -> Self {
+        Self::new(Values::from_complex_reim(samples))
+    }
+
+    /// Highlight this line in the plot by scaling up the line.
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Add a stroke.
+    pub fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.stroke = stroke.into();
+        self
+    }
+
+    /// Stroke width. A high value means the plot thickens.
+    pub fn width(mut self, width: impl Into<f32>) -> Self {
+        self.stroke.width = width.into();
+        self
+    }
+
+    /// Stroke color. Default is `Color32::TRANSPARENT` which means a color will be auto-assigned.
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.stroke.color = color.into();
+        self
+    }
+
+    /// Fill the area between this line and a given horizontal reference line, e.g. `0.0`, with a
+    /// translucent version of the stroke color.
+    ///
+    /// The fill is split where the line crosses the reference line, see
+    /// [`Self::signed_fill_colors`], and where the values are interrupted by NaN.
+    /// Hovering anywhere within the filled area shows the value of the line closest to the
+    /// pointer's x-coordinate.
+    pub fn fill(mut self, y_reference: impl Into<f32>) -> Self {
+        self.fill = Some(y_reference.into());
+        self
+    }
+
+    /// The opacity of the area of [`Self::fill`]. Default: `0.05`.
+    pub fn fill_alpha(mut self, alpha: impl Into<f32>) -> Self {
+        let alpha = alpha.into();
+        self.fill_alpha = (alpha, alpha);
+        self
+    }
+
+    /// Use different opacities for the filled area above and below the reference line, see
+    /// [`Self::fill_alpha`].
+    pub fn signed_fill_alpha(mut self, positive: impl Into<f32>, negative: impl Into<f32>) -> Self {
+        self.fill_alpha = (positive.into(), negative.into());
+        self
+    }
+
+    /// Use different colors for the filled area above and below the reference line.
+    /// Only has an effect if [`Self::fill`] is set. By default the stroke color is used for both.
+    pub fn signed_fill_colors(
+        mut self,
+        positive: impl Into<Color32>,
+        negative: impl Into<Color32>,
+    ) -> Self {
+        self.signed_fill_colors = Some((positive.into(), negative.into()));
+        self
+    }
+
+    /// Fill only the parts of the area between this line and the horizontal line at `threshold`
+    /// that are on the given side of it, e.g. to highlight where a signal exceeds a limit. The
+    /// crossings with the threshold are interpolated. Can be called multiple times, e.g. with
+    /// different colors for both sides, and is independent of [`Self::fill`].
+    pub fn fill_where(
+        mut self,
+        threshold: impl Into<f64>,
+        side: FillSide,
+        color: impl Into<Color32>,
+    ) -> Self {
+        self.fill_where.push((threshold.into(), side, color.into()));
+        self
+    }
+
+    /// Set the line's style. Default is `LineStyle::Solid`.
+    pub fn style(mut self, style: LineStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the shape of the ends of the line and of its dashes. Default is `LineCap::Butt`.
+    pub fn cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// Whether consecutive values with the same x-coordinate are drawn as vertical segments.
+    /// Default is `true`, which suits step-like and parametric data.
+    ///
+    /// If `false`, such values are collapsed into the last one of them, so that the line is a
+    /// proper function of x.
+    pub fn allow_vertical(mut self, allow_vertical: bool) -> Self {
+        self.allow_vertical = allow_vertical;
+        self
+    }
+
+    /// Draw the line as a staircase that holds each value horizontally, e.g. for discrete state
+    /// signals. The steps work with [`Self::fill`] and every [`LineStyle`], and hovering a step
+    /// reports the value that is drawn there. The steps are only drawn, hovered and clicked
+    /// values keep the indices of the given values. Default: [`StepMode::None`].
+    pub fn step_mode(mut self, mode: StepMode) -> Self {
+        self.step_mode = mode;
+        self
+    }
+
+    /// When there is more than one value per pixel column, only draw the first, lowest, highest
+    /// and last value of each column, e.g. for series with millions of values. This looks the
+    /// same as the full line, and hovering still reports the actual values. Default: `false`.
+    pub fn downsample(mut self, on: bool) -> Self {
+        self.downsample = on;
+        self
+    }
+
+    /// Drop vertices that deviate less than `tolerance` from the simplified line (Douglas–Peucker),
+    /// which also simplifies its [`Self::fill`], e.g. for smooth series with many nearly collinear
+    /// vertices. Hovering still reports the actual values.
+    ///
+    /// The tolerance is in screen points, see [`Polygon::simplify`]. Default: `0.0`, i.e. no
+    /// simplification.
+    pub fn simplify(mut self, tolerance: impl Into<f32>) -> Self {
+        self.simplify = tolerance.into();
+        self
+    }
+
+    /// Drop the values far outside of the visible x-range before drawing, keeping one value past
+    /// each edge so that the line still leaves the plot. This saves work for huge series when
+    /// zoomed in. Hovering only considers the remaining values. Default: `false`.
+    ///
+    /// Lines defined by a function are always only sampled within the visible range.
+    pub fn clip_to_range(mut self, clip_to_range: bool) -> Self {
+        self.clip_to_range = clip_to_range;
+        self
+    }
+
+    /// Draw a faded copy of `previous` data beneath this line, e.g. to compare with the data of a
+    /// previous frame. It uses the color and style of this line, with its opacity scaled by
+    /// `alpha`. The ghost shares the legend entry of this line, but is not hoverable.
+    pub fn ghost(mut self, previous: Values, alpha: impl Into<f32>) -> Self {
+        self.ghost = Some((previous, alpha.into()));
+        self
+    }
+
+    /// Whether the automatic plot bounds include the [`Self::ghost`]. Default: `false`.
+    pub fn ghost_in_bounds(mut self, include: bool) -> Self {
+        self.ghost_in_bounds = include;
+        self
+    }
+
+    /// Map each value before it is plotted, e.g. to show data stored in SI units in other units
+    /// without converting the series itself. The mapping also applies to the [`Self::ghost`].
+    ///
+    /// Everything about the line works on the mapped values, including its bounds and hovering,
+    /// i.e. the closest value to the pointer is found in the mapped space.
+    pub fn value_transform(mut self, transform: impl Fn(Value) -> Value + 'static) -> Self {
+        self.value_transform = Some(Box::new(transform));
+        self
+    }
+
+    /// Plot this line against the secondary y axis on the right, e.g. for a quantity with other
+    /// units than the rest of the plot. Default: `false`.
+    ///
+    /// The secondary axis has its own range that is fitted to its items, see
+    /// [`super::YAxis::Secondary`].
+    pub fn on_secondary_axis(mut self, on_secondary_axis: bool) -> Self {
+        self.secondary_axis = on_secondary_axis;
+        self
+    }
+
+    /// Identify the content of this line, i.e. its values and style, with a hash of the given
+    /// value, e.g. a version number of the data. While it stays the same, the line may reuse its
+    /// shapes from the last frame, see [`super::Plot::cache_shapes`].
+    pub fn content_hash(mut self, content: impl std::hash::Hash) -> Self {
+        self.content_hash = Some(epaint::util::hash(content));
+        self
+    }
+
+    /// Name of this line.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on.
+    ///
+    /// Multiple plot items may share the same name, in which case they will also share an entry in
+    /// the legend.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Whether to show this item in the legend. Items that are not shown can still have a name,
+    /// which is used when hovering them. Default: `true`.
+    pub fn show_in_legend(mut self, show: bool) -> Self {
+        self.show_in_legend = show;
+        self
+    }
+
+    /// Only draw and hover the parts of this item within the given bounds in plot coordinates, in
+    /// addition to clipping it to the plot frame, e.g. to mask out a region. Default: `None`.
+    pub fn clip_data(mut self, bounds: Option<PlotBounds>) -> Self {
+        self.clip_data = bounds;
+        self
+    }
+
+    /// Total length of the line path in data units.
+    ///
+    /// Lines defined by an explicit callback only have values once they have been added to a
+    /// plot, so their length is zero before that.
+    pub fn data_length(&self) -> f64 {
+        self.mapped_values()
+            .windows(2)
+            .map(|w| (w[1].x - w[0].x).hypot(w[1].y - w[0].y))
+            .sum()
+    }
+
+    /// Total length of the line path in screen points, using the given transform.
+    pub fn screen_length(&self, transform: &ScreenTransform) -> f32 {
+        self.mapped_values()
+            .windows(2)
+            .map(|w| {
+                transform
+                    .position_from_value(&w[0])
+                    .distance(transform.position_from_value(&w[1]))
+            })
+            .sum()
+    }
+
+    /// The point at the given fraction `t` (between 0 and 1) of the total path length, measured
+    /// in data units. Returns `None` if the line has no values.
+    pub fn point_at_fraction(&self, t: f64) -> Option<Value> {
+        let values = self.mapped_values();
+        let mut remaining = t.clamp(0.0, 1.0) * self.data_length();
+        for w in values.windows(2) {
+            let (a, b) = (w[0], w[1]);
+            let length = (b.x - a.x).hypot(b.y - a.y);
+            if length > 0.0 && remaining <= length {
+                let f = remaining / length;
+                return Some(Value::new(a.x + f * (b.x - a.x), a.y + f * (b.y - a.y)));
+            }
+            remaining -= length;
+        }
+        values.last().copied()
+    }
+
+    /// The number, minimum, maximum and sum of the y-coordinates of the values in the given pixel
+    /// column.
+    fn column_stats(&self, column: f32, transform: &ScreenTransform) -> (usize, f64, f64, f64) {
+        let column_of = |v: &Value| transform.position_from_value(v).x.floor();
+        let values = &self.series.values;
+        let bin: Box<dyn Iterator<Item = &Value>> = if self.sorted_by_x {
+            // The values in the column are contiguous.
+            let start = values.partition_point(|v| column_of(v) < column);
+            let end = start + values[start..].partition_point(|v| column_of(v) <= column);
+            Box::new(values[start..end].iter())
+        } else {
+            Box::new(values.iter().filter(|v| column_of(v) == column))
+        };
+        bin.fold(
+            (0, f64::INFINITY, f64::NEG_INFINITY, 0.0),
+            |(count, min, max, sum), v| (count + 1, min.min(v.y), max.max(v.y), sum + v.y),
+        )
+    }
+
+    /// The values with [`Self::value_transform`] applied, which `initialize` does only once the
+    /// line has been added to a plot.
+    fn mapped_values(&self) -> Cow<'_, [Value]> {
+        match &self.value_transform {
+            Some(value_transform) => self
+                .series
+                .values
+                .iter()
+                .map(|value| value_transform(*value))
+                .collect(),
+            None => Cow::Borrowed(&self.series.values),
+        }
+    }
+
+    /// The index of the value closest to the pointer's x-coordinate, if the pointer is within
+    /// the filled area between the line and its reference line.
+    fn find_closest_in_fill(&self, point: Pos2, transform: &ScreenTransform) -> Option<usize> {
+        let y_reference = self.fill?;
+        let y = transform
+            .position_from_value(&Value::new(0.0, y_reference))
+            .y;
+        if self.step_mode != StepMode::None {
+            return (0..self.series.values.len()).find(|&index| {
+                self.tread(index).map_or(false, |(start, end)| {
+                    let start = transform.position_from_value(&start);
+                    let end = transform.position_from_value(&end);
+                    let inside_x = point.x >= start.x.min(end.x) && point.x <= start.x.max(end.x);
+                    inside_x && point.y >= start.y.min(y) && point.y <= start.y.max(y)
+                })
+            });
+        }
+        self.series
+            .values
+            .windows(2)
+            .enumerate()
+            .find_map(|(i, w)| {
+                let p0 = transform.position_from_value(&w[0]);
+                let p1 = transform.position_from_value(&w[1]);
+                if p0.x == p1.x || point.x < p0.x.min(p1.x) || point.x > p0.x.max(p1.x) {
+                    return None;
+                }
+                let t = (point.x - p0.x) / (p1.x - p0.x);
+                let line_y = lerp(p0.y..=p1.y, t);
+                let inside = point.y >= line_y.min(y) && point.y <= line_y.max(y);
+                inside.then(|| if t < 0.5 { i } else { i + 1 })
+            })
+    }
+
+    /// If the segment starting at `index` is the tread of a staircase, i.e. a horizontal segment
+    /// next to a vertical one, the value at its end.
+    fn tread_end(&self, index: usize) -> Option<Value> {
+        let values = &self.series.values;
+        let (start, end) = (*values.get(index)?, *values.get(index + 1)?);
+        if start.y != end.y || start.x == end.x {
+            return None;
+        }
+        let riser_before = index > 0 && values[index - 1].x == start.x;
+        let riser_after = values.get(index + 2).map_or(false, |v| v.x == end.x);
+        (riser_before || riser_after).then(|| end)
+    }
+
+    /// The start and end of the staircase tread that holds the value at `index`, either drawn
+    /// by [`Self::step_mode`] or present in the values themselves.
+    fn tread(&self, index: usize) -> Option<(Value, Value)> {
+        let values = &self.series.values;
+        let value = *values.get(index)?;
+        let previous_x = index.checked_sub(1).map_or(value.x, |i| values[i].x);
+        let next_x = values.get(index + 1).map_or(value.x, |next| next.x);
+        let (start_x, end_x) = match self.step_mode {
+            StepMode::None => return self.tread_end(index).map(|end| (value, end)),
+            StepMode::Before => (previous_x, value.x),
+            StepMode::After => (value.x, next_x),
+            StepMode::Center => ((previous_x + value.x) / 2.0, (value.x + next_x) / 2.0),
+        };
+        (start_x != end_x).then(|| (Value::new(start_x, value.y), Value::new(end_x, value.y)))
+    }
+
+    /// The staircase tread below or above the pointer, so that hovering anywhere along a step
+    /// reports the value of that step.
+    fn find_closest_tread(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
+        (0..self.series.values.len())
+            .filter_map(|index| {
+                let (start, end) = self.tread(index)?;
+                let start = transform.position_from_value(&start);
+                let end = transform.position_from_value(&end);
+                let inside = point.x >= start.x.min(end.x) && point.x <= start.x.max(end.x);
+                inside.then(|| ClosestElem::new(index, (point.y - start.y).powi(2)))
+            })
+            .min_by_key(|e| e.dist_sq.ord())
+    }
+}
+
+/// Fills the area between the line through `points` and the horizontal line at the screen
+/// coordinate `y`, split where they intersect. `color_for` gives the color of the parts above
+/// (`true`) and below (`false`) the horizontal line, transparent parts are skipped. Segments
+/// with a non-finite end, e.g. from NaN values, are skipped as well.
+fn fill_to_level(points: &[Pos2], y: f32, color_for: impl Fn(bool) -> Color32, mesh: &mut Mesh) {
+    let add_triangle = |mesh: &mut Mesh, vertices: [Pos2; 3], color: Color32| {
+        if color != Color32::TRANSPARENT {
+            let i = mesh.vertices.len() as u32;
+            for vertex in vertices {
+                mesh.colored_vertex(vertex, color);
+            }
+            mesh.add_triangle(i, i + 1, i + 2);
+        }
+    };
+    points.windows(2).for_each(|w| {
+        let (p0, p1) = (w[0], w[1]);
+        if !(p0.x.is_finite() && p0.y.is_finite() && p1.x.is_finite() && p1.y.is_finite()) {
+            return;
+        }
+        // Screen coordinates have +Y down, so "above" the horizontal line means a smaller y.
+        if let Some(x) = y_intersection(&p0, &p1, y) {
+            let point = pos2(x, y);
+            add_triangle(mesh, [p0, pos2(p0.x, y), point], color_for(p0.y < y));
+            add_triangle(mesh, [point, p1, pos2(p1.x, y)], color_for(p1.y < y));
+        } else {
+            let color = color_for(p0.y + p1.y < 2.0 * y);
+            if color != Color32::TRANSPARENT {
+                let i = mesh.vertices.len() as u32;
+                mesh.colored_vertex(p0, color);
+                mesh.colored_vertex(pos2(p0.x, y), color);
+                mesh.colored_vertex(p1, color);
+                mesh.colored_vertex(pos2(p1.x, y), color);
+                mesh.add_triangle(i, i + 1, i + 2);
+                mesh.add_triangle(i + 1, i + 2, i + 3);
+            }
+        }
+    });
+}
+
+/// A mesh of the cells between consecutive `column_edges` and `row_edges`, in screen
+/// coordinates, colored by `color_of(column, row)`. Cells without a color are left out.
+pub(super) fn grid_mesh(
+    column_edges: &[f32],
+    row_edges: &[f32],
+    color_of: impl Fn(usize, usize) -> Option<Color32>,
+) -> Mesh {
+    let mut mesh = Mesh::default();
+    for (j, rows) in row_edges.windows(2).enumerate() {
+        for (i, columns) in column_edges.windows(2).enumerate() {
+            if let Some(color) = color_of(i, j) {
+                let cell = Rect::from_two_pos(pos2(columns[0], rows[0]), pos2(columns[1], rows[1]));
+                mesh.add_colored_rect(cell, color);
+            }
+        }
+    }
+    mesh
+}
+
+/// The `cells + 1` edges of equally sized cells between `start` and `end`.
+fn uniform_edges(start: f32, end: f32, cells: usize) -> Vec<f32> {
+    (0..=cells)
+        .map(|i| lerp(start..=end, i as f32 / cells as f32))
+        .collect()
+}
+
+/// The index of the cell between consecutive `edges` that contains `x`, if any.
+fn cell_index(edges: &[f32], x: f32) -> Option<usize> {
+    edges
+        .windows(2)
+        .position(|edge| x >= edge[0].min(edge[1]) && x <= edge[0].max(edge[1]))
+}
+
+/// The vertices of the polyline that are kept by the Douglas–Peucker algorithm, i.e. those that
+/// deviate at least `tolerance` from the simplified line. The end points are always kept.
+fn simplify_polyline(points: &[Pos2], tolerance: f32) -> Vec<Pos2> {
+    points
+        .iter()
+        .zip(kept_vertices(points, tolerance))
+        .filter(|(_, keep)| *keep)
+        .map(|(p, _)| *p)
+        .collect()
+}
+
+/// Like [`simplify_polyline`], but simplifies each run of finite points on its own and keeps the
+/// non-finite points that interrupt the line.
+fn simplify_runs(points: &[Pos2], tolerance: f32) -> Vec<Pos2> {
+    let mut simplified = Vec::with_capacity(points.len());
+    let mut start = 0;
+    for (i, p) in points.iter().enumerate() {
+        if !p.x.is_finite() || !p.y.is_finite() {
+            simplified.extend(simplify_polyline(&points[start..i], tolerance));
+            simplified.push(*p);
+            start = i + 1;
+        }
+    }
+    simplified.extend(simplify_polyline(&points[start..], tolerance));
+    simplified
+}
+
+/// For each vertex of the polyline, whether [`simplify_polyline`] keeps it.
+fn kept_vertices(points: &[Pos2], tolerance: f32) -> Vec<bool> {
+    if points.len() < 3 {
+        return vec![true; points.len()];
+    }
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+    let mut stack = vec![(0, points.len() - 1)];
+    while let Some((first, last)) = stack.pop() {
+        let (a, b) = (points[first], points[last]);
+        let distance = |p: Pos2| {
+            let ab = b - a;
+            let length = ab.length();
+            if length > 0.0 {
+                (ab.x * (p.y - a.y) - ab.y * (p.x - a.x)).abs() / length
+            } else {
+                p.distance(a)
+            }
+        };
+        let farthest = (first + 1..last)
+            .map(|i| (i, distance(points[i])))
+            .max_by_key(|(_, d)| d.ord());
+        if let Some((i, d)) = farthest {
+            if d >= tolerance {
+                keep[i] = true;
+                stack.push((first, i));
+                stack.push((i, last));
+            }
+        }
+    }
+    keep
+}
+
+/// Drops the points of the polyline that only belong to segments outside of `rect`, so that
+/// long lines that are mostly off-screen are cheap to draw. The parts that are kept are separated
+/// by a NaN point, which interrupts the line.
+pub(super) fn cull_polyline(points: &[Pos2], rect: Rect) -> Vec<Pos2> {
+    let visible = |a: Pos2, b: Pos2| {
+        a.is_finite() && b.is_finite() && Rect::from_two_pos(a, b).intersects(rect)
+    };
+    let mut culled = Vec::with_capacity(points.len());
+    for (i, &point) in points.iter().enumerate() {
+        let after = i + 1 < points.len() && visible(point, points[i + 1]);
+        let before = i > 0 && visible(points[i - 1], point);
+        if !before && after && culled.last().map_or(false, |p: &Pos2| p.is_finite()) {
+            culled.push(Pos2::new(f32::NAN, f32::NAN));
+        }
+        if before || after {
+            culled.push(point);
+        }
+    }
+    culled
+}
+
+/// Reduces each run of consecutive points in the same pixel column to its first, lowest, highest
+/// and last point, in their original order. Non-finite points are kept, so that they still
+/// interrupt the line.
+fn downsample_min_max(points: &[Pos2]) -> Vec<Pos2> {
+    let mut downsampled = Vec::new();
+    let mut start = 0;
+    while start < points.len() {
+        let column = points[start].x.floor();
+        let len = points[start..]
+            .iter()
+            .take_while(|p| p.x.floor() == column && p.y.is_finite())
+            .count();
+        if len <= 4 {
+            // Includes a single non-finite point, which never equals the column.
+            let len = len.max(1);
+            downsampled.extend_from_slice(&points[start..start + len]);
+            start += len;
+            continue;
+        }
+        let run = &points[start..start + len];
+        let lowest = (0..len).max_by_key(|&i| run[i].y.ord()).unwrap_or(0);
+        let highest = (0..len).min_by_key(|&i| run[i].y.ord()).unwrap_or(0);
+        let mut kept = [0, lowest, highest, len - 1];
+        kept.sort_unstable();
+        downsampled.extend(kept.iter().map(|&i| run[i]));
+        start += len;
+    }
+    downsampled
+}
+
+/// Returns the x-coordinate of a possible intersection between a line segment from `p1` to `p2` and
+/// a horizontal line at the given y-coordinate.
+fn y_intersection(p1: &Pos2, p2: &Pos2, y: f32) -> Option<f32> {
+    ((p1.y > y && p2.y < y) || (p1.y < y && p2.y > y))
+        .then(|| ((y * (p1.x - p2.x)) - (p1.x * p2.y - p1.y * p2.x)) / (p1.y - p2.y))
+}
+
+impl PlotItem for Line {
+    fn get_shapes(
+        &self,
+        _ui: &mut Ui,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+    ) {
+        let Self {
+            series,
+            stroke,
+            highlight,
+            mut fill,
+            fill_alpha,
+            signed_fill_colors,
+            fill_where,
+            style,
+            cap,
+            ghost,
+            downsample,
+            simplify,
+            step_mode,
+            ..
+        } = self;
+
+        if let Some((previous, alpha)) = ghost {
+            let ghost_tf: Vec<_> = previous
+                .values
+                .iter()
+                .map(|v| transform.position_from_value(v))
+                .collect();
+            let ghost_stroke = Stroke::new(stroke.width, stroke.color.linear_multiply(*alpha));
+            let mut ghost_shapes = Vec::new();
+            style.style_line(ghost_tf, ghost_stroke, None, &mut ghost_shapes);
+            cap.apply(&mut ghost_shapes);
+            shapes.extend(ghost_shapes);
+        }
+
+        let stepped;
+        let values = if *step_mode == StepMode::None {
+            &series.values
+        } else {
+            stepped = series.stepped(*step_mode);
+            &stepped
+        };
+        let mut values_tf: Vec<_> = values
+            .iter()
+            .map(|v| transform.position_from_value(v))
+            .collect();
+        if *downsample && values_tf.len() as f32 > transform.frame().width() {
+            values_tf = downsample_min_max(&values_tf);
+        }
+        if *simplify > 0.0 {
+            values_tf = simplify_runs(&values_tf, *simplify);
+        }
+        let frame = *transform.frame();
+        let line_stroke = if *highlight {
+            highlight_style.stroke(*stroke)
+        } else {
+            *stroke
+        };
+        let visible_line = cull_polyline(&values_tf, frame.expand(line_stroke.width + 2.0));
+        // A fill reaches across the whole height of the frame, so only cull it horizontally.
+        let values_tf = cull_polyline(
+            &values_tf,
+            Rect::from_x_y_ranges(
+                frame.expand(1.0).x_range(),
+                f32::NEG_INFINITY..=f32::INFINITY,
+            ),
+        );
+        let n_values = values_tf.len();
+
+        // Fill the area between the line and a reference line, if required.
+        if n_values < 2 {
+            fill = None;
+        }
+        if let Some(y_reference) = fill {
+            // A reference line far outside of the frame, e.g. when zoomed in, fills the frame
+            // just like one at its edge.
+            let y = transform
+                .position_from_value(&Value::new(0.0, y_reference))
+                .y
+                .clamp(frame.top() - 1.0, frame.bottom() + 1.0);
+            let (positive_color, negative_color) =
+                signed_fill_colors.unwrap_or((stroke.color, stroke.color));
+            let fill_color = |color: Color32, mut alpha: f32| -> Color32 {
+                if *highlight {
+                    alpha = highlight_style.fill_alpha(alpha);
+                }
+                Rgba::from(color).to_opaque().multiply(alpha).into()
+            };
+            let (positive_color, negative_color) = (
+                fill_color(positive_color, fill_alpha.0),
+                fill_color(negative_color, fill_alpha.1),
+            );
+
+            let mut mesh = Mesh::default();
+            let expected_intersections = 20;
+            mesh.reserve_triangles((n_values - 1) * 2);
+            mesh.reserve_vertices((n_values - 1) * 4 + expected_intersections);
+            let color_for = |above: bool| {
+                if above {
+                    positive_color
+                } else {
+                    negative_color
+                }
+            };
+            fill_to_level(&values_tf, y, color_for, &mut mesh);
+            shapes.push(Shape::Mesh(mesh));
+        }
+
+        // Fill the parts on one side of each threshold.
+        for (threshold, side, color) in fill_where {
+            let y = transform
+                .position_from_value(&Value::new(0.0, *threshold))
+                .y;
+            let mut color = *color;
+            if *highlight {
+                let rgba = Rgba::from(color);
+                color = rgba
+                    .to_opaque()
+                    .multiply(highlight_style.fill_alpha(rgba.a()))
+                    .into();
+            }
+            let color_for = |above: bool| {
+                if above == (*side == FillSide::Above) {
+                    color
+                } else {
+                    Color32::TRANSPARENT
+                }
+            };
+            let mut mesh = Mesh::default();
+            fill_to_level(&values_tf, y, color_for, &mut mesh);
+            shapes.push(Shape::Mesh(mesh));
+        }
+        // NaN values interrupt the line.
+        let mut line_shapes = Vec::new();
+        for run in visible_line.split(|p| !p.x.is_finite() || !p.y.is_finite()) {
+            style.style_line(
+                run.to_vec(),
+                *stroke,
+                highlight.then(|| highlight_style),
+                &mut line_shapes,
+            );
+        }
+        cap.apply(&mut line_shapes);
+        shapes.extend(line_shapes);
+    }
+
+    fn initialize(&mut self, x_range: RangeInclusive<f64>) {
+        if let Some((previous, _)) = &mut self.ghost {
+            previous.generate_points(x_range.clone());
+        }
+        self.series.generate_points(x_range.clone());
+        // Map the values once, everything after this works on the mapped values.
+        if let Some(value_transform) = self.value_transform.take() {
+            let map = |values: &mut Values| {
+                values
+                    .values
+                    .iter_mut()
+                    .for_each(|value| *value = value_transform(*value));
+            };
+            map(&mut self.series);
+            if let Some((previous, _)) = &mut self.ghost {
+                map(previous);
+            }
+        }
+        if self.clip_to_range {
+            self.series.clip_to_x_range(x_range);
+        }
+        if !self.allow_vertical {
+            self.series.remove_vertical_segments();
+        }
+        self.sorted_by_x = self.series.values.windows(2).all(|w| w[0].x <= w[1].x);
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn show_in_legend(&self) -> bool {
+        self.show_in_legend
+    }
+
+    fn clip_data(&self) -> Option<PlotBounds> {
+        self.clip_data
+    }
+
+    fn color(&self) -> Color32 {
+        self.stroke.color
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::Points(&self.series.values)
+    }
+
+    fn values(&self) -> Option<&[Value]> {
+        Some(&self.series.values)
+    }
+
+    fn on_secondary_axis(&self) -> bool {
+        self.secondary_axis
+    }
+
+    fn content_hash(&self) -> Option<u64> {
+        self.content_hash
+    }
+
+    fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
+        // The filled area is as hittable as the line itself.
+        if let Some(index) = self.find_closest_in_fill(point, transform) {
+            return Some(ClosestElem::new(index, 0.0));
+        }
+        let closest = find_closest_value(&self.series.values, point, transform);
+        match (closest, self.find_closest_tread(point, transform)) {
+            (Some(vertex), Some(tread)) if tread.dist_sq < vertex.dist_sq => Some(tread),
+            (closest, _) => closest,
+        }
+    }
+
+    fn on_hover(&self, elem: ClosestElem, shapes: &mut Vec<Shape>, plot: &PlotConfig<'_>) {
+        let value = self.series.values[elem.index];
+        let pointer = plot.transform.position_from_value(&value);
+        shapes.push(Shape::circle_filled(pointer, 3.0, rulers_color(plot.ui)));
+
+        // A hovered staircase step reports its value together with the x-span of its tread.
+        if let Some((start, end)) = self.tread(elem.index) {
+            shapes.push(Shape::line_segment(
+                [
+                    plot.transform.position_from_value(&start),
+                    plot.transform.position_from_value(&end),
+                ],
+                Stroke::new(3.0, rulers_color(plot.ui)),
+            ));
+            let rulers_only = PlotConfig {
+                ui: plot.ui,
+                transform: plot.transform,
+                pointer: plot.pointer,
+                hover_config: HoverConfig {
+                    hover_line: plot.hover_config.hover_line,
+                    show_hover_label: false,
+                },
+                hover_formatter: plot.hover_formatter,
+                highlight_style: plot.highlight_style,
+            };
+            rulers_at_value(pointer, value, self.name(), &rulers_only, shapes);
+            if plot.hover_config.show_hover_label {
+                let decimals = num_decimals_with_max_digits(start.x.abs().max(end.x.abs()), 6);
+                let text = format!(
+                    "{}\nstep: x = {:.*} … {:.*}",
+                    (plot.hover_formatter)(&plot.hover_config, self.name(), &value),
+                    decimals,
+                    start.x,
+                    decimals,
+                    end.x
+                );
+                hover_label(pointer, text, plot, shapes);
+            }
+            return;
+        }
+
+        // If there is more than one point in the hovered pixel column (e.g. for dense or
+        // downsampled data), also report the range of all of them.
+        let (count, min, max, sum) = self.column_stats(pointer.x.floor(), plot.transform);
+        if count < 2 {
+            rulers_at_value(pointer, value, self.name(), plot, shapes);
+            return;
+        }
+
+        let rulers_only = PlotConfig {
+            ui: plot.ui,
+            transform: plot.transform,
+            pointer: plot.pointer,
+            hover_config: HoverConfig {
+                hover_line: plot.hover_config.hover_line,
+                show_hover_label: false,
+            },
+            hover_formatter: plot.hover_formatter,
+            highlight_style: plot.highlight_style,
+        };
+        rulers_at_value(pointer, value, self.name(), &rulers_only, shapes);
+
+        if plot.hover_config.show_hover_label {
+            let mean = sum / count as f64;
+            let decimals = num_decimals_with_max_digits(min.abs().max(max.abs()), 6);
+            let text = format!(
+                "{}\n{} points: min = {:.*}, max = {:.*}, mean = {:.*}",
+                (plot.hover_formatter)(&plot.hover_config, self.name(), &value),
+                count,
+                decimals,
+                min,
+                decimals,
+                max,
+                decimals,
+                mean
+            );
+            hover_label(pointer, text, plot, shapes);
+        }
+    }
+
+    fn get_bounds(&self) -> PlotBounds {
+        // The bounds may be needed before the values are mapped in `initialize`.
+        let bounds_of = |values: &Values| match &self.value_transform {
+            Some(value_transform) => {
+                let mut bounds = PlotBounds::NOTHING;
+                for value in &values.values {
+                    bounds.extend_with(&value_transform(*value));
+                }
+                bounds
+            }
+            None => values.get_bounds(),
+        };
+        let mut bounds = bounds_of(&self.series);
+        if let Some((previous, _)) = self.ghost.as_ref().filter(|_| self.ghost_in_bounds) {
+            bounds.merge(&bounds_of(previous));
+        }
+        bounds
+    }
+}
+
+/// A convex polygon.
+pub struct Polygon {
+    pub(super) series: Values,
+    pub(super) stroke: Stroke,
+    pub(super) name: String,
+    pub(super) show_in_legend: bool,
+    pub(super) clip_data: Option<PlotBounds>,
+    pub(super) highlight: bool,
+    pub(super) fill_alpha: f32,
+    pub(super) fill_pattern: FillPattern,
+    pub(super) style: LineStyle,
+    pub(super) simplify: f32,
+}
+
+impl Polygon {
+    pub fn new(series: Values) -> Self {
+        Self {
+            series,
+            stroke: Stroke::new(1.0, Color32::TRANSPARENT),
+            name: Default::default(),
+            show_in_legend: true,
+            clip_data: None,
+            highlight: false,
+            fill_alpha: DEFAULT_FILL_ALPHA,
+            fill_pattern: FillPattern::Solid,
+            style: LineStyle::Solid,
+            simplify: 0.0,
+        }
+    }
+
+    /// Highlight this polygon in the plot by scaling up the stroke and reducing the fill
+    /// transparency.
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Add a custom stroke.
+    pub fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.stroke = stroke.into();
+        self
+    }
+
+    /// Set the stroke width.
+    pub fn width(mut self, width: impl Into<f32>) -> Self {
+        self.stroke.width = width.into();
+        self
+    }
+
+    /// Stroke color. Default is `Color32::TRANSPARENT` which means a color will be auto-assigned.
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.stroke.color = color.into();
+        self
+    }
+
+    /// Alpha of the filled area.
+    pub fn fill_alpha(mut self, alpha: impl Into<f32>) -> Self {
+        self.fill_alpha = alpha.into();
+        self
+    }
+
+    /// Fill the polygon with a pattern instead of a solid color. Default is `FillPattern::Solid`.
+    pub fn fill_pattern(mut self, pattern: FillPattern) -> Self {
+        self.fill_pattern = pattern;
+        self
+    }
+
+    /// Set the outline's style. Default is `LineStyle::Solid`.
+    pub fn style(mut self, style: LineStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Drop vertices that deviate less than `tolerance` from the simplified outline
+    /// (Douglas–Peucker), e.g. for detailed map regions with many nearly collinear vertices.
+    ///
+    /// The tolerance is in screen points rather than plot coordinates, so the outline is
+    /// simplified anew each frame and keeps its detail when zooming in. A tolerance below one
+    /// point makes no visible difference. Default: `0.0`, i.e. no simplification.
+    pub fn simplify(mut self, tolerance: impl Into<f32>) -> Self {
+        self.simplify = tolerance.into();
+        self
+    }
+
+    /// Name of this polygon.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on.
+    ///
+    /// Multiple plot items may share the same name, in which case they will also share an entry in
+    /// the legend.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Whether to show this item in the legend. Items that are not shown can still have a name,
+    /// which is used when hovering them. Default: `true`.
+    pub fn show_in_legend(mut self, show: bool) -> Self {
+        self.show_in_legend = show;
+        self
+    }
+
+    /// Only draw and hover the parts of this item within the given bounds in plot coordinates, in
+    /// addition to clipping it to the plot frame, e.g. to mask out a region. Default: `None`.
+    pub fn clip_data(mut self, bounds: Option<PlotBounds>) -> Self {
+        self.clip_data = bounds;
+        self
+    }
+}
+
+impl PlotItem for Polygon {
+    fn get_shapes(
+        &self,
+        _ui: &mut Ui,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+    ) {
+        let Self {
+            series,
+            stroke,
+            highlight,
+            mut fill_alpha,
+            fill_pattern,
+            style,
+            simplify,
+            ..
+        } = self;
+
+        if *highlight {
+            fill_alpha = highlight_style.fill_alpha(fill_alpha);
+        }
+
+        let mut values_tf: Vec<_> = series
+            .values
+            .iter()
+            .map(|v| transform.position_from_value(v))
+            .collect();
+        if *simplify > 0.0 && values_tf.len() > 3 {
+            // Simplify the closed outline, keeping the first vertex.
+            values_tf.push(values_tf[0]);
+            values_tf = simplify_polyline(&values_tf, *simplify);
+            values_tf.pop();
+        }
+
+        if *fill_pattern == FillPattern::Solid {
+            let fill = Rgba::from(stroke.color).to_opaque().multiply(fill_alpha);
+            let shape = Shape::convex_polygon(values_tf.clone(), fill, Stroke::none());
+            shapes.push(shape);
+        } else {
+            fill_pattern.hatch(&values_tf, Stroke::new(1.0, stroke.color), shapes);
+        }
+        values_tf.push(*values_tf.first().unwrap());
+        style.style_line(
+            values_tf,
+            *stroke,
+            highlight.then(|| highlight_style),
+            shapes,
+        );
+    }
+
+    fn initialize(&mut self, x_range: RangeInclusive<f64>) {
+        self.series.generate_points(x_range);
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn show_in_legend(&self) -> bool {
+        self.show_in_legend
+    }
+
+    fn clip_data(&self) -> Option<PlotBounds> {
+        self.clip_data
+    }
+
+    fn color(&self) -> Color32 {
+        self.stroke.color
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::Points(&self.series.values)
+    }
+
+    fn values(&self) -> Option<&[Value]> {
+        Some(&self.series.values)
+    }
+
+    fn get_bounds(&self) -> PlotBounds {
+        self.series.get_bounds()
+    }
+}
+
+/// A band between a lower and an upper value for each x-coordinate, e.g. the range of a sensor
+/// over time. Optionally, a line through the center of the band is drawn as well.
+pub struct Ribbon {
+    pub(super) lower: Vec<Value>,
+    pub(super) upper: Vec<Value>,
+    pub(super) stroke: Stroke,
+    pub(super) name: String,
+    pub(super) show_in_legend: bool,
+    pub(super) clip_data: Option<PlotBounds>,
+    pub(super) highlight: bool,
+    pub(super) fill_alpha: f32,
+    pub(super) center_line: bool,
+}
+
+impl Ribbon {
+    /// Create a ribbon from `(x, y_lo, y_hi)` tuples, ordered by x.
+    pub fn new(values: Vec<(f64, f64, f64)>) -> Self {
+        let (lower, upper) = values
+            .into_iter()
+            .map(|(x, y_lo, y_hi)| (Value::new(x, y_lo), Value::new(x, y_hi)))
+            .unzip();
+        Self {
+            lower,
+            upper,
+            stroke: Stroke::new(1.0, Color32::TRANSPARENT),
+            name: Default::default(),
+            show_in_legend: true,
+            clip_data: None,
+            highlight: false,
+            fill_alpha: DEFAULT_FILL_ALPHA,
+            center_line: false,
+        }
+    }
+
+    /// Highlight this ribbon in the plot by scaling up the center line and reducing the fill
+    /// transparency.
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Add a custom stroke, used for the center line.
+    pub fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.stroke = stroke.into();
+        self
+    }
+
+    /// Set the stroke width of the center line.
+    pub fn width(mut self, width: impl Into<f32>) -> Self {
+        self.stroke.width = width.into();
+        self
+    }
+
+    /// Stroke color. Default is `Color32::TRANSPARENT` which means a color will be auto-assigned.
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.stroke.color = color.into();
+        self
+    }
+
+    /// Alpha of the filled band.
+    pub fn fill_alpha(mut self, alpha: impl Into<f32>) -> Self {
+        self.fill_alpha = alpha.into();
+        self
+    }
+
+    /// Draw a line through the center of the band. Default is `false`.
+    pub fn center_line(mut self, center_line: bool) -> Self {
+        self.center_line = center_line;
+        self
+    }
+
+    /// Name of this ribbon.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on.
+    ///
+    /// Multiple plot items may share the same name, in which case they will also share an entry in
+    /// the legend.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Whether to show this item in the legend. Items that are not shown can still have a name,
+    /// which is used when hovering them. Default: `true`.
+    pub fn show_in_legend(mut self, show: bool) -> Self {
+        self.show_in_legend = show;
+        self
+    }
+
+    /// Only draw and hover the parts of this item within the given bounds in plot coordinates, in
+    /// addition to clipping it to the plot frame, e.g. to mask out a region. Default: `None`.
+    pub fn clip_data(mut self, bounds: Option<PlotBounds>) -> Self {
+        self.clip_data = bounds;
+        self
+    }
+}
+
+impl PlotItem for Ribbon {
+    fn get_shapes(
+        &self,
+        _ui: &mut Ui,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+    ) {
+        let Self {
+            lower,
+            upper,
+            stroke,
+            highlight,
+            mut fill_alpha,
+            center_line,
+            ..
+        } = self;
+
+        if lower.len() < 2 {
+            return;
+        }
+
+        if *highlight {
+            fill_alpha = highlight_style.fill_alpha(fill_alpha);
+        }
+        let fill = Rgba::from(stroke.color).to_opaque().multiply(fill_alpha);
+
+        let mut mesh = Mesh::default();
+        mesh.reserve_triangles((lower.len() - 1) * 2);
+        mesh.reserve_vertices(lower.len() * 2);
+        for (lo, hi) in lower.iter().zip(upper) {
+            mesh.colored_vertex(transform.position_from_value(lo), fill.into());
+            mesh.colored_vertex(transform.position_from_value(hi), fill.into());
+        }
+        for i in 0..(lower.len() as u32 - 1) {
+            mesh.add_triangle(2 * i, 2 * i + 1, 2 * i + 2);
+            mesh.add_triangle(2 * i + 1, 2 * i + 2, 2 * i + 3);
+        }
+        shapes.push(Shape::Mesh(mesh));
+
+        if *center_line {
+            let center = lower
+                .iter()
+                .zip(upper)
+                .map(|(lo, hi)| {
+                    transform.position_from_value(&Value::new(lo.x, (lo.y + hi.y) / 2.0))
+                })
+                .collect();
+            LineStyle::Solid.style_line(
+                center,
+                *stroke,
+                highlight.then(|| highlight_style),
+                shapes,
+            );
+        }
+    }
+
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {}
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn show_in_legend(&self) -> bool {
+        self.show_in_legend
+    }
+
+    fn clip_data(&self) -> Option<PlotBounds> {
+        self.clip_data
+    }
+
+    fn color(&self) -> Color32 {
+        self.stroke.color
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::None
+    }
+
+    fn get_bounds(&self) -> PlotBounds {
+        let mut bounds = PlotBounds::NOTHING;
+        self.lower
+            .iter()
+            .chain(&self.upper)
+            .for_each(|value| bounds.extend_with(value));
+        bounds
+    }
+
+    fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
+        self.lower
+            .iter()
+            .zip(&self.upper)
+            .enumerate()
+            .map(|(index, (lo, hi))| {
+                // Distance to the vertical extent of the band at this x-coordinate.
+                let lo = transform.position_from_value(lo);
+                let hi = transform.position_from_value(hi);
+                let dx = point.x - lo.x;
+                let dy = point.y - point.y.clamp(lo.y.min(hi.y), lo.y.max(hi.y));
+                let dist_sq = dx * dx + dy * dy;
+                ClosestElem::new(index, dist_sq)
+            })
+            .min_by_key(|e| e.dist_sq.ord())
+    }
+
+    fn on_hover(&self, elem: ClosestElem, shapes: &mut Vec<Shape>, plot: &PlotConfig<'_>) {
+        let line_color = rulers_color(plot.ui);
+        let lo = self.lower[elem.index];
+        let hi = self.upper[elem.index];
+        let lo_pointer = plot.transform.position_from_value(&lo);
+        let hi_pointer = plot.transform.position_from_value(&hi);
+        shapes.push(Shape::circle_filled(lo_pointer, 3.0, line_color));
+        shapes.push(Shape::circle_filled(hi_pointer, 3.0, line_color));
+
+        rulers_at_value(hi_pointer, hi, self.name(), plot, shapes);
+
+        // The vertical ruler is already shown for the upper value.
+        let hover_line = if plot.hover_config.hover_line.show_y_line() {
+            HoverLine::Y
+        } else {
+            HoverLine::None
+        };
+        let lower_plot = PlotConfig {
+            ui: plot.ui,
+            transform: plot.transform,
+            pointer: plot.pointer,
+            hover_config: HoverConfig {
+                hover_line,
+                show_hover_label: plot.hover_config.show_hover_label,
+            },
+            hover_formatter: plot.hover_formatter,
+            highlight_style: plot.highlight_style,
+        };
+        rulers_at_value(lo_pointer, lo, self.name(), &lower_plot, shapes);
+    }
+}
+
+/// One layer of a stacked area chart, e.g. the usage of one CPU core. The layers are added
+/// together with [`super::PlotUi::stacked_area`], which stacks each layer on top of the ones
+/// before it.
+///
+/// The area between the top of the layers below and the sum of those and this layer's values is
+/// filled. The layers may be sampled at different x-coordinates: each layer is stacked on the
+/// linearly interpolated values of the layers below, which count as zero outside of their range.
+///
+/// Hiding a layer in the legend does not restack the layers above it.
+pub struct StackedArea {
+    pub(super) series: Values,
+    /// The top of the layers below, at the x-coordinate of each value.
+    pub(super) base: Vec<f64>,
+    pub(super) stroke: Stroke,
+    pub(super) name: String,
+    pub(super) show_in_legend: bool,
+    pub(super) clip_data: Option<PlotBounds>,
+    pub(super) highlight: bool,
+    pub(super) fill_alpha: f32,
+    pub(super) simplify: f32,
+}
+
+impl StackedArea {
+    /// A layer with the given values, ordered by x. Values given by an explicit callback are not
+    /// supported, as the layers are stacked before the callbacks are evaluated.
+    pub fn new(series: Values) -> Self {
+        Self {
+            series,
+            base: Vec::new(),
+            stroke: Stroke::new(1.0, Color32::TRANSPARENT),
+            name: Default::default(),
+            show_in_legend: true,
+            clip_data: None,
+            highlight: false,
+            fill_alpha: DEFAULT_STACK_ALPHA,
+            simplify: 0.0,
+        }
+    }
+
+    /// Highlight this layer in the plot by scaling up its top line and reducing the fill
+    /// transparency.
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Add a custom stroke, used for the line along the top of the layer.
+    pub fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.stroke = stroke.into();
+        self
+    }
+
+    /// Set the stroke width of the line along the top of the layer.
+    pub fn width(mut self, width: impl Into<f32>) -> Self {
+        self.stroke.width = width.into();
+        self
+    }
+
+    /// Stroke color. Default is `Color32::TRANSPARENT` which means a color will be auto-assigned.
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.stroke.color = color.into();
+        self
+    }
+
+    /// Alpha of the filled area. Default: `0.5`.
+    pub fn fill_alpha(mut self, alpha: impl Into<f32>) -> Self {
+        self.fill_alpha = alpha.into();
+        self
+    }
+
+    /// Drop the values at which neither the top nor the bottom of the layer deviates at least
+    /// `tolerance` from its simplified outline (Douglas–Peucker).
+    ///
+    /// The tolerance is in screen points, see [`Polygon::simplify`]. Default: `0.0`, i.e. no
+    /// simplification.
+    pub fn simplify(mut self, tolerance: impl Into<f32>) -> Self {
+        self.simplify = tolerance.into();
+        self
+    }
+
+    /// Name of this layer.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on.
+    ///
+    /// Multiple plot items may share the same name, in which case they will also share an entry in
+    /// the legend.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Whether to show this item in the legend. Items that are not shown can still have a name,
+    /// which is used when hovering them. Default: `true`.
+    pub fn show_in_legend(mut self, show: bool) -> Self {
+        self.show_in_legend = show;
+        self
+    }
+
+    /// Only draw and hover the parts of this item within the given bounds in plot coordinates, in
+    /// addition to clipping it to the plot frame, e.g. to mask out a region. Default: `None`.
+    pub fn clip_data(mut self, bounds: Option<PlotBounds>) -> Self {
+        self.clip_data = bounds;
+        self
+    }
+
+    /// Stack this layer on the given layers below it, see [`Self::base`].
+    pub(super) fn stack_on(&mut self, layers_below: &[Vec<Value>]) {
+        self.base = self
+            .series
+            .values
+            .iter()
+            .map(|value| {
+                layers_below
+                    .iter()
+                    .map(|layer| interpolate_at(layer, value.x))
+                    .sum()
+            })
+            .collect();
+    }
+
+    /// The bottom and top of the layer at the value with the given index.
+    fn band(&self, index: usize) -> (Value, Value) {
+        let value = self.series.values[index];
+        let base = self.base.get(index).copied().unwrap_or(0.0);
+        (
+            Value::new(value.x, base),
+            Value::new(value.x, base + value.y),
+        )
+    }
+}
+
+/// The linearly interpolated y-coordinate of the values at `x`, which are ordered by x. Zero
+/// outside of their range.
+fn interpolate_at(values: &[Value], x: f64) -> f64 {
+    let (first, last) = match (values.first(), values.last()) {
+        (Some(first), Some(last)) if first.x <= x && x <= last.x => (first, last),
+        _ => return 0.0,
+    };
+    if x == last.x {
+        return last.y;
+    }
+    let i = values.partition_point(|value| value.x <= x).max(1);
+    let (a, b) = (values[i - 1], values[i]);
+    if b.x > a.x {
+        remap(x, a.x..=b.x, a.y..=b.y)
+    } else {
+        first.y
+    }
+}
+
+impl PlotItem for StackedArea {
+    fn get_shapes(
+        &self,
+        _ui: &mut Ui,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+    ) {
+        let len = self.series.values.len();
+        if len < 2 {
+            return;
+        }
+
+        let fill_alpha = if self.highlight {
+            highlight_style.fill_alpha(self.fill_alpha)
+        } else {
+            self.fill_alpha
+        };
+        let fill: Color32 = Rgba::from(self.stroke.color)
+            .to_opaque()
+            .multiply(fill_alpha)
+            .into();
+
+        let mut bands: Vec<(Pos2, Pos2)> = (0..len)
+            .map(|index| {
+                let (bottom, top) = self.band(index);
+                (
+                    transform.position_from_value(&bottom),
+                    transform.position_from_value(&top),
+                )
+            })
+            .collect();
+        if self.simplify > 0.0 {
+            let bottoms: Vec<_> = bands.iter().map(|(bottom, _)| *bottom).collect();
+            let tops: Vec<_> = bands.iter().map(|(_, top)| *top).collect();
+            let keep_bottoms = kept_vertices(&bottoms, self.simplify);
+            let keep_tops = kept_vertices(&tops, self.simplify);
+            bands = (0..len)
+                .filter(|&i| keep_bottoms[i] || keep_tops[i])
+                .map(|i| bands[i])
+                .collect();
+        }
+        for pair in bands.windows(2) {
+            let ((bottom_a, top_a), (bottom_b, top_b)) = (pair[0], pair[1]);
+            shapes.push(Shape::convex_polygon(
+                vec![bottom_a, top_a, top_b, bottom_b],
+                fill,
+                Stroke::none(),
+            ));
+        }
+
+        let top = bands.iter().map(|(_, top)| *top).collect();
+        LineStyle::Solid.style_line(
+            top,
+            self.stroke,
+            self.highlight.then(|| highlight_style),
+            shapes,
+        );
+    }
+
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {}
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn show_in_legend(&self) -> bool {
+        self.show_in_legend
+    }
+
+    fn clip_data(&self) -> Option<PlotBounds> {
+        self.clip_data
+    }
+
+    fn color(&self) -> Color32 {
+        self.stroke.color
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::None
+    }
+
+    fn values(&self) -> Option<&[Value]> {
+        Some(&self.series.values)
+    }
+
+    fn get_bounds(&self) -> PlotBounds {
+        let mut bounds = PlotBounds::NOTHING;
+        for index in 0..self.series.values.len() {
+            let (bottom, top) = self.band(index);
+            bounds.extend_with(&bottom);
+            bounds.extend_with(&top);
+        }
+        bounds
+    }
+
+    fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
+        (0..self.series.values.len())
+            .map(|index| {
+                // Distance to the vertical extent of the layer at this x-coordinate.
+                let (bottom, top) = self.band(index);
+                let bottom = transform.position_from_value(&bottom);
+                let top = transform.position_from_value(&top);
+                let dx = point.x - bottom.x;
+                let dy = point.y - point.y.clamp(bottom.y.min(top.y), bottom.y.max(top.y));
+                ClosestElem::new(index, dx * dx + dy * dy)
+            })
+            .min_by_key(|e| e.dist_sq.ord())
+    }
+
+    fn on_hover(&self, elem: ClosestElem, shapes: &mut Vec<Shape>, plot: &PlotConfig<'_>) {
+        let (_, top) = self.band(elem.index);
+        let raw = self.series.values[elem.index].y;
+        let pointer = plot.transform.position_from_value(&top);
+        shapes.push(Shape::circle_filled(pointer, 3.0, rulers_color(plot.ui)));
+
+        // The label shows the cumulative value, followed by the value of this layer.
+        let rulers_only = PlotConfig {
+            ui: plot.ui,
+            transform: plot.transform,
+            pointer: plot.pointer,
+            hover_config: HoverConfig {
+                hover_line: plot.hover_config.hover_line,
+                show_hover_label: false,
+            },
+            hover_formatter: plot.hover_formatter,
+            highlight_style: plot.highlight_style,
+        };
+        rulers_at_value(pointer, top, self.name(), &rulers_only, shapes);
+        if plot.hover_config.show_hover_label {
+            let mut text = (plot.hover_formatter)(&plot.hover_config, self.name(), &top);
+            text.push_str(&format!(
+                "\nlayer = {:.*}",
+                num_decimals_with_max_digits(raw, 6),
+                raw
+            ));
+            hover_label(pointer, text, plot, shapes);
+        }
+    }
+}
+
+/// A filled circle in plot coordinates, e.g. an error radius or a region of interest. The circle
+/// scales with the plot when zooming.
+///
+/// The radius is given in plot units on both axes, so the circle is drawn as an ellipse if the
+/// axes are scaled differently. Use [`super::Plot::data_aspect`] to keep it round.
+pub struct Circle {
+    pub(super) center: Value,
+    pub(super) radius: f64,
+    pub(super) stroke: Stroke,
+    pub(super) name: String,
+    pub(super) show_in_legend: bool,
+    pub(super) clip_data: Option<PlotBounds>,
+    pub(super) highlight: bool,
+    pub(super) fill_alpha: f32,
+}
+
+impl Circle {
+    pub fn new(center: Value, radius: impl Into<f64>) -> Self {
+        Self {
+            center,
+            radius: radius.into(),
+            stroke: Stroke::new(1.0, Color32::TRANSPARENT),
+            name: Default::default(),
+            show_in_legend: true,
+            clip_data: None,
+            highlight: false,
+            fill_alpha: DEFAULT_FILL_ALPHA,
+        }
+    }
+
+    /// Highlight this circle in the plot by scaling up the stroke and reducing the fill
+    /// transparency.
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Add a custom stroke.
+    pub fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.stroke = stroke.into();
+        self
+    }
+
+    /// Set the stroke width.
+    pub fn width(mut self, width: impl Into<f32>) -> Self {
+        self.stroke.width = width.into();
+        self
+    }
+
+    /// Stroke color. Default is `Color32::TRANSPARENT` which means a color will be auto-assigned.
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.stroke.color = color.into();
+        self
+    }
+
+    /// Alpha of the filled area.
+    pub fn fill_alpha(mut self, alpha: impl Into<f32>) -> Self {
+        self.fill_alpha = alpha.into();
+        self
+    }
+
+    /// Name of this circle.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on.
+    ///
+    /// Multiple plot items may share the same name, in which case they will also share an entry in
+    /// the legend.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Whether to show this item in the legend. Items that are not shown can still have a name,
+    /// which is used when hovering them. Default: `true`.
+    pub fn show_in_legend(mut self, show: bool) -> Self {
+        self.show_in_legend = show;
+        self
+    }
+
+    /// Only draw and hover the parts of this item within the given bounds in plot coordinates, in
+    /// addition to clipping it to the plot frame, e.g. to mask out a region. Default: `None`.
+    pub fn clip_data(mut self, bounds: Option<PlotBounds>) -> Self {
+        self.clip_data = bounds;
+        self
+    }
+
+    /// The radii of the circle on screen along the x and y axis.
+    fn screen_radii(&self, transform: &ScreenTransform) -> Vec2 {
+        let [dx, dy] = transform.dpos_dvalue();
+        vec2(
+            (self.radius * dx).abs() as f32,
+            (self.radius * dy).abs() as f32,
+        )
+    }
+}
+
+impl PlotItem for Circle {
+    fn get_shapes(
+        &self,
+        _ui: &mut Ui,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+    ) {
+        let Self {
+            center,
+            stroke,
+            highlight,
+            mut fill_alpha,
+            ..
+        } = self;
+
+        let mut stroke = *stroke;
+        if *highlight {
+            fill_alpha = highlight_style.fill_alpha(fill_alpha);
+            stroke = highlight_style.stroke(stroke);
+        }
+        let fill = Rgba::from(stroke.color).to_opaque().multiply(fill_alpha);
+
+        let center = transform.position_from_value(center);
+        let radii = self.screen_radii(transform);
+        let segments = (radii.max_elem() / 2.0).ceil().clamp(16.0, 256.0) as usize;
+        let points = (0..segments)
+            .map(|i| {
+                let angle = std::f32::consts::TAU * i as f32 / segments as f32;
+                center + radii * vec2(angle.cos(), angle.sin())
+            })
+            .collect();
+        shapes.push(Shape::convex_polygon(points, fill, stroke));
+    }
+
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {}
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn show_in_legend(&self) -> bool {
+        self.show_in_legend
+    }
+
+    fn clip_data(&self) -> Option<PlotBounds> {
+        self.clip_data
+    }
+
+    fn color(&self) -> Color32 {
+        self.stroke.color
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::Points(std::slice::from_ref(&self.center))
+    }
+
+    fn get_bounds(&self) -> PlotBounds {
+        let mut bounds = PlotBounds::NOTHING;
+        bounds.extend_with(&Value::new(
+            self.center.x - self.radius,
+            self.center.y - self.radius,
+        ));
+        bounds.extend_with(&Value::new(
+            self.center.x + self.radius,
+            self.center.y + self.radius,
+        ));
+        bounds
+    }
+
+    fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
+        // Distance to the outline along the ray from the center, zero inside the circle.
+        let center = transform.position_from_value(&self.center);
+        let radii = self.screen_radii(transform);
+        let delta = point - center;
+        let distance = delta.length();
+        let radius = if distance > 0.0 {
+            let dir = delta / distance;
+            1.0 / (dir.x / radii.x).hypot(dir.y / radii.y)
+        } else {
+            0.0
+        };
+        let dist = (distance - radius).at_least(0.0);
+        Some(ClosestElem::new(0, dist * dist))
+    }
+}
+
+/// Text inside the plot.
+pub struct Text {
+    pub(super) text: WidgetText,
+    pub(super) position: Value,
+    pub(super) name: String,
+    pub(super) show_in_legend: bool,
+    pub(super) clip_data: Option<PlotBounds>,
+    pub(super) highlight: bool,
+    pub(super) color: Color32,
+    pub(super) anchor: Align2,
+}
+
+impl Text {
+    pub fn new(position: Value, text: impl Into<WidgetText>) -> Self {
+        Self {
+            text: text.into(),
+            position,
+            name: Default::default(),
+            show_in_legend: true,
+            clip_data: None,
+            highlight: false,
+            color: Color32::TRANSPARENT,
+            anchor: Align2::CENTER_CENTER,
+        }
+    }
+
+    /// Highlight this text in the plot by drawing a rectangle around it.
+    pub fn highlight(mut self, highlight: bool) -> Self {
         self.highlight = highlight;
         self
     }
@@ -662,16 +2728,28 @@ impl Text {
         self.name = name.to_string();
         self
     }
-}
 
-impl PlotItem for Text {
-    fn get_shapes(&self, ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
-        let color = if self.color == Color32::TRANSPARENT {
-            ui.style().visuals.text_color()
-        } else {
-            self.color
-        };
+    /// Whether to show this item in the legend. Items that are not shown can still have a name,
+    /// which is used when hovering them. Default: `true`.
+    pub fn show_in_legend(mut self, show: bool) -> Self {
+        self.show_in_legend = show;
+        self
+    }
+
+    /// Only draw and hover the parts of this item within the given bounds in plot coordinates, in
+    /// addition to clipping it to the plot frame, e.g. to mask out a region. Default: `None`.
+    pub fn clip_data(mut self, bounds: Option<PlotBounds>) -> Self {
+        self.clip_data = bounds;
+        self
+    }
+}
 
+impl Text {
+    fn layout(
+        &self,
+        ui: &Ui,
+        transform: &ScreenTransform,
+    ) -> (crate::widget_text::WidgetTextGalley, Rect) {
         let galley =
             self.text
                 .clone()
@@ -681,6 +2759,25 @@ impl PlotItem for Text {
         let rect = self
             .anchor
             .anchor_rect(Rect::from_min_size(pos, galley.size()));
+        (galley, rect)
+    }
+}
+
+impl PlotItem for Text {
+    fn get_shapes(
+        &self,
+        ui: &mut Ui,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+    ) {
+        let color = if self.color == Color32::TRANSPARENT {
+            ui.style().visuals.text_color()
+        } else {
+            self.color
+        };
+
+        let (galley, rect) = self.layout(ui, transform);
 
         let mut text_shape = epaint::TextShape::new(rect.min, galley.galley);
         if !galley.galley_has_color {
@@ -692,7 +2789,7 @@ impl PlotItem for Text {
             shapes.push(Shape::rect_stroke(
                 rect.expand(2.0),
                 1.0,
-                Stroke::new(0.5, color),
+                highlight_style.outline(Stroke::new(0.5, color)),
             ));
         }
     }
@@ -703,6 +2800,14 @@ impl PlotItem for Text {
         self.name.as_str()
     }
 
+    fn show_in_legend(&self) -> bool {
+        self.show_in_legend
+    }
+
+    fn clip_data(&self) -> Option<PlotBounds> {
+        self.clip_data
+    }
+
     fn color(&self) -> Color32 {
         self.color
     }
@@ -724,12 +2829,220 @@ impl PlotItem for Text {
         bounds.extend_with(&self.position);
         bounds
     }
+
+    fn label_rect(&self, ui: &Ui, transform: &ScreenTransform) -> Option<Rect> {
+        Some(self.layout(ui, transform).1)
+    }
+}
+
+/// Text that follows a path, e.g. a label along a contour line. Each character is rotated to the
+/// direction of the path at its position.
+///
+/// The text is centered on the path at [`Self::position`]. On paths that are shorter than the
+/// text, the characters continue beyond the ends in the direction of the first and last segment.
+/// Sharp bends can make neighboring characters overlap or drift apart.
+pub struct PathText {
+    pub(super) series: Values,
+    pub(super) text: String,
+    pub(super) name: String,
+    pub(super) show_in_legend: bool,
+    pub(super) clip_data: Option<PlotBounds>,
+    pub(super) highlight: bool,
+    pub(super) color: Color32,
+    pub(super) position: f32,
+}
+
+impl PathText {
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn new(series: Values, text: impl ToString) -> Self {
+        Self {
+            series,
+            text: text.to_string(),
+            name: Default::default(),
+            show_in_legend: true,
+            clip_data: None,
+            highlight: false,
+            color: Color32::TRANSPARENT,
+            position: 0.5,
+        }
+    }
+
+    /// Highlight this text in the plot by also drawing the path it follows.
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Text color.
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Where to center the text, as a fraction (between 0 and 1) of the length of the path on
+    /// screen. Default is `0.5`.
+    pub fn position(mut self, position: f32) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Name of this text.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on.
+    ///
+    /// Multiple plot items may share the same name, in which case they will also share an entry in
+    /// the legend.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Whether to show this item in the legend. Items that are not shown can still have a name,
+    /// which is used when hovering them. Default: `true`.
+    pub fn show_in_legend(mut self, show: bool) -> Self {
+        self.show_in_legend = show;
+        self
+    }
+
+    /// Only draw and hover the parts of this item within the given bounds in plot coordinates, in
+    /// addition to clipping it to the plot frame, e.g. to mask out a region. Default: `None`.
+    pub fn clip_data(mut self, bounds: Option<PlotBounds>) -> Self {
+        self.clip_data = bounds;
+        self
+    }
+}
+
+impl PlotItem for PathText {
+    fn get_shapes(
+        &self,
+        ui: &mut Ui,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+    ) {
+        let color = if self.color == Color32::TRANSPARENT {
+            ui.style().visuals.text_color()
+        } else {
+            self.color
+        };
+
+        let mut path: Vec<Pos2> = self
+            .series
+            .values
+            .iter()
+            .map(|v| transform.position_from_value(v))
+            .collect();
+        if path.len() < 2 {
+            return;
+        }
+        // Keep the text upright by following the path from left to right.
+        if path[path.len() - 1].x < path[0].x {
+            path.reverse();
+        }
+
+        if self.highlight {
+            shapes.push(Shape::line(
+                path.clone(),
+                highlight_style.outline(Stroke::new(1.0, color)),
+            ));
+        }
+
+        let font_id = TextStyle::Small.resolve(ui.style());
+        let galley = ui
+            .fonts()
+            .layout_no_wrap(self.text.clone(), font_id.clone(), color);
+        let length: f32 = path.windows(2).map(|w| w[0].distance(w[1])).sum();
+        let start = self.position.clamp(0.0, 1.0) * length - galley.size().x / 2.0;
+
+        for glyph in galley.rows.iter().flat_map(|row| &row.glyphs) {
+            if glyph.chr.is_whitespace() {
+                continue;
+            }
+            let glyph_galley =
+                ui.fonts()
+                    .layout_no_wrap(glyph.chr.to_string(), font_id.clone(), color);
+            let (center, direction) =
+                point_along_path(&path, start + glyph.pos.x + glyph.size.x / 2.0);
+            let angle = direction.y.atan2(direction.x);
+            // Text shapes are rotated around their top left corner.
+            let pos = center - emath::Rot2::from_angle(angle) * (glyph_galley.size() / 2.0);
+            let mut text_shape = epaint::TextShape::new(pos, glyph_galley);
+            text_shape.angle = angle;
+            shapes.push(text_shape.into());
+        }
+    }
+
+    fn initialize(&mut self, x_range: RangeInclusive<f64>) {
+        self.series.generate_points(x_range);
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn show_in_legend(&self) -> bool {
+        self.show_in_legend
+    }
+
+    fn clip_data(&self) -> Option<PlotBounds> {
+        self.clip_data
+    }
+
+    fn color(&self) -> Color32 {
+        self.color
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::None
+    }
+
+    fn get_bounds(&self) -> PlotBounds {
+        self.series.get_bounds()
+    }
+}
+
+/// The point at the given distance along a path of at least two points, and the direction of the
+/// path there. Distances outside of the path extend its first or last segment.
+fn point_along_path(path: &[Pos2], distance: f32) -> (Pos2, Vec2) {
+    let mut remaining = distance;
+    let mut beyond_end = (path[0], Vec2::X);
+    for (i, w) in path.windows(2).enumerate() {
+        let delta = w[1] - w[0];
+        let length = delta.length();
+        if length <= 0.0 {
+            continue;
+        }
+        let direction = delta / length;
+        if remaining <= length || i == path.len() - 2 {
+            return (w[0] + remaining * direction, direction);
+        }
+        remaining -= length;
+        beyond_end = (w[1] + remaining * direction, direction);
+    }
+    beyond_end
+}
+
+/// The points in each cell of the [`Points::as_density`] grid, row by row from the bottom.
+struct DensityCells {
+    counts: Vec<usize>,
+    /// The index of the first point, which stands for the cell when hovering it.
+    first: Vec<Option<usize>>,
 }
 
 /// A set of points.
 pub struct Points {
     pub(super) series: Values,
-    pub(super) shape: MarkerShape,
+    pub(super) shape: Option<MarkerShape>,
+    pub(super) custom_marker: Option<Box<CustomMarkerFn>>,
     /// Color of the marker. `Color32::TRANSPARENT` means that it will be picked automatically.
     pub(super) color: Color32,
     /// Whether to fill the marker. Does not apply to all types.
@@ -737,27 +3050,60 @@ pub struct Points {
     /// The maximum extent of the marker from its center.
     pub(super) radius: f32,
     pub(super) name: String,
+    pub(super) show_in_legend: bool,
+    pub(super) clip_data: Option<PlotBounds>,
     pub(super) highlight: bool,
     pub(super) stems: Option<f32>,
+    pub(super) connect: bool,
+    pub(super) density: Option<([usize; 2], Box<ColormapFn>)>,
+    /// The cells of the [`Self::as_density`] grid and the transform they were counted for, so
+    /// that hovering reuses the counts of drawing.
+    density_cells: RefCell<Option<(ScreenTransform, Arc<DensityCells>)>>,
+    pub(super) ids: Vec<u64>,
+    pub(super) secondary_axis: bool,
+    pub(super) content_hash: Option<u64>,
 }
 
 impl Points {
     pub fn new(series: Values) -> Self {
         Self {
             series,
-            shape: MarkerShape::Circle,
+            shape: None,
+            custom_marker: None,
             color: Color32::TRANSPARENT,
             filled: true,
             radius: 1.0,
             name: Default::default(),
+            show_in_legend: true,
+            clip_data: None,
             highlight: false,
             stems: None,
+            connect: false,
+            density: None,
+            density_cells: Default::default(),
+            ids: Vec::new(),
+            secondary_axis: false,
+            content_hash: None,
         }
     }
 
-    /// Set the shape of the markers.
+    /// Set the shape of the markers. Default is `MarkerShape::Circle`, or the next shape of
+    /// [`super::Plot::auto_marker_cycle`] if set.
     pub fn shape(mut self, shape: MarkerShape) -> Self {
-        self.shape = shape;
+        self.shape = Some(shape);
+        self
+    }
+
+    /// Draw the markers with the given function instead of a [`MarkerShape`]. It receives the
+    /// center, radius and color of a marker and returns its shapes.
+    ///
+    /// The function is called once per visible point and frame, so keep it cheap when showing
+    /// many points.
+    pub fn custom_marker(
+        mut self,
+        draw: impl Fn(Pos2, f32, Color32) -> Vec<Shape> + 'static,
+    ) -> Self {
+        self.custom_marker = Some(Box::new(draw));
         self
     }
 
@@ -785,6 +3131,87 @@ impl Points {
         self
     }
 
+    /// Whether to connect consecutive points with a line of the marker color, for a "line with
+    /// markers" plot as a single item. Hovering still picks the markers. Default: `false`.
+    pub fn connect(mut self, connect: bool) -> Self {
+        self.connect = connect;
+        self
+    }
+
+    /// Give each point an id of your own, e.g. the key of a database row, in the order of the
+    /// values. It is reported for hovered and clicked points in [`super::PlotResponse`], so that
+    /// they can be mapped back to the data. Points without an id are identified by their index.
+    pub fn ids(mut self, ids: impl IntoIterator<Item = u64>) -> Self {
+        self.ids = ids.into_iter().collect();
+        self
+    }
+
+    /// Plot these points against the secondary y axis on the right, see
+    /// [`Line::on_secondary_axis`].
+    pub fn on_secondary_axis(mut self, on_secondary_axis: bool) -> Self {
+        self.secondary_axis = on_secondary_axis;
+        self
+    }
+
+    /// Identify the content of these points, i.e. their values and style, with a hash of the
+    /// given value, see [`Line::content_hash`].
+    pub fn content_hash(mut self, content: impl std::hash::Hash) -> Self {
+        self.content_hash = Some(epaint::util::hash(content));
+        self
+    }
+
+    /// Instead of a marker per point, show the number of points in each cell of a grid of
+    /// `bins` cells covering the visible bounds, e.g. for scatter data with millions of points.
+    /// `colormap` gives the color of a cell from its count relative to the fullest cell, between
+    /// `0.0` and `1.0`, e.g. [`viridis`]. Empty cells are not drawn. The grid follows panning and
+    /// zooming, and hovering a cell reports its count. The cells have the same size on screen,
+    /// also on logarithmic axes, see [`super::Plot::axis_scales`].
+    pub fn as_density(
+        mut self,
+        bins: [usize; 2],
+        colormap: impl Fn(f64) -> Color32 + 'static,
+    ) -> Self {
+        self.density = Some((bins, Box::new(colormap)));
+        self
+    }
+
+    /// The cell of the [`Self::as_density`] grid over the plot frame that contains the screen
+    /// position, if any. Binning on screen matches the drawn cells on any [`super::AxisScale`].
+    fn density_bin(bins: [usize; 2], frame: &Rect, pos: Pos2) -> Option<[usize; 2]> {
+        if bins.contains(&0) || !frame.contains(pos) {
+            return None;
+        }
+        let t = [
+            (pos.x - frame.left()) / frame.width(),
+            (frame.bottom() - pos.y) / frame.height(),
+        ];
+        Some([0, 1].map(|axis| ((t[axis] * bins[axis] as f32) as usize).min(bins[axis] - 1)))
+    }
+
+    /// The points in each cell of the [`Self::as_density`] grid over the frame.
+    fn density_cells(&self, bins: [usize; 2], transform: &ScreenTransform) -> Arc<DensityCells> {
+        let mut cached = self.density_cells.borrow_mut();
+        if let Some((cached_transform, cells)) = &*cached {
+            if cached_transform == transform {
+                return cells.clone();
+            }
+        }
+        let mut cells = DensityCells {
+            counts: vec![0; bins[0] * bins[1]],
+            first: vec![None; bins[0] * bins[1]],
+        };
+        for (index, value) in self.series.values.iter().enumerate() {
+            let pos = transform.position_from_value(value);
+            if let Some([i, j]) = Self::density_bin(bins, transform.frame(), pos) {
+                cells.counts[j * bins[0] + i] += 1;
+                cells.first[j * bins[0] + i].get_or_insert(index);
+            }
+        }
+        let cells = Arc::new(cells);
+        *cached = Some((transform.clone(), cells.clone()));
+        cells
+    }
+
     /// Set the maximum extent of the marker around its position.
     pub fn radius(mut self, radius: impl Into<f32>) -> Self {
         self.radius = radius.into();
@@ -802,10 +3229,29 @@ impl Points {
         self.name = name.to_string();
         self
     }
-}
 
-impl PlotItem for Points {
-    fn get_shapes(&self, _ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
+    /// Whether to show this item in the legend. Items that are not shown can still have a name,
+    /// which is used when hovering them. Default: `true`.
+    pub fn show_in_legend(mut self, show: bool) -> Self {
+        self.show_in_legend = show;
+        self
+    }
+
+    /// Only draw and hover the parts of this item within the given bounds in plot coordinates, in
+    /// addition to clipping it to the plot frame, e.g. to mask out a region. Default: `None`.
+    pub fn clip_data(mut self, bounds: Option<PlotBounds>) -> Self {
+        self.clip_data = bounds;
+        self
+    }
+
+    /// Adds the shapes of the points until there are `max_shapes` more shapes.
+    fn add_shapes(
+        &self,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+        max_shapes: usize,
+    ) {
         let sqrt_3 = 3f32.sqrt();
         let frac_sqrt_3_2 = 3f32.sqrt() / 2.0;
         let frac_1_sqrt_2 = 1.0 / 2f32.sqrt();
@@ -813,14 +3259,48 @@ impl PlotItem for Points {
         let Self {
             series,
             shape,
+            custom_marker,
             color,
             filled,
             mut radius,
             highlight,
             stems,
+            connect,
+            density,
             ..
         } = self;
 
+        if let Some((bins, colormap)) = density {
+            let cells = self.density_cells(*bins, transform);
+            let counts = &cells.counts;
+            let max = counts.iter().copied().max().unwrap_or(0).max(1) as f64;
+            let frame = transform.frame();
+            let column_edges = uniform_edges(frame.left(), frame.right(), bins[0]);
+            let row_edges = uniform_edges(frame.bottom(), frame.top(), bins[1]);
+            let mesh = grid_mesh(&column_edges, &row_edges, |i, j| {
+                let count = counts[j * bins[0] + i];
+                (count > 0).then(|| colormap(count as f64 / max))
+            });
+            shapes.push(Shape::Mesh(mesh));
+            return;
+        }
+
+        if *connect {
+            let mut line_stroke = Stroke::new(1.0, *color);
+            if *highlight {
+                line_stroke = highlight_style.stroke(line_stroke);
+            }
+            let points: Vec<_> = series
+                .values
+                .iter()
+                .map(|value| transform.position_from_value(value))
+                .collect();
+            let visible = cull_polyline(&points, transform.frame().expand(line_stroke.width));
+            for run in visible.split(|p| !p.is_finite()) {
+                shapes.push(Shape::line(run.to_vec(), line_stroke));
+            }
+        }
+
         let stroke_size = radius / 5.0;
 
         let default_stroke = Stroke::new(stroke_size, *color);
@@ -831,101 +3311,139 @@ impl PlotItem for Points {
         let fill = filled.then(|| *color).unwrap_or_default();
 
         if *highlight {
-            radius *= 2f32.sqrt();
-            stem_stroke.width *= 2.0;
+            radius = highlight_style.radius(radius);
+            stem_stroke = highlight_style.stroke(stem_stroke);
         }
 
         let y_reference =
             stems.map(|y| transform.position_from_value(&Value::new(0.0, y)).y as f32);
+        let shape = shape.unwrap_or(MarkerShape::Circle);
 
-        series
+        // Skip the markers outside of the frame. Their stems may still cross it.
+        let visible = transform.frame().expand(radius + stroke_size);
+        let end = shapes.len().saturating_add(max_shapes);
+        let centers = series
             .values
             .iter()
             .map(|value| transform.position_from_value(value))
-            .for_each(|center| {
-                let tf = |dx: f32, dy: f32| -> Pos2 { center + radius * vec2(dx, dy) };
+            .filter(|center| {
+                visible.contains(*center)
+                    || (y_reference.is_some() && visible.x_range().contains(&center.x))
+            });
+        for center in centers {
+            if shapes.len() >= end {
+                break;
+            }
+            let tf = |dx: f32, dy: f32| -> Pos2 { center + radius * vec2(dx, dy) };
 
-                if let Some(y) = y_reference {
-                    let stem = Shape::line_segment([center, pos2(center.x, y)], stem_stroke);
-                    shapes.push(stem);
-                }
+            if let Some(y) = y_reference {
+                let stem = Shape::line_segment([center, pos2(center.x, y)], stem_stroke);
+                shapes.push(stem);
+            }
+
+            if let Some(draw) = custom_marker {
+                shapes.extend(draw(center, radius, *color));
+                continue;
+            }
 
-                match shape {
-                    MarkerShape::Circle => {
-                        shapes.push(Shape::Circle(epaint::CircleShape {
-                            center,
-                            radius,
-                            fill,
-                            stroke,
-                        }));
-                    }
-                    MarkerShape::Diamond => {
-                        let points = vec![tf(1.0, 0.0), tf(0.0, -1.0), tf(-1.0, 0.0), tf(0.0, 1.0)];
-                        shapes.push(Shape::convex_polygon(points, fill, stroke));
-                    }
-                    MarkerShape::Square => {
-                        let points = vec![
-                            tf(frac_1_sqrt_2, frac_1_sqrt_2),
-                            tf(frac_1_sqrt_2, -frac_1_sqrt_2),
-                            tf(-frac_1_sqrt_2, -frac_1_sqrt_2),
-                            tf(-frac_1_sqrt_2, frac_1_sqrt_2),
-                        ];
-                        shapes.push(Shape::convex_polygon(points, fill, stroke));
-                    }
-                    MarkerShape::Cross => {
-                        let diagonal1 = [
-                            tf(-frac_1_sqrt_2, -frac_1_sqrt_2),
-                            tf(frac_1_sqrt_2, frac_1_sqrt_2),
-                        ];
-                        let diagonal2 = [
-                            tf(frac_1_sqrt_2, -frac_1_sqrt_2),
-                            tf(-frac_1_sqrt_2, frac_1_sqrt_2),
-                        ];
-                        shapes.push(Shape::line_segment(diagonal1, default_stroke));
-                        shapes.push(Shape::line_segment(diagonal2, default_stroke));
-                    }
-                    MarkerShape::Plus => {
-                        let horizontal = [tf(-1.0, 0.0), tf(1.0, 0.0)];
-                        let vertical = [tf(0.0, -1.0), tf(0.0, 1.0)];
-                        shapes.push(Shape::line_segment(horizontal, default_stroke));
-                        shapes.push(Shape::line_segment(vertical, default_stroke));
-                    }
-                    MarkerShape::Up => {
-                        let points =
-                            vec![tf(0.0, -1.0), tf(-0.5 * sqrt_3, 0.5), tf(0.5 * sqrt_3, 0.5)];
-                        shapes.push(Shape::convex_polygon(points, fill, stroke));
-                    }
-                    MarkerShape::Down => {
-                        let points = vec![
-                            tf(0.0, 1.0),
-                            tf(-0.5 * sqrt_3, -0.5),
-                            tf(0.5 * sqrt_3, -0.5),
-                        ];
-                        shapes.push(Shape::convex_polygon(points, fill, stroke));
-                    }
-                    MarkerShape::Left => {
-                        let points =
-                            vec![tf(-1.0, 0.0), tf(0.5, -0.5 * sqrt_3), tf(0.5, 0.5 * sqrt_3)];
-                        shapes.push(Shape::convex_polygon(points, fill, stroke));
-                    }
-                    MarkerShape::Right => {
-                        let points = vec![
-                            tf(1.0, 0.0),
-                            tf(-0.5, -0.5 * sqrt_3),
-                            tf(-0.5, 0.5 * sqrt_3),
-                        ];
-                        shapes.push(Shape::convex_polygon(points, fill, stroke));
-                    }
-                    MarkerShape::Asterisk => {
-                        let vertical = [tf(0.0, -1.0), tf(0.0, 1.0)];
-                        let diagonal1 = [tf(-frac_sqrt_3_2, 0.5), tf(frac_sqrt_3_2, -0.5)];
-                        let diagonal2 = [tf(-frac_sqrt_3_2, -0.5), tf(frac_sqrt_3_2, 0.5)];
-                        shapes.push(Shape::line_segment(vertical, default_stroke));
-                        shapes.push(Shape::line_segment(diagonal1, default_stroke));
-                        shapes.push(Shape::line_segment(diagonal2, default_stroke));
-                    }
+            match shape {
+                MarkerShape::Circle => {
+                    shapes.push(Shape::Circle(epaint::CircleShape {
+                        center,
+                        radius,
+                        fill,
+                        stroke,
+                    }));
                 }
-            });
+                MarkerShape::Diamond => {
+                    let points = vec![tf(1.0, 0.0), tf(0.0, -1.0), tf(-1.0, 0.0), tf(0.0, 1.0)];
+                    shapes.push(Shape::convex_polygon(points, fill, stroke));
+                }
+                MarkerShape::Square => {
+                    let points = vec![
+                        tf(frac_1_sqrt_2, frac_1_sqrt_2),
+                        tf(frac_1_sqrt_2, -frac_1_sqrt_2),
+                        tf(-frac_1_sqrt_2, -frac_1_sqrt_2),
+                        tf(-frac_1_sqrt_2, frac_1_sqrt_2),
+                    ];
+                    shapes.push(Shape::convex_polygon(points, fill, stroke));
+                }
+                MarkerShape::Cross => {
+                    let diagonal1 = [
+                        tf(-frac_1_sqrt_2, -frac_1_sqrt_2),
+                        tf(frac_1_sqrt_2, frac_1_sqrt_2),
+                    ];
+                    let diagonal2 = [
+                        tf(frac_1_sqrt_2, -frac_1_sqrt_2),
+                        tf(-frac_1_sqrt_2, frac_1_sqrt_2),
+                    ];
+                    shapes.push(Shape::line_segment(diagonal1, default_stroke));
+                    shapes.push(Shape::line_segment(diagonal2, default_stroke));
+                }
+                MarkerShape::Plus => {
+                    let horizontal = [tf(-1.0, 0.0), tf(1.0, 0.0)];
+                    let vertical = [tf(0.0, -1.0), tf(0.0, 1.0)];
+                    shapes.push(Shape::line_segment(horizontal, default_stroke));
+                    shapes.push(Shape::line_segment(vertical, default_stroke));
+                }
+                MarkerShape::Up => {
+                    let points = vec![tf(0.0, -1.0), tf(-0.5 * sqrt_3, 0.5), tf(0.5 * sqrt_3, 0.5)];
+                    shapes.push(Shape::convex_polygon(points, fill, stroke));
+                }
+                MarkerShape::Down => {
+                    let points = vec![
+                        tf(0.0, 1.0),
+                        tf(-0.5 * sqrt_3, -0.5),
+                        tf(0.5 * sqrt_3, -0.5),
+                    ];
+                    shapes.push(Shape::convex_polygon(points, fill, stroke));
+                }
+                MarkerShape::Left => {
+                    let points = vec![tf(-1.0, 0.0), tf(0.5, -0.5 * sqrt_3), tf(0.5, 0.5 * sqrt_3)];
+                    shapes.push(Shape::convex_polygon(points, fill, stroke));
+                }
+                MarkerShape::Right => {
+                    let points = vec![
+                        tf(1.0, 0.0),
+                        tf(-0.5, -0.5 * sqrt_3),
+                        tf(-0.5, 0.5 * sqrt_3),
+                    ];
+                    shapes.push(Shape::convex_polygon(points, fill, stroke));
+                }
+                MarkerShape::Asterisk => {
+                    let vertical = [tf(0.0, -1.0), tf(0.0, 1.0)];
+                    let diagonal1 = [tf(-frac_sqrt_3_2, 0.5), tf(frac_sqrt_3_2, -0.5)];
+                    let diagonal2 = [tf(-frac_sqrt_3_2, -0.5), tf(frac_sqrt_3_2, 0.5)];
+                    shapes.push(Shape::line_segment(vertical, default_stroke));
+                    shapes.push(Shape::line_segment(diagonal1, default_stroke));
+                    shapes.push(Shape::line_segment(diagonal2, default_stroke));
+                }
+            }
+        }
+        shapes.truncate(end);
+    }
+}
+
+impl PlotItem for Points {
+    fn get_shapes(
+        &self,
+        _ui: &mut Ui,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+    ) {
+        self.add_shapes(transform, highlight_style, shapes, usize::MAX);
+    }
+
+    fn get_shapes_limited(
+        &self,
+        _ui: &mut Ui,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+        max_shapes: usize,
+    ) {
+        self.add_shapes(transform, highlight_style, shapes, max_shapes);
     }
 
     fn initialize(&mut self, x_range: RangeInclusive<f64>) {
@@ -936,6 +3454,14 @@ impl PlotItem for Points {
         self.name.as_str()
     }
 
+    fn show_in_legend(&self) -> bool {
+        self.show_in_legend
+    }
+
+    fn clip_data(&self) -> Option<PlotBounds> {
+        self.clip_data
+    }
+
     fn color(&self) -> Color32 {
         self.color
     }
@@ -952,9 +3478,100 @@ impl PlotItem for Points {
         PlotGeometry::Points(&self.series.values)
     }
 
+    fn values(&self) -> Option<&[Value]> {
+        Some(&self.series.values)
+    }
+
+    fn user_id(&self, index: usize) -> u64 {
+        self.ids.get(index).copied().unwrap_or(index as u64)
+    }
+
+    fn element_value(&self, index: usize) -> Option<Value> {
+        // The elements of a density plot are the cells of its grid.
+        if self.density.is_some() {
+            return None;
+        }
+        self.series.values.get(index).copied()
+    }
+
+    fn on_secondary_axis(&self) -> bool {
+        self.secondary_axis
+    }
+
+    fn content_hash(&self) -> Option<u64> {
+        self.content_hash
+    }
+
     fn get_bounds(&self) -> PlotBounds {
         self.series.get_bounds()
     }
+
+    fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
+        let (bins, _) = match &self.density {
+            Some(density) => density,
+            None => return find_closest_value(&self.series.values, point, transform),
+        };
+        // The first point in the hovered cell stands for the cell.
+        let [i, j] = Self::density_bin(*bins, transform.frame(), point)?;
+        let index = self.density_cells(*bins, transform).first[j * bins[0] + i]?;
+        Some(ClosestElem::new(index, 0.0))
+    }
+
+    fn on_hover(&self, elem: ClosestElem, shapes: &mut Vec<Shape>, plot: &PlotConfig<'_>) {
+        let value = self.series.values[elem.index];
+        let bins = if let Some((bins, _)) = &self.density {
+            *bins
+        } else {
+            let pointer = plot.transform.position_from_value(&value);
+            shapes.push(Shape::circle_filled(pointer, 3.0, rulers_color(plot.ui)));
+            rulers_at_value(pointer, value, self.name(), plot, shapes);
+            return;
+        };
+
+        let frame = plot.transform.frame();
+        let pos = plot.transform.position_from_value(&value);
+        let [i, j] = match Self::density_bin(bins, frame, pos) {
+            Some(bin) => bin,
+            None => return,
+        };
+        let count = self.density_cells(bins, plot.transform).counts[j * bins[0] + i];
+        let cell_size = frame.size() / vec2(bins[0] as f32, bins[1] as f32);
+        let cell = Rect::from_min_size(
+            pos2(
+                frame.left() + i as f32 * cell_size.x,
+                frame.bottom() - (j + 1) as f32 * cell_size.y,
+            ),
+            cell_size,
+        );
+        shapes.push(Shape::rect_stroke(
+            cell,
+            0.0,
+            Stroke::new(1.0, rulers_color(plot.ui)),
+        ));
+
+        let pointer = cell.center();
+        let center = plot.transform.value_from_position(pointer);
+        let rulers_only = PlotConfig {
+            ui: plot.ui,
+            transform: plot.transform,
+            pointer: plot.pointer,
+            hover_config: HoverConfig {
+                hover_line: plot.hover_config.hover_line,
+                show_hover_label: false,
+            },
+            hover_formatter: plot.hover_formatter,
+            highlight_style: plot.highlight_style,
+        };
+        rulers_at_value(pointer, center, self.name(), &rulers_only, shapes);
+        if plot.hover_config.show_hover_label {
+            let text = format!(
+                "{}\n{} points",
+                (plot.hover_formatter)(&plot.hover_config, self.name(), &center),
+                count
+            );
+            hover_label(pointer, text, plot, shapes);
+        }
+    }
 }
 
 /// A set of arrows.
@@ -963,7 +3580,10 @@ pub struct Arrows {
     pub(super) tips: Values,
     pub(super) color: Color32,
     pub(super) name: String,
+    pub(super) show_in_legend: bool,
+    pub(super) clip_data: Option<PlotBounds>,
     pub(super) highlight: bool,
+    pub(super) zero_length: ZeroLengthMode,
 }
 
 impl Arrows {
@@ -973,7 +3593,10 @@ impl Arrows {
             tips,
             color: Color32::TRANSPARENT,
             name: Default::default(),
+            show_in_legend: true,
+            clip_data: None,
             highlight: false,
+            zero_length: ZeroLengthMode::Skip,
         }
     }
 
@@ -989,6 +3612,13 @@ impl Arrows {
         self
     }
 
+    /// How to draw arrows whose origin and tip coincide, which have no direction.
+    /// Default is `ZeroLengthMode::Skip`.
+    pub fn zero_length(mut self, mode: ZeroLengthMode) -> Self {
+        self.zero_length = mode;
+        self
+    }
+
     /// Name of this set of arrows.
     ///
     /// This name will show up in the plot legend, if legends are turned on.
@@ -1000,45 +3630,97 @@ impl Arrows {
         self.name = name.to_string();
         self
     }
+
+    /// Whether to show this item in the legend. Items that are not shown can still have a name,
+    /// which is used when hovering them. Default: `true`.
+    pub fn show_in_legend(mut self, show: bool) -> Self {
+        self.show_in_legend = show;
+        self
+    }
+
+    /// Only draw and hover the parts of this item within the given bounds in plot coordinates, in
+    /// addition to clipping it to the plot frame, e.g. to mask out a region. Default: `None`.
+    pub fn clip_data(mut self, bounds: Option<PlotBounds>) -> Self {
+        self.clip_data = bounds;
+        self
+    }
 }
 
-impl PlotItem for Arrows {
-    fn get_shapes(&self, _ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
+impl Arrows {
+    /// Adds the shapes of the arrows until there are `max_shapes` more shapes.
+    fn add_shapes(
+        &self,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+        max_shapes: usize,
+    ) {
         use crate::emath::*;
         let Self {
             origins,
             tips,
             color,
             highlight,
+            zero_length,
             ..
         } = self;
-        let stroke = Stroke::new(if *highlight { 2.0 } else { 1.0 }, *color);
-        origins
-            .values
-            .iter()
-            .zip(tips.values.iter())
-            .map(|(origin, tip)| {
-                (
-                    transform.position_from_value(origin),
-                    transform.position_from_value(tip),
-                )
-            })
-            .for_each(|(origin, tip)| {
-                let vector = tip - origin;
-                let rot = Rot2::from_angle(std::f32::consts::TAU / 10.0);
-                let tip_length = vector.length() / 4.0;
-                let tip = origin + vector;
-                let dir = vector.normalized();
-                shapes.push(Shape::line_segment([origin, tip], stroke));
-                shapes.push(Shape::line(
-                    vec![
-                        tip - tip_length * (rot.inverse() * dir),
-                        tip,
-                        tip - tip_length * (rot * dir),
-                    ],
-                    stroke,
-                ));
-            });
+        let mut stroke = Stroke::new(1.0, *color);
+        if *highlight {
+            stroke = highlight_style.stroke(stroke);
+        }
+        let end = shapes.len().saturating_add(max_shapes);
+        for (origin, tip) in origins.values.iter().zip(tips.values.iter()) {
+            if shapes.len() >= end {
+                break;
+            }
+            let origin = transform.position_from_value(origin);
+            let tip = transform.position_from_value(tip);
+            let vector = tip - origin;
+            if vector == Vec2::ZERO {
+                // Without a direction there is no arrow head to draw.
+                if *zero_length == ZeroLengthMode::Dot {
+                    shapes.push(Shape::circle_filled(origin, 2.0 * stroke.width, *color));
+                }
+                continue;
+            }
+            let rot = Rot2::from_angle(std::f32::consts::TAU / 10.0);
+            let tip_length = vector.length() / 4.0;
+            let tip = origin + vector;
+            let dir = vector.normalized();
+            shapes.push(Shape::line_segment([origin, tip], stroke));
+            shapes.push(Shape::line(
+                vec![
+                    tip - tip_length * (rot.inverse() * dir),
+                    tip,
+                    tip - tip_length * (rot * dir),
+                ],
+                stroke,
+            ));
+        }
+        shapes.truncate(end);
+    }
+}
+
+impl PlotItem for Arrows {
+    fn get_shapes(
+        &self,
+        _ui: &mut Ui,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+    ) {
+        self.add_shapes(transform, highlight_style, shapes, usize::MAX);
+    }
+
+    fn get_shapes_limited(
+        &self,
+        _ui: &mut Ui,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+        max_shapes: usize,
+    ) {
+        self.add_shapes(transform, highlight_style, shapes, max_shapes);
     }
 
     fn initialize(&mut self, _x_range: RangeInclusive<f64>) {
@@ -1051,6 +3733,14 @@ impl PlotItem for Arrows {
         self.name.as_str()
     }
 
+    fn show_in_legend(&self) -> bool {
+        self.show_in_legend
+    }
+
+    fn clip_data(&self) -> Option<PlotBounds> {
+        self.clip_data
+    }
+
     fn color(&self) -> Color32 {
         self.color
     }
@@ -1082,6 +3772,10 @@ pub struct PlotImage {
     pub(super) tint: Color32,
     pub(super) highlight: bool,
     pub(super) name: String,
+    pub(super) show_in_legend: bool,
+    pub(super) clip_data: Option<PlotBounds>,
+    pub(super) hoverable: bool,
+    pub(super) hover_text: Option<Box<dyn Fn(Pos2) -> String>>,
 }
 
 impl PlotImage {
@@ -1094,12 +3788,16 @@ impl PlotImage {
         Self {
             position: center_position,
             name: Default::default(),
+            show_in_legend: true,
+            clip_data: None,
             highlight: false,
             texture_id: texture_id.into(),
             uv: Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
             size: size.into(),
             bg_fill: Default::default(),
             tint: Color32::WHITE,
+            hoverable: false,
+            hover_text: None,
         }
     }
 
@@ -1121,60 +3819,380 @@ impl PlotImage {
         self
     }
 
-    /// Multiply image color with this. Default is WHITE (no tint).
-    pub fn tint(mut self, tint: impl Into<Color32>) -> Self {
-        self.tint = tint.into();
-        self
+    /// Multiply image color with this. Default is WHITE (no tint).
+    pub fn tint(mut self, tint: impl Into<Color32>) -> Self {
+        self.tint = tint.into();
+        self
+    }
+
+    /// Show where the pointer is within the image when hovering it, as texture coordinates in
+    /// the range of [`Self::uv`], formatted like other values. Multiply them by the size of the
+    /// texture to get pixel coordinates.
+    ///
+    /// A hovered image takes precedence over other items drawn on top of it, so this is off by
+    /// default.
+    pub fn hoverable(mut self, hoverable: bool) -> Self {
+        self.hoverable = hoverable;
+        self
+    }
+
+    /// Show a custom text when hovering the image, e.g. the value of the pixel under the pointer.
+    /// `text` is called with the texture coordinates of the pointer, see [`Self::hoverable`],
+    /// which this turns on.
+    pub fn hover_text(mut self, text: impl Fn(Pos2) -> String + 'static) -> Self {
+        self.hoverable = true;
+        self.hover_text = Some(Box::new(text));
+        self
+    }
+
+    /// Name of this image.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on.
+    ///
+    /// Multiple plot items may share the same name, in which case they will also share an entry in
+    /// the legend.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Whether to show this item in the legend. Items that are not shown can still have a name,
+    /// which is used when hovering them. Default: `true`.
+    pub fn show_in_legend(mut self, show: bool) -> Self {
+        self.show_in_legend = show;
+        self
+    }
+
+    /// Only draw and hover the parts of this item within the given bounds in plot coordinates, in
+    /// addition to clipping it to the plot frame, e.g. to mask out a region. Default: `None`.
+    pub fn clip_data(mut self, bounds: Option<PlotBounds>) -> Self {
+        self.clip_data = bounds;
+        self
+    }
+}
+
+impl PlotImage {
+    fn screen_rect(&self, transform: &ScreenTransform) -> Rect {
+        let Self { position, size, .. } = self;
+        let left_top = Value::new(
+            position.x as f32 - size.x / 2.0,
+            position.y as f32 - size.y / 2.0,
+        );
+        let right_bottom = Value::new(
+            position.x as f32 + size.x / 2.0,
+            position.y as f32 + size.y / 2.0,
+        );
+        let left_top_tf = transform.position_from_value(&left_top);
+        let right_bottom_tf = transform.position_from_value(&right_bottom);
+        Rect::from_two_pos(left_top_tf, right_bottom_tf)
+    }
+}
+
+impl PlotItem for PlotImage {
+    fn get_shapes(
+        &self,
+        ui: &mut Ui,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+    ) {
+        let Self {
+            texture_id,
+            uv,
+            size,
+            bg_fill,
+            tint,
+            highlight,
+            clip_data,
+            ..
+        } = self;
+        let rect = self.screen_rect(transform);
+        // The image is painted directly, so its data clip region is applied to the `ui`.
+        let frame_clip_rect = ui.clip_rect();
+        if let Some(bounds) = clip_data {
+            let [min_x, min_y] = bounds.min();
+            let [max_x, max_y] = bounds.max();
+            let clip_rect =
+                transform.rect_from_values(&Value::new(min_x, min_y), &Value::new(max_x, max_y));
+            ui.set_clip_rect(frame_clip_rect.intersect(clip_rect));
+        }
+        Image::new(*texture_id, *size)
+            .bg_fill(*bg_fill)
+            .tint(*tint)
+            .uv(*uv)
+            .paint_at(ui, rect);
+        ui.set_clip_rect(frame_clip_rect);
+        if *highlight {
+            shapes.push(Shape::rect_stroke(
+                rect,
+                0.0,
+                highlight_style.outline(Stroke::new(1.0, ui.visuals().strong_text_color())),
+            ));
+        }
+    }
+
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {}
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn show_in_legend(&self) -> bool {
+        self.show_in_legend
+    }
+
+    fn clip_data(&self) -> Option<PlotBounds> {
+        self.clip_data
+    }
+
+    fn color(&self) -> Color32 {
+        Color32::TRANSPARENT
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::None
+    }
+
+    fn get_bounds(&self) -> PlotBounds {
+        let mut bounds = PlotBounds::NOTHING;
+        let left_top = Value::new(
+            self.position.x as f32 - self.size.x / 2.0,
+            self.position.y as f32 - self.size.y / 2.0,
+        );
+        let right_bottom = Value::new(
+            self.position.x as f32 + self.size.x / 2.0,
+            self.position.y as f32 + self.size.y / 2.0,
+        );
+        bounds.extend_with(&left_top);
+        bounds.extend_with(&right_bottom);
+        bounds
+    }
+
+    fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
+        let inside = self.hoverable && self.screen_rect(transform).contains(point);
+        inside.then(|| ClosestElem::new(0, 0.0))
+    }
+
+    fn on_hover(&self, _elem: ClosestElem, shapes: &mut Vec<Shape>, plot: &PlotConfig<'_>) {
+        let rect = self.screen_rect(plot.transform);
+        let t = (plot.pointer - rect.min) / rect.size();
+        let uv = pos2(lerp(self.uv.x_range(), t.x), lerp(self.uv.y_range(), t.y));
+        let text = match &self.hover_text {
+            Some(hover_text) => hover_text(uv),
+            None => {
+                (plot.hover_formatter)(&plot.hover_config, self.name(), &Value::new(uv.x, uv.y))
+            }
+        };
+
+        let rulers_only = PlotConfig {
+            ui: plot.ui,
+            transform: plot.transform,
+            pointer: plot.pointer,
+            hover_config: HoverConfig {
+                hover_line: plot.hover_config.hover_line,
+                show_hover_label: false,
+            },
+            hover_formatter: plot.hover_formatter,
+            highlight_style: plot.highlight_style,
+        };
+        let value = plot.transform.value_from_position(plot.pointer);
+        rulers_at_value(plot.pointer, value, self.name(), &rulers_only, shapes);
+        if plot.hover_config.show_hover_label {
+            hover_label(plot.pointer, text, plot, shapes);
+        }
+    }
+}
+
+/// A heatmap of a matrix of values, e.g. a correlation matrix, drawn as a grid of cells colored
+/// by a colormap. Unlike [`PlotImage`], it is built from the values themselves, and hovering it
+/// reports the row, column and value of a cell.
+///
+/// Row `0` is at the top. By default each cell is one unit wide and high, with the lower left
+/// corner of the grid at the origin.
+pub struct HeatMap {
+    pub(super) values: Vec<f64>,
+    pub(super) rows: usize,
+    pub(super) columns: usize,
+    pub(super) position: Value,
+    pub(super) cell_size: [f64; 2],
+    pub(super) colormap: Box<ColormapFn>,
+    pub(super) value_range: Option<RangeInclusive<f64>>,
+    pub(super) name: String,
+    pub(super) show_in_legend: bool,
+    pub(super) clip_data: Option<PlotBounds>,
+    pub(super) highlight: bool,
+}
+
+impl HeatMap {
+    /// A heatmap of `rows` × `columns` values, row by row. `colormap` gives the color of a cell
+    /// from its value relative to the [`Self::value_range`], between `0.0` and `1.0`, e.g.
+    /// [`viridis`] or [`grayscale`].
+    ///
+    /// Cells with a missing or NaN value are left out.
+    pub fn new(
+        rows: usize,
+        columns: usize,
+        values: Vec<f64>,
+        colormap: impl Fn(f64) -> Color32 + 'static,
+    ) -> Self {
+        Self {
+            values,
+            rows,
+            columns,
+            position: Value::new(0.0, 0.0),
+            cell_size: [1.0, 1.0],
+            colormap: Box::new(colormap),
+            value_range: None,
+            name: Default::default(),
+            show_in_legend: true,
+            clip_data: None,
+            highlight: false,
+        }
+    }
+
+    /// The lower left corner of the grid in plot coordinates. Default: the origin.
+    pub fn position(mut self, position: Value) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// The width and height of each cell in plot coordinates. Default: `[1.0, 1.0]`.
+    pub fn cell_size(mut self, size: [f64; 2]) -> Self {
+        self.cell_size = size;
+        self
+    }
+
+    /// The values mapped to the ends of the colormap. Values outside of the range get the color
+    /// of the nearest end. Default: the range of the values.
+    pub fn value_range(mut self, range: RangeInclusive<f64>) -> Self {
+        self.value_range = Some(range);
+        self
+    }
+
+    /// Highlight this heatmap in the plot by drawing a frame around it.
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Name of this heatmap.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on.
+    ///
+    /// Multiple plot items may share the same name, in which case they will also share an entry in
+    /// the legend.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Whether to show this item in the legend. Items that are not shown can still have a name,
+    /// which is used when hovering them. Default: `true`.
+    pub fn show_in_legend(mut self, show: bool) -> Self {
+        self.show_in_legend = show;
+        self
+    }
+
+    /// Only draw and hover the parts of this item within the given bounds in plot coordinates, in
+    /// addition to clipping it to the plot frame, e.g. to mask out a region. Default: `None`.
+    pub fn clip_data(mut self, bounds: Option<PlotBounds>) -> Self {
+        self.clip_data = bounds;
+        self
+    }
+
+    /// The value of the cell, or NaN if it is missing.
+    fn value(&self, row: usize, column: usize) -> f64 {
+        self.values
+            .get(row * self.columns + column)
+            .copied()
+            .unwrap_or(f64::NAN)
+    }
+
+    /// The range of values mapped to the colormap.
+    fn range(&self) -> RangeInclusive<f64> {
+        self.value_range.clone().unwrap_or_else(|| {
+            let finite = self.values.iter().copied().filter(|v| v.is_finite());
+            let min = finite.clone().fold(f64::INFINITY, f64::min);
+            let max = finite.fold(f64::NEG_INFINITY, f64::max);
+            min..=max
+        })
+    }
+
+    /// The grid in screen coordinates.
+    fn screen_rect(&self, transform: &ScreenTransform) -> Rect {
+        let bounds = self.get_bounds();
+        Rect::from_two_pos(
+            transform.position_from_value(&Value::new(bounds.min[0], bounds.min[1])),
+            transform.position_from_value(&Value::new(bounds.max[0], bounds.max[1])),
+        )
+    }
+
+    /// The screen coordinates of the edges between the columns from the left and between the
+    /// rows from the bottom. They are not equally spaced on logarithmic axes.
+    fn cell_edges(&self, transform: &ScreenTransform) -> [Vec<f32>; 2] {
+        let Value { x, y } = self.position;
+        let column_edges = (0..=self.columns)
+            .map(|i| {
+                let edge = Value::new(x + i as f64 * self.cell_size[0], y);
+                transform.position_from_value(&edge).x
+            })
+            .collect();
+        let row_edges = (0..=self.rows)
+            .map(|j| {
+                let edge = Value::new(x, y + j as f64 * self.cell_size[1]);
+                transform.position_from_value(&edge).y
+            })
+            .collect();
+        [column_edges, row_edges]
     }
 
-    /// Name of this image.
-    ///
-    /// This name will show up in the plot legend, if legends are turned on.
-    ///
-    /// Multiple plot items may share the same name, in which case they will also share an entry in
-    /// the legend.
-    #[allow(clippy::needless_pass_by_value)]
-    pub fn name(mut self, name: impl ToString) -> Self {
-        self.name = name.to_string();
-        self
+    /// The row and column of the cell at the given screen position.
+    fn cell_at(&self, pos: Pos2, transform: &ScreenTransform) -> Option<(usize, usize)> {
+        let [column_edges, row_edges] = self.cell_edges(transform);
+        let column = cell_index(&column_edges, pos.x)?;
+        let j = cell_index(&row_edges, pos.y)?;
+        Some((self.rows - 1 - j, column))
     }
 }
 
-impl PlotItem for PlotImage {
-    fn get_shapes(&self, ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
-        let Self {
-            position,
-            texture_id,
-            uv,
-            size,
-            bg_fill,
-            tint,
-            highlight,
-            ..
-        } = self;
-        let rect = {
-            let left_top = Value::new(
-                position.x as f32 - size.x / 2.0,
-                position.y as f32 - size.y / 2.0,
-            );
-            let right_bottom = Value::new(
-                position.x as f32 + size.x / 2.0,
-                position.y as f32 + size.y / 2.0,
-            );
-            let left_top_tf = transform.position_from_value(&left_top);
-            let right_bottom_tf = transform.position_from_value(&right_bottom);
-            Rect::from_two_pos(left_top_tf, right_bottom_tf)
-        };
-        Image::new(*texture_id, *size)
-            .bg_fill(*bg_fill)
-            .tint(*tint)
-            .uv(*uv)
-            .paint_at(ui, rect);
-        if *highlight {
+impl PlotItem for HeatMap {
+    fn get_shapes(
+        &self,
+        ui: &mut Ui,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+    ) {
+        let range = self.range();
+        let [column_edges, row_edges] = self.cell_edges(transform);
+        let mesh = grid_mesh(&column_edges, &row_edges, |column, j| {
+            let value = self.value(self.rows - 1 - j, column);
+            value.is_finite().then(|| {
+                let t = if range.end() > range.start() {
+                    remap_clamp(value, range.clone(), 0.0..=1.0)
+                } else {
+                    0.5
+                };
+                (self.colormap)(t)
+            })
+        });
+        shapes.push(Shape::Mesh(mesh));
+
+        if self.highlight {
             shapes.push(Shape::rect_stroke(
-                rect,
+                self.screen_rect(transform),
                 0.0,
-                Stroke::new(1.0, ui.visuals().strong_text_color()),
+                highlight_style.outline(Stroke::new(2.0, ui.visuals().text_color())),
             ));
         }
     }
@@ -1185,8 +4203,16 @@ impl PlotItem for PlotImage {
         self.name.as_str()
     }
 
+    fn show_in_legend(&self) -> bool {
+        self.show_in_legend
+    }
+
+    fn clip_data(&self) -> Option<PlotBounds> {
+        self.clip_data
+    }
+
     fn color(&self) -> Color32 {
-        Color32::TRANSPARENT
+        (self.colormap)(1.0)
     }
 
     fn highlight(&mut self) {
@@ -1203,18 +4229,50 @@ impl PlotItem for PlotImage {
 
     fn get_bounds(&self) -> PlotBounds {
         let mut bounds = PlotBounds::NOTHING;
-        let left_top = Value::new(
-            self.position.x as f32 - self.size.x / 2.0,
-            self.position.y as f32 - self.size.y / 2.0,
-        );
-        let right_bottom = Value::new(
-            self.position.x as f32 + self.size.x / 2.0,
-            self.position.y as f32 + self.size.y / 2.0,
-        );
-        bounds.extend_with(&left_top);
-        bounds.extend_with(&right_bottom);
+        bounds.extend_with(&self.position);
+        bounds.extend_with(&Value::new(
+            self.position.x + self.columns as f64 * self.cell_size[0],
+            self.position.y + self.rows as f64 * self.cell_size[1],
+        ));
         bounds
     }
+
+    fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
+        self.cell_at(point, transform)
+            .map(|(row, column)| ClosestElem::new(row * self.columns + column, 0.0))
+    }
+
+    fn on_hover(&self, elem: ClosestElem, shapes: &mut Vec<Shape>, plot: &PlotConfig<'_>) {
+        let (row, column) = (elem.index / self.columns, elem.index % self.columns);
+        let value = self.value(row, column);
+
+        // Outline the hovered cell.
+        let [column_edges, row_edges] = self.cell_edges(plot.transform);
+        let j = self.rows - 1 - row;
+        let cell = Rect::from_two_pos(
+            pos2(column_edges[column], row_edges[j]),
+            pos2(column_edges[column + 1], row_edges[j + 1]),
+        );
+        shapes.push(Shape::rect_stroke(
+            cell,
+            0.0,
+            Stroke::new(1.0, rulers_color(plot.ui)),
+        ));
+
+        if plot.hover_config.show_hover_label {
+            let mut text = String::new();
+            if !self.name.is_empty() {
+                text = format!("{}\n", self.name);
+            }
+            text.push_str(&format!(
+                "row = {}, column = {}\nvalue = {}",
+                row,
+                column,
+                format_magnitude_aware(value)
+            ));
+            hover_label(plot.pointer, text, plot, shapes);
+        }
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -1223,9 +4281,13 @@ impl PlotItem for PlotImage {
 pub struct BarChart {
     pub(super) bars: Vec<Bar>,
     pub(super) default_color: Color32,
+    signed_colors: Option<(Color32, Color32)>,
     pub(super) name: String,
+    pub(super) show_in_legend: bool,
+    pub(super) clip_data: Option<PlotBounds>,
     /// A custom element formatter
     pub(super) element_formatter: Option<Box<dyn Fn(&Bar, &BarChart) -> String>>,
+    width_in_points: Option<f32>,
     highlight: bool,
 }
 
@@ -1235,8 +4297,12 @@ impl BarChart {
         BarChart {
             bars,
             default_color: Color32::TRANSPARENT,
+            signed_colors: None,
             name: String::new(),
+            show_in_legend: true,
+            clip_data: None,
             element_formatter: None,
+            width_in_points: None,
             highlight: false,
         }
     }
@@ -1249,7 +4315,7 @@ impl BarChart {
         let plot_color = color.into();
         self.default_color = plot_color;
         self.bars.iter_mut().for_each(|b| {
-            if b.fill == Color32::TRANSPARENT && b.stroke.color == Color32::TRANSPARENT {
+            if !b.explicit_color {
                 b.fill = plot_color.linear_multiply(0.2);
                 b.stroke.color = plot_color;
             }
@@ -1257,6 +4323,19 @@ impl BarChart {
         self
     }
 
+    /// Use different colors for bars with positive and negative values, e.g. for profit/loss charts.
+    /// This only applies to elements that do not have a specific color, and takes precedence over
+    /// [`Self::color`] regardless of the order in which they are called.
+    /// The positive color is the one that shows up in the legend.
+    pub fn signed_colors(
+        mut self,
+        positive: impl Into<Color32>,
+        negative: impl Into<Color32>,
+    ) -> Self {
+        self.signed_colors = Some((positive.into(), negative.into()));
+        self
+    }
+
     /// Name of this chart.
     ///
     /// This name will show up in the plot legend, if legends are turned on. Multiple charts may
@@ -1267,6 +4346,20 @@ impl BarChart {
         self
     }
 
+    /// Whether to show this item in the legend. Items that are not shown can still have a name,
+    /// which is used when hovering them. Default: `true`.
+    pub fn show_in_legend(mut self, show: bool) -> Self {
+        self.show_in_legend = show;
+        self
+    }
+
+    /// Only draw and hover the parts of this item within the given bounds in plot coordinates, in
+    /// addition to clipping it to the plot frame, e.g. to mask out a region. Default: `None`.
+    pub fn clip_data(mut self, bounds: Option<PlotBounds>) -> Self {
+        self.clip_data = bounds;
+        self
+    }
+
     /// Set all elements to be in a vertical orientation.
     /// Argument axis will be X and bar values will be on the Y axis.
     pub fn vertical(mut self) -> Self {
@@ -1293,6 +4386,25 @@ impl BarChart {
         self
     }
 
+    /// Draw all elements with a constant width in screen points, regardless of the zoom level.
+    /// This overrides the (data unit) width of the elements when drawing them.
+    ///
+    /// Note that the automatic bounds are still computed using the data unit width of the
+    /// elements, since the screen scale is not known at that point. Elements at the edges may thus
+    /// be partially cut off or have some extra margin.
+    pub fn width_in_points(mut self, width: f32) -> Self {
+        self.width_in_points = Some(width);
+        self
+    }
+
+    /// Set the fill pattern of all its elements.
+    pub fn fill_pattern(mut self, pattern: FillPattern) -> Self {
+        self.bars.iter_mut().for_each(|b| {
+            b.fill_pattern = pattern;
+        });
+        self
+    }
+
     /// Highlight all plot elements.
     pub fn highlight(mut self, highlight: bool) -> Self {
         self.highlight = highlight;
@@ -1331,10 +4443,58 @@ impl BarChart {
     }
 }
 
+impl BarChart {
+    /// The bar as it should be drawn, with the colors of [`Self::signed_colors`] unless it has a
+    /// specific color, and its extent adjusted to [`Self::width_in_points`] if set. On a
+    /// non-linear argument axis, the latter also moves its argument to the middle of that extent.
+    fn displayed_bar<'a>(&self, bar: &'a Bar, transform: &ScreenTransform) -> Cow<'a, Bar> {
+        let mut bar = Cow::Borrowed(bar);
+        if let Some((positive, negative)) = self.signed_colors {
+            if !bar.explicit_color {
+                let color = if bar.value.is_sign_positive() {
+                    positive
+                } else {
+                    negative
+                };
+                let bar = bar.to_mut();
+                bar.fill = color.linear_multiply(0.2);
+                bar.stroke.color = color;
+            }
+        }
+        if let Some(width) = self.width_in_points {
+            // Offset the edges on the screen, since the argument axis need not be linear.
+            let center = transform.position_from_value(&bar.point_at(bar.argument, bar.value));
+            let (offset, argument_axis) = match bar.orientation {
+                Orientation::Vertical => (vec2(width / 2.0, 0.0), 0),
+                Orientation::Horizontal => (vec2(0.0, width / 2.0), 1),
+            };
+            let argument = |pos| {
+                let value = transform.value_from_position(pos);
+                [value.x, value.y][argument_axis]
+            };
+            let (lower, upper) = (argument(center - offset), argument(center + offset));
+            let bar = bar.to_mut();
+            bar.argument = (lower + upper) / 2.0;
+            bar.bar_width = (upper - lower).abs();
+        }
+        bar
+    }
+}
+
 impl PlotItem for BarChart {
-    fn get_shapes(&self, _ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
+    fn get_shapes(
+        &self,
+        _ui: &mut Ui,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+    ) {
         self.bars.iter().for_each(|b| {
-            b.add_shapes(transform, self.highlight, shapes);
+            self.displayed_bar(b, transform).add_shapes(
+                transform,
+                self.highlight.then(|| highlight_style),
+                shapes,
+            );
         });
     }
 
@@ -1346,8 +4506,17 @@ impl PlotItem for BarChart {
         self.name.as_str()
     }
 
+    fn show_in_legend(&self) -> bool {
+        self.show_in_legend
+    }
+
+    fn clip_data(&self) -> Option<PlotBounds> {
+        self.clip_data
+    }
+
     fn color(&self) -> Color32 {
-        self.default_color
+        self.signed_colors
+            .map_or(self.default_color, |(positive, _)| positive)
     }
 
     fn highlight(&mut self) {
@@ -1371,14 +4540,39 @@ impl PlotItem for BarChart {
     }
 
     fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
-        find_closest_rect(&self.bars, point, transform)
+        let bars: Vec<_> = self
+            .bars
+            .iter()
+            .map(|b| self.displayed_bar(b, transform))
+            .collect();
+        find_closest_rect(bars.iter().map(|b| &**b), point, transform)
+    }
+
+    fn element_value(&self, index: usize) -> Option<Value> {
+        let bar = self.bars.get(index)?;
+        Some(bar.point_at(bar.argument, bar.value))
+    }
+
+    fn value_at_x(&self, x: f32, _max_dx: f32, transform: &ScreenTransform) -> Option<Value> {
+        self.bars
+            .iter()
+            .filter(|bar| bar.orientation == Orientation::Vertical)
+            .find(|bar| {
+                let bar = self.displayed_bar(bar, transform);
+                transform
+                    .rect_from_values(&bar.bounds_min(), &bar.bounds_max())
+                    .x_range()
+                    .contains(&x)
+            })
+            .map(|bar| Value::new(bar.argument, bar.value))
     }
 
     fn on_hover(&self, elem: ClosestElem, shapes: &mut Vec<Shape>, plot: &PlotConfig<'_>) {
         let bar = &self.bars[elem.index];
+        let displayed = self.displayed_bar(bar, plot.transform);
 
-        bar.add_shapes(plot.transform, true, shapes);
-        bar.add_rulers_and_text(self, plot, shapes);
+        displayed.add_shapes(plot.transform, Some(plot.highlight_style), shapes);
+        displayed.add_rulers_and_text(bar, self, plot, shapes);
     }
 }
 
@@ -1387,6 +4581,8 @@ pub struct BoxPlot {
     pub(super) boxes: Vec<BoxElem>,
     pub(super) default_color: Color32,
     pub(super) name: String,
+    pub(super) show_in_legend: bool,
+    pub(super) clip_data: Option<PlotBounds>,
     /// A custom element formatter
     pub(super) element_formatter: Option<Box<dyn Fn(&BoxElem, &BoxPlot) -> String>>,
     highlight: bool,
@@ -1399,6 +4595,8 @@ impl BoxPlot {
             boxes,
             default_color: Color32::TRANSPARENT,
             name: String::new(),
+            show_in_legend: true,
+            clip_data: None,
             element_formatter: None,
             highlight: false,
         }
@@ -1432,6 +4630,20 @@ impl BoxPlot {
         self
     }
 
+    /// Whether to show this item in the legend. Items that are not shown can still have a name,
+    /// which is used when hovering them. Default: `true`.
+    pub fn show_in_legend(mut self, show: bool) -> Self {
+        self.show_in_legend = show;
+        self
+    }
+
+    /// Only draw and hover the parts of this item within the given bounds in plot coordinates, in
+    /// addition to clipping it to the plot frame, e.g. to mask out a region. Default: `None`.
+    pub fn clip_data(mut self, bounds: Option<PlotBounds>) -> Self {
+        self.clip_data = bounds;
+        self
+    }
+
     /// Set all elements to be in a vertical orientation.
     /// Argument axis will be X and values will be on the Y axis.
     pub fn vertical(mut self) -> Self {
@@ -1468,9 +4680,15 @@ impl BoxPlot {
 }
 
 impl PlotItem for BoxPlot {
-    fn get_shapes(&self, _ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
+    fn get_shapes(
+        &self,
+        _ui: &mut Ui,
+        transform: &ScreenTransform,
+        highlight_style: &HighlightStyle,
+        shapes: &mut Vec<Shape>,
+    ) {
         self.boxes.iter().for_each(|b| {
-            b.add_shapes(transform, self.highlight, shapes);
+            b.add_shapes(transform, self.highlight.then(|| highlight_style), shapes);
         });
     }
 
@@ -1482,6 +4700,14 @@ impl PlotItem for BoxPlot {
         self.name.as_str()
     }
 
+    fn show_in_legend(&self) -> bool {
+        self.show_in_legend
+    }
+
+    fn clip_data(&self) -> Option<PlotBounds> {
+        self.clip_data
+    }
+
     fn color(&self) -> Color32 {
         self.default_color
     }
@@ -1513,7 +4739,7 @@ impl PlotItem for BoxPlot {
     fn on_hover(&self, elem: ClosestElem, shapes: &mut Vec<Shape>, plot: &PlotConfig<'_>) {
         let box_plot = &self.boxes[elem.index];
 
-        box_plot.add_shapes(plot.transform, true, shapes);
+        box_plot.add_shapes(plot.transform, Some(plot.highlight_style), shapes);
         box_plot.add_rulers_and_text(self, plot, shapes);
     }
 }
@@ -1521,7 +4747,7 @@ impl PlotItem for BoxPlot {
 // ----------------------------------------------------------------------------
 // Helper functions
 
-fn rulers_color(ui: &Ui) -> Color32 {
+pub(super) fn rulers_color(ui: &Ui) -> Color32 {
     if ui.visuals().dark_mode {
         Color32::from_gray(100).additive()
     } else {
@@ -1647,18 +4873,68 @@ pub(super) fn rulers_at_value(
     if hover_config.show_hover_label {
         let hover_label_func = plot.hover_formatter;
         let text = hover_label_func(&plot.hover_config, name, &value);
+        hover_label(pointer, text, plot, shapes);
+    }
+}
+
+/// Shows the hover label with the given text next to the pointer.
+fn hover_label(pointer: Pos2, text: String, plot: &PlotConfig<'_>, shapes: &mut Vec<Shape>) {
+    let font_id = TextStyle::Body.resolve(plot.ui.style());
+
+    shapes.push(Shape::text(
+        &*plot.ui.fonts(),
+        pointer + vec2(3.0, -2.0),
+        Align2::LEFT_BOTTOM,
+        text,
+        font_id,
+        plot.ui.visuals().text_color(),
+    ));
+}
 
-        let font_id = TextStyle::Body.resolve(plot.ui.style());
+/// Shows a vertical ruler at the pointer and a single label listing the values of all items at
+/// that x-coordinate, the topmost item first.
+pub(super) fn stacked_rulers(
+    pointer: Pos2,
+    entries: &[(&str, Value)],
+    plot: &PlotConfig<'_>,
+    shapes: &mut Vec<Shape>,
+) {
+    let hover_config = &plot.hover_config;
 
-        shapes.push(Shape::text(
-            &*plot.ui.fonts(),
-            pointer + vec2(3.0, -2.0),
-            Align2::LEFT_BOTTOM,
-            text,
-            font_id,
-            plot.ui.visuals().text_color(),
+    if hover_config.hover_line.show_x_line() {
+        shapes.push(vertical_line(
+            pointer,
+            plot.transform,
+            rulers_color(plot.ui),
         ));
     }
+
+    if hover_config.show_hover_label {
+        let hover_label_func = plot.hover_formatter;
+        let text = entries
+            .iter()
+            .rev()
+            .map(|(name, value)| hover_label_func(hover_config, name, value))
+            .collect::<Vec<_>>()
+            .join("\n");
+        hover_label(pointer, text, plot, shapes);
+    }
+}
+
+fn find_closest_value(
+    values: &[Value],
+    point: Pos2,
+    transform: &ScreenTransform,
+) -> Option<ClosestElem> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let pos = transform.position_from_value(value);
+            let dist_sq = point.distance_sq(pos);
+            ClosestElem::new(index, dist_sq)
+        })
+        .min_by_key(|e| e.dist_sq.ord())
 }
 
 fn find_closest_rect<'a, T>(
@@ -1676,7 +4952,7 @@ where
             let bar_rect: Rect = transform.rect_from_values(&bar.bounds_min(), &bar.bounds_max());
             let dist_sq = bar_rect.distance_sq_to_pos(point);
 
-            ClosestElem { index, dist_sq }
+            ClosestElem::new(index, dist_sq)
         })
         .min_by_key(|e| e.dist_sq.ord())
 }
@@ -1685,3 +4961,823 @@ where
 pub(super) fn num_decimals_with_max_digits(value: f64, max_digits: usize) -> usize {
     max_digits - ((value.abs().log10()).ceil().at_least(0.0) as usize).at_most(max_digits)
 }
+
+/// Formats a number with up to six digits, switching to scientific notation for very large and
+/// very small magnitudes, e.g. `0.25`, `1234.5` and `1.5e-7`. Useful in a custom
+/// [`super::Plot::hover_formatter`], see also [`super::Plot::magnitude_aware_hover_formatter`].
+pub fn format_magnitude_aware(value: f64) -> String {
+    let magnitude = value.abs();
+    if magnitude == 0.0 || (1e-3..1e6).contains(&magnitude) || !value.is_finite() {
+        format!("{:.*}", num_decimals_with_max_digits(value, 6), value)
+    } else {
+        let scientific = format!("{:.5e}", value);
+        match scientific.split_once('e') {
+            Some((mantissa, exponent)) => {
+                let mantissa = mantissa.trim_end_matches('0').trim_end_matches('.');
+                format!("{}e{}", mantissa, exponent)
+            }
+            None => scientific,
+        }
+    }
+}
+
+/// Formats powers of ten as `10^n`, e.g. `10^-9` and `10^3`, and other values like
+/// [`format_magnitude_aware`], but without trailing zeros. This is the default for the labels of
+/// logarithmic axes, see [`super::Plot::log_axis`].
+pub fn format_power_of_ten(value: f64) -> String {
+    if value > 0.0 && value.is_finite() {
+        let exponent = value.log10().round();
+        if (value / 10.0_f64.powf(exponent) - 1.0).abs() < 1e-9 {
+            return format!("10^{}", exponent);
+        }
+    }
+    let text = format_magnitude_aware(value);
+    if text.contains('.') && !text.contains('e') {
+        text.trim_end_matches('0').trim_end_matches('.').to_owned()
+    } else {
+        text
+    }
+}
+
+/// The perceptually uniform viridis colormap, from dark blue at `0.0` over green to yellow at
+/// `1.0`, e.g. for a [`HeatMap`].
+pub fn viridis(value: f64) -> Color32 {
+    const COLORS: [[u8; 3]; 9] = [
+        [68, 1, 84],
+        [71, 45, 123],
+        [59, 82, 139],
+        [44, 114, 142],
+        [33, 145, 140],
+        [40, 174, 128],
+        [94, 201, 98],
+        [173, 220, 48],
+        [253, 231, 37],
+    ];
+    let pos = value.clamp(0.0, 1.0) * (COLORS.len() - 1) as f64;
+    let idx = (pos as usize).min(COLORS.len() - 2);
+    let frac = (pos - idx as f64) as f32;
+    let [red, green, blue] = [0, 1, 2].map(|channel| {
+        lerp(
+            COLORS[idx][channel] as f32..=COLORS[idx + 1][channel] as f32,
+            frac,
+        )
+        .round() as u8
+    });
+    Color32::from_rgb(red, green, blue)
+}
+
+/// A colormap from black at `0.0` to white at `1.0`, e.g. for a [`HeatMap`].
+pub fn grayscale(t: f64) -> Color32 {
+    Color32::from_gray((t.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+fn duplicated_x_line() -> Line {
+    Line::new(Values::from_values(vec![
+        Value::new(0.0, 0.0),
+        Value::new(1.0, 0.0),
+        Value::new(1.0, 1.0),
+        Value::new(2.0, 1.0),
+    ]))
+}
+
+#[test]
+fn test_format_magnitude_aware() {
+    // Fixed notation from 1e-3 up to 1e6, with up to six digits.
+    assert_eq!(format_magnitude_aware(0.0), "0.000000");
+    assert_eq!(format_magnitude_aware(0.001), "0.001000");
+    assert_eq!(format_magnitude_aware(0.25), "0.250000");
+    assert_eq!(format_magnitude_aware(-1234.5), "-1234.50");
+    assert_eq!(format_magnitude_aware(999_999.0), "999999");
+    // Scientific notation without trailing zeros outside of it.
+    assert_eq!(format_magnitude_aware(0.000_999), "9.99e-4");
+    assert_eq!(format_magnitude_aware(1e6), "1e6");
+    assert_eq!(format_magnitude_aware(-1.5e-7), "-1.5e-7");
+    assert_eq!(format_magnitude_aware(1.234_567e9), "1.23457e9");
+    // Non-finite values are not touched.
+    assert_eq!(format_magnitude_aware(f64::NAN), "NaN");
+    assert_eq!(format_magnitude_aware(f64::INFINITY), "inf");
+}
+
+#[test]
+fn test_simplify_polyline() {
+    // Nearly collinear vertices are dropped, the corner is kept.
+    let points = [
+        pos2(0.0, 0.0),
+        pos2(5.0, 0.2),
+        pos2(10.0, 0.0),
+        pos2(10.0, 5.0),
+        pos2(10.1, 10.0),
+    ];
+    assert_eq!(
+        simplify_polyline(&points, 1.0),
+        vec![pos2(0.0, 0.0), pos2(10.0, 0.0), pos2(10.1, 10.0)]
+    );
+    assert_eq!(simplify_polyline(&points, 0.01), points.to_vec());
+
+    // Non-finite points interrupt the line, and each run is simplified on its own.
+    let nan = pos2(f32::NAN, f32::NAN);
+    let runs = simplify_runs(&[points[0], points[1], points[2], nan, points[3]], 1.0);
+    assert_eq!(runs.len(), 4);
+    assert_eq!(runs[..2], [points[0], points[2]]);
+    assert!(runs[2].x.is_nan());
+    assert_eq!(runs[3], points[3]);
+}
+
+#[test]
+fn test_simplify_line_and_area() {
+    let values = || Values::from_values((0..=100).map(|i| Value::new(i as f64, 1.0)).collect());
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 100.0));
+    let bounds = PlotBounds::from_min_max([0.0, 0.0], [100.0, 2.0]);
+    let transform = ScreenTransform::new(frame, bounds, false, false);
+    let shapes = |item: &dyn PlotItem| {
+        let mut shapes = Vec::new();
+        crate::__run_test_ui(|ui| {
+            shapes.clear();
+            item.get_shapes(ui, &transform, &HighlightStyle::default(), &mut shapes);
+        });
+        shapes
+    };
+
+    // The straight line and its fill are reduced to the end points.
+    let line = Line::new(values()).fill(0.0).simplify(1.0);
+    let line_shapes = shapes(&line);
+    let fill_vertices = match &line_shapes[0] {
+        Shape::Mesh(mesh) => mesh.vertices.len(),
+        _ => panic!("expected the fill first"),
+    };
+    assert_eq!(fill_vertices, 4);
+    match line_shapes.last() {
+        Some(Shape::Path(path)) => assert_eq!(path.points.len(), 2),
+        _ => panic!("expected the line last"),
+    }
+
+    // The flat area is a single band.
+    let area = StackedArea::new(values()).simplify(1.0);
+    assert_eq!(shapes(&area).len(), 2);
+    assert_eq!(shapes(&StackedArea::new(values())).len(), 101);
+}
+
+#[test]
+fn test_line_downsample() {
+    let values: Vec<_> = (0..1000)
+        .map(|i| Value::new(i as f64, (i % 7) as f64 - 3.0))
+        .collect();
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 100.0));
+    let bounds = PlotBounds::from_min_max([0.0, -3.0], [1000.0, 3.0]);
+    let transform = ScreenTransform::new(frame, bounds, false, false);
+    let line = Line::new(Values::from_values(values)).downsample(true);
+    let mut shapes = Vec::new();
+    crate::__run_test_ui(|ui| {
+        shapes.clear();
+        line.get_shapes(ui, &transform, &HighlightStyle::default(), &mut shapes);
+    });
+
+    let points = match &shapes[..] {
+        [Shape::Path(path)] => &path.points,
+        _ => panic!("expected a single path"),
+    };
+    // At most four points per pixel column, which still reach the extremes.
+    assert!(points.len() <= 4 * 101);
+    assert!(points.iter().any(|p| p.y == frame.top()));
+    assert!(points.iter().any(|p| p.y == frame.bottom()));
+
+    // Hovering still finds the actual values.
+    let closest = line.find_closest(pos2(50.0, 50.0), &transform).unwrap();
+    assert!((490..=510).contains(&closest.index));
+}
+
+#[test]
+fn test_culling() {
+    let values: Vec<_> = (0..10_000)
+        .map(|i| Value::new(i as f64 * 1e-3, (i as f64 * 1e-3).sin()))
+        .collect();
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 100.0));
+    // A tiny window with about ten values.
+    let bounds = PlotBounds::from_min_max([5.0, -1.0], [5.01, 1.0]);
+    let transform = ScreenTransform::new(frame, bounds, false, false);
+    let line = Line::new(Values::from_values(values.clone())).fill(0.0);
+    let points = Points::new(Values::from_values(values)).connect(true);
+    let mut line_shapes = Vec::new();
+    let mut point_shapes = Vec::new();
+    crate::__run_test_ui(|ui| {
+        line_shapes.clear();
+        point_shapes.clear();
+        let style = HighlightStyle::default();
+        line.get_shapes(ui, &transform, &style, &mut line_shapes);
+        points.get_shapes(ui, &transform, &style, &mut point_shapes);
+    });
+
+    // The fill and a single line, which still reaches past both edges of the frame.
+    let (mesh, line) = match &line_shapes[..] {
+        [Shape::Mesh(mesh), Shape::Path(path)] => (mesh, &path.points),
+        _ => panic!("expected the fill and one line"),
+    };
+    assert!(mesh.vertices.len() < 100);
+    assert!(line.len() < 20);
+    assert!(line[0].x < frame.left() && line[line.len() - 1].x > frame.right());
+    // The connecting line and the markers in view.
+    assert!(point_shapes.len() < 20);
+}
+
+#[test]
+fn test_line_keeps_vertical_segments() {
+    let mut line = duplicated_x_line();
+    line.initialize(0.0..=2.0);
+    assert_eq!(line.series.values.len(), 4);
+    assert_eq!(line.data_length(), 3.0);
+
+    let bounds = line.get_bounds();
+    assert_eq!(bounds.min(), [0.0, 0.0]);
+    assert_eq!(bounds.max(), [2.0, 1.0]);
+
+    // Both ends of the vertical segment can be hovered.
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(200.0, 100.0));
+    let transform = ScreenTransform::new(frame, bounds, false, false);
+    let lower = transform.position_from_value(&Value::new(1.0, 0.1));
+    let upper = transform.position_from_value(&Value::new(1.0, 0.9));
+    assert_eq!(
+        line.find_closest(lower, &transform).map(|e| e.index),
+        Some(1)
+    );
+    assert_eq!(
+        line.find_closest(upper, &transform).map(|e| e.index),
+        Some(2)
+    );
+}
+
+#[test]
+fn test_line_column_stats() {
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(10.0, 10.0));
+    let bounds = PlotBounds::from_min_max([0.0, 0.0], [10.0, 10.0]);
+    let transform = ScreenTransform::new(frame, bounds, false, false);
+    let values =
+        |xs: &[f64]| Values::from_values(xs.iter().map(|&x| Value::new(x, 10.0 * x)).collect());
+
+    // Sorted values are found by a binary search, unsorted ones by a scan, with the same result.
+    let mut sorted = Line::new(values(&[1.5, 3.2, 3.4, 3.6, 5.0]));
+    let mut unsorted = Line::new(values(&[3.4, 5.0, 3.2, 1.5, 3.6]));
+    sorted.initialize(0.0..=10.0);
+    unsorted.initialize(0.0..=10.0);
+    assert!(sorted.sorted_by_x);
+    assert!(!unsorted.sorted_by_x);
+    for line in [sorted, unsorted] {
+        let (count, min, max, sum) = line.column_stats(3.0, &transform);
+        assert_eq!((count, min, max), (3, 32.0, 36.0));
+        assert!((sum - 102.0).abs() < 1e-9);
+        assert_eq!(line.column_stats(2.0, &transform).0, 0);
+    }
+}
+
+#[test]
+fn test_line_hover_snaps_to_step() {
+    let line = Line::new(Values::from_values(vec![
+        Value::new(0.0, 0.0),
+        Value::new(1.0, 0.0),
+        Value::new(1.0, 1.0),
+        Value::new(3.0, 1.0),
+        Value::new(3.0, 2.0),
+        Value::new(4.0, 2.0),
+    ]));
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(400.0, 200.0));
+    let bounds = PlotBounds::from_min_max([0.0, 0.0], [4.0, 2.0]);
+    let transform = ScreenTransform::new(frame, bounds, false, false);
+
+    // In the middle of the tread from x = 1 to x = 3, far from both of its vertices.
+    let pointer = transform.position_from_value(&Value::new(2.0, 1.05));
+    let closest = line.find_closest(pointer, &transform).unwrap();
+    assert_eq!(closest.index, 2);
+    assert!(closest.dist_sq < 100.0);
+    assert_eq!(line.tread_end(closest.index), Some(Value::new(3.0, 1.0)));
+
+    // Risers are not treads.
+    assert_eq!(line.tread_end(1), None);
+}
+
+#[test]
+fn test_line_value_transform() {
+    let meters = Values::from_values(vec![Value::new(0.0, 1.0), Value::new(1.0, 2.0)]);
+    let mut line = Line::new(meters).value_transform(|v| Value::new(v.x, v.y / 0.3048));
+
+    // The bounds are mapped both before and after initialization.
+    let feet = PlotBounds::from_min_max([0.0, 1.0 / 0.3048], [1.0, 2.0 / 0.3048]);
+    assert_eq!(line.get_bounds(), feet);
+    line.initialize(0.0..=1.0);
+    assert_eq!(line.get_bounds(), feet);
+
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 100.0));
+    let transform = ScreenTransform::new(frame, feet, false, false);
+    let pointer = transform.position_from_value(&Value::new(1.0, 2.0 / 0.3048));
+    let closest = line.find_closest(pointer, &transform).unwrap();
+    assert_eq!(closest.index, 1);
+    assert_eq!(closest.dist_sq, 0.0);
+}
+
+#[test]
+fn test_line_value_transform_length() {
+    let values = || Values::from_values(vec![Value::new(0.0, 0.0), Value::new(1.0, 1.0)]);
+    let line = || Line::new(values()).value_transform(|v| Value::new(3.0 * v.x, 4.0 * v.y));
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 100.0));
+    let transform = ScreenTransform::new(
+        frame,
+        PlotBounds::from_min_max([0.0, 0.0], [100.0, 100.0]),
+        false,
+        false,
+    );
+
+    // The lengths are measured on the mapped values, both before and after initialization.
+    let mut initialized = line();
+    initialized.initialize(0.0..=1.0);
+    for line in [line(), initialized] {
+        assert_eq!(line.data_length(), 5.0);
+        assert!((line.screen_length(&transform) - 5.0).abs() < 1e-4);
+        assert_eq!(line.point_at_fraction(0.5), Some(Value::new(1.5, 2.0)));
+    }
+}
+
+#[test]
+fn test_complex_values() {
+    let samples = [(1.0, 0.0), (0.0, 2.0), (-3.0, -4.0)];
+    assert_eq!(
+        Values::from_complex_reim(&samples).values,
+        vec![
+            Value::new(1.0, 0.0),
+            Value::new(0.0, 2.0),
+            Value::new(-3.0, -4.0)
+        ]
+    );
+
+    let (magnitude, phase) = Values::from_complex_magphase(&samples);
+    assert_eq!(
+        magnitude.values,
+        vec![
+            Value::new(0.0, 1.0),
+            Value::new(1.0, 2.0),
+            Value::new(2.0, 5.0)
+        ]
+    );
+    assert_eq!(phase.values[0], Value::new(0.0, 0.0));
+    assert_eq!(
+        phase.values[1],
+        Value::new(1.0, std::f64::consts::FRAC_PI_2)
+    );
+}
+
+#[test]
+fn test_points_max_shapes() {
+    let ys: Vec<f32> = (0..100).map(|i| i as f32).collect();
+    let points = Points::new(Values::from_ys_f32(&ys)).stems(0.0);
+    let bounds = PlotBounds::from_min_max([0.0, 0.0], [100.0, 100.0]);
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 100.0));
+    let transform = ScreenTransform::new(frame, bounds, false, false);
+    let style = HighlightStyle::default();
+
+    // A stem and a marker for each point.
+    let mut shapes = Vec::new();
+    points.add_shapes(&transform, &style, &mut shapes, usize::MAX);
+    assert_eq!(shapes.len(), 200);
+
+    // The limit counts from the shapes that are already there.
+    let mut shapes = vec![Shape::Noop];
+    points.add_shapes(&transform, &style, &mut shapes, 5);
+    assert_eq!(shapes.len(), 6);
+}
+
+#[test]
+fn test_points_density() {
+    let values = vec![
+        Value::new(0.1, 0.1),
+        Value::new(0.2, 0.3),
+        Value::new(0.9, 0.9),
+        Value::new(2.0, 0.5),
+    ];
+    let points = Points::new(Values::from_values(values)).as_density([2, 2], |_| Color32::RED);
+    let bounds = PlotBounds::from_min_max([0.0, 0.0], [1.0, 1.0]);
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 100.0));
+    let transform = ScreenTransform::new(frame, bounds, false, false);
+    // The last point is outside of the bounds.
+    assert_eq!(
+        points.density_cells([2, 2], &transform).counts,
+        vec![2, 0, 0, 1]
+    );
+
+    // On a logarithmic axis, the cells are equally wide on screen, not in value space.
+    let values = vec![Value::new(2.0, 0.5), Value::new(50.0, 0.5)];
+    let log_points = Points::new(Values::from_values(values)).as_density([2, 1], |_| Color32::RED);
+    let log_transform = ScreenTransform::new(
+        frame,
+        PlotBounds::from_min_max([1.0, 0.0], [100.0, 1.0]),
+        false,
+        false,
+    )
+    .with_scales([super::AxisScale::Log, super::AxisScale::Linear]);
+    assert_eq!(
+        log_points.density_cells([2, 1], &log_transform).counts,
+        vec![1, 1]
+    );
+
+    // Hovering an empty cell finds nothing, hovering a full one finds one of its points.
+    let empty = transform.position_from_value(&Value::new(0.75, 0.25));
+    assert!(points.find_closest(empty, &transform).is_none());
+    let full = transform.position_from_value(&Value::new(0.4, 0.4));
+    assert_eq!(points.find_closest(full, &transform).unwrap().index, 0);
+}
+
+#[test]
+fn test_bar_chart_signed_colors() {
+    let bars = || {
+        vec![
+            Bar::new(0.0, 1.0),
+            Bar::new(1.0, -1.0),
+            Bar::new(2.0, 1.0).fill(Color32::YELLOW),
+        ]
+    };
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 100.0));
+    let bounds = PlotBounds::from_min_max([0.0, -1.0], [2.0, 1.0]);
+    let transform = ScreenTransform::new(frame, bounds, false, false);
+    let fills = |chart: &BarChart| -> Vec<Color32> {
+        chart
+            .bars
+            .iter()
+            .map(|bar| chart.displayed_bar(bar, &transform).fill)
+            .collect()
+    };
+
+    // The signed colors win over the chart color in either order, but not over bar colors.
+    let expected = vec![
+        Color32::GREEN.linear_multiply(0.2),
+        Color32::RED.linear_multiply(0.2),
+        Color32::YELLOW,
+    ];
+    let before = BarChart::new(bars())
+        .signed_colors(Color32::GREEN, Color32::RED)
+        .color(Color32::BLUE);
+    let after = BarChart::new(bars())
+        .color(Color32::BLUE)
+        .signed_colors(Color32::GREEN, Color32::RED);
+    assert_eq!(fills(&before), expected);
+    assert_eq!(fills(&after), expected);
+    assert_eq!(PlotItem::color(&before), Color32::GREEN);
+    assert_eq!(PlotItem::color(&after), Color32::GREEN);
+
+    // A bar explicitly set to the chart color keeps it.
+    let explicit = Bar::new(0.0, 1.0)
+        .fill(Color32::BLUE.linear_multiply(0.2))
+        .stroke(Stroke::new(1.0, Color32::BLUE));
+    let chart = BarChart::new(vec![explicit])
+        .color(Color32::BLUE)
+        .signed_colors(Color32::GREEN, Color32::RED);
+    assert_eq!(fills(&chart), vec![Color32::BLUE.linear_multiply(0.2)]);
+}
+
+#[test]
+fn test_bar_chart_width_in_points_log() {
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(300.0, 100.0));
+    let bounds = PlotBounds::from_min_max([1.0, 0.0], [1000.0, 1.0]);
+    let transform = ScreenTransform::new(frame, bounds, false, false)
+        .with_scales([super::AxisScale::Log, super::AxisScale::Linear]);
+    let chart = BarChart::new(vec![Bar::new(10.0, 1.0)]).width_in_points(20.0);
+
+    // The bar is centered on its argument on the screen, with the given width.
+    let bar = chart.displayed_bar(&chart.bars[0], &transform);
+    let rect = transform.rect_from_values(&bar.bounds_min(), &bar.bounds_max());
+    let center = transform.position_from_value(&Value::new(10.0, 0.0));
+    assert!((rect.width() - 20.0).abs() < 1e-3);
+    assert!((rect.center().x - center.x).abs() < 1e-3);
+}
+
+#[test]
+fn test_span_bounds_and_area() {
+    let span = HSpan::new(3.0, -1.0).name("safe zone");
+    let bounds = span.get_bounds();
+    assert_eq!(bounds.min()[1], -1.0);
+    assert_eq!(bounds.max()[1], 3.0);
+    assert!(!bounds.min()[0].is_finite());
+    assert!(span.contains_value(&Value::new(100.0, 0.0)));
+    assert!(!span.contains_value(&Value::new(0.0, 4.0)));
+
+    let span = VSpan::new(1.0, 2.0);
+    assert!(span.contains_value(&Value::new(1.5, -100.0)));
+    assert!(!span.contains_value(&Value::new(0.5, 0.0)));
+}
+
+#[test]
+fn test_line_fill_is_hoverable() {
+    let values = vec![
+        Value::new(0.0, 1.0),
+        Value::new(1.0, 2.0),
+        Value::new(2.0, 1.0),
+    ];
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(200.0, 100.0));
+    let bounds = PlotBounds::from_min_max([0.0, -1.0], [2.0, 3.0]);
+    let transform = ScreenTransform::new(frame, bounds, false, false);
+    let inside = transform.position_from_value(&Value::new(1.2, 0.5));
+    let below = transform.position_from_value(&Value::new(1.2, -0.5));
+
+    let line = Line::new(Values::from_values(values.clone()));
+    assert!(line.find_closest(inside, &transform).unwrap().dist_sq > 0.0);
+
+    let filled = Line::new(Values::from_values(values)).fill(0.0);
+    let closest = filled.find_closest(inside, &transform).unwrap();
+    assert_eq!(closest.index, 1);
+    assert_eq!(closest.dist_sq, 0.0);
+    assert!(filled.find_closest(below, &transform).unwrap().dist_sq > 0.0);
+}
+
+#[test]
+fn test_fill_to_level_one_side() {
+    // A peak crossing the level at y = 10 (screen coordinates, so the peak points upwards).
+    let points = [pos2(0.0, 20.0), pos2(10.0, 0.0), pos2(20.0, 20.0)];
+    let above = |above: bool| {
+        if above {
+            Color32::RED
+        } else {
+            Color32::TRANSPARENT
+        }
+    };
+    let mut mesh = Mesh::default();
+    fill_to_level(&points, 10.0, above, &mut mesh);
+
+    // Only the tip of the peak is filled, starting at the interpolated crossings.
+    assert_eq!(mesh.indices.len(), 2 * 3);
+    assert!(mesh.vertices.iter().all(|v| v.pos.y <= 10.0));
+    assert!(mesh.vertices.iter().any(|v| v.pos == pos2(5.0, 10.0)));
+    assert!(mesh.vertices.iter().any(|v| v.pos == pos2(15.0, 10.0)));
+}
+
+#[test]
+fn test_line_collapses_vertical_segments() {
+    let mut line = duplicated_x_line().allow_vertical(false);
+    line.initialize(0.0..=2.0);
+    assert_eq!(
+        line.series.values,
+        vec![
+            Value::new(0.0, 0.0),
+            Value::new(1.0, 1.0),
+            Value::new(2.0, 1.0)
+        ]
+    );
+}
+
+#[cfg(test)]
+fn arrow_field_shapes(zero_length: ZeroLengthMode) -> Vec<Shape> {
+    let origins = vec![
+        Value::new(0.0, 0.0),
+        Value::new(1.0, 0.0),
+        Value::new(2.0, 0.0),
+    ];
+    let tips = vec![
+        Value::new(0.5, 0.5),
+        Value::new(1.0, 0.0), // zero vector
+        Value::new(2.5, -0.5),
+    ];
+    let arrows = Arrows::new(Values::from_values(origins), Values::from_values(tips))
+        .color(Color32::RED)
+        .zero_length(zero_length);
+
+    let bounds = arrows.get_bounds();
+    assert!(bounds
+        .min()
+        .iter()
+        .chain(&bounds.max())
+        .all(|v| v.is_finite()));
+
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(200.0, 100.0));
+    let transform = ScreenTransform::new(frame, bounds, false, false);
+    let mut shapes = Vec::new();
+    arrows.add_shapes(
+        &transform,
+        &HighlightStyle::default(),
+        &mut shapes,
+        usize::MAX,
+    );
+    shapes
+}
+
+#[cfg(test)]
+fn shape_points(shape: &Shape) -> Vec<Pos2> {
+    match shape {
+        Shape::LineSegment { points, .. } => points.to_vec(),
+        Shape::Path(path) => path.points.clone(),
+        Shape::Circle(circle) => vec![circle.center],
+        _ => panic!("unexpected shape"),
+    }
+}
+
+#[test]
+fn test_arrows_skip_zero_length() {
+    let shapes = arrow_field_shapes(ZeroLengthMode::Skip);
+    // A shaft and a head for each of the two non-zero arrows.
+    assert_eq!(shapes.len(), 4);
+    assert!(shapes
+        .iter()
+        .flat_map(shape_points)
+        .all(|p| p.x.is_finite() && p.y.is_finite()));
+}
+
+#[test]
+fn test_arrows_dot_zero_length() {
+    let shapes = arrow_field_shapes(ZeroLengthMode::Dot);
+    assert_eq!(shapes.len(), 5);
+    assert_eq!(
+        shapes
+            .iter()
+            .filter(|shape| matches!(shape, Shape::Circle(_)))
+            .count(),
+        1
+    );
+    assert!(shapes
+        .iter()
+        .flat_map(shape_points)
+        .all(|p| p.x.is_finite() && p.y.is_finite()));
+}
+
+#[test]
+fn test_line_clip_to_range() {
+    let values = (0..10).map(|x| Value::new(x as f64, (x % 2) as f64));
+    let mut line = Line::new(Values::from_values_iter(values)).clip_to_range(true);
+    line.initialize(3.5..=5.5);
+    let xs: Vec<f64> = line.series.values.iter().map(|v| v.x).collect();
+    assert_eq!(xs, vec![3.0, 4.0, 5.0, 6.0]);
+
+    // Hovering finds the values by their index in the clipped series.
+    let bounds = line.get_bounds();
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(200.0, 100.0));
+    let transform = ScreenTransform::new(frame, bounds, false, false);
+    let pointer = transform.position_from_value(&Value::new(5.0, 1.0));
+    let closest = line.find_closest(pointer, &transform).unwrap();
+    assert_eq!(line.series.values[closest.index], Value::new(5.0, 1.0));
+}
+
+#[test]
+fn test_stacked_area() {
+    let lower = vec![Value::new(0.0, 1.0), Value::new(2.0, 3.0)];
+    // The lower layer is interpolated at x = 1 and counts as zero beyond x = 2.
+    let mut upper = StackedArea::new(Values::from_values(vec![
+        Value::new(0.0, 1.0),
+        Value::new(1.0, 1.0),
+        Value::new(3.0, 1.0),
+    ]));
+    upper.stack_on(&[lower]);
+    assert_eq!(upper.base, vec![1.0, 2.0, 0.0]);
+
+    let bounds = upper.get_bounds();
+    assert_eq!(bounds.min(), [0.0, 0.0]);
+    assert_eq!(bounds.max(), [3.0, 3.0]);
+}
+
+#[test]
+fn test_heatmap_highlight_style() {
+    let heatmap = HeatMap::new(1, 1, vec![0.0], grayscale).highlight(true);
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(10.0, 10.0));
+    let transform = ScreenTransform::new(frame, heatmap.get_bounds(), false, false);
+    let outline_width = |style: HighlightStyle| {
+        let mut shapes = Vec::new();
+        crate::__run_test_ui(|ui| {
+            shapes.clear();
+            heatmap.get_shapes(ui, &transform, &style, &mut shapes);
+        });
+        match shapes.last() {
+            Some(Shape::Rect(rect)) => rect.stroke.width,
+            _ => panic!("expected an outline"),
+        }
+    };
+
+    // The outline follows the width factor, and vanishes without emphasis.
+    assert_eq!(outline_width(HighlightStyle::default()), 2.0);
+    assert_eq!(
+        outline_width(HighlightStyle::default().width_factor(3.0)),
+        4.0
+    );
+    assert_eq!(outline_width(HighlightStyle::none()), 0.0);
+}
+
+#[test]
+fn test_heatmap() {
+    let heatmap = HeatMap::new(2, 3, vec![0.0, 1.0, 2.0, 3.0, 4.0, f64::NAN], grayscale)
+        .position(Value::new(10.0, 0.0))
+        .cell_size([2.0, 1.0]);
+    let bounds = heatmap.get_bounds();
+    assert_eq!(bounds.min(), [10.0, 0.0]);
+    assert_eq!(bounds.max(), [16.0, 2.0]);
+    assert_eq!(heatmap.range(), 0.0..=4.0);
+
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(60.0, 20.0));
+    let transform = ScreenTransform::new(frame, bounds, false, false);
+    // Row 0 is at the top.
+    let elem = heatmap.find_closest(pos2(25.0, 5.0), &transform).unwrap();
+    assert_eq!((elem.index / 3, elem.index % 3), (0, 1));
+    assert!(heatmap.find_closest(pos2(70.0, 5.0), &transform).is_none());
+
+    // On a logarithmic axis, the cells are as wide on screen as they are in value space.
+    let heatmap = HeatMap::new(1, 2, vec![0.0, 1.0], viridis).position(Value::new(1.0, 0.0));
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 10.0));
+    let bounds = PlotBounds::from_min_max([1.0, 0.0], [3.0, 1.0]);
+    let transform = ScreenTransform::new(frame, bounds, false, false)
+        .with_scales([super::AxisScale::Log, super::AxisScale::Linear]);
+    let [column_edges, _] = heatmap.cell_edges(&transform);
+    let middle = transform.position_from_value(&Value::new(2.0, 0.0)).x;
+    assert_eq!(column_edges, vec![0.0, middle, 100.0]);
+    let elem = heatmap
+        .find_closest(pos2(middle + 1.0, 5.0), &transform)
+        .unwrap();
+    assert_eq!(elem.index, 1);
+
+    // The built-in colormaps also work for density plots.
+    let _ = Points::new(Values::from_ys_f32(&[1.0])).as_density([2, 2], viridis);
+
+    assert_eq!(viridis(0.0), Color32::from_rgb(68, 1, 84));
+    assert_eq!(viridis(1.0), Color32::from_rgb(253, 231, 37));
+}
+
+#[test]
+fn test_line_step_mode() {
+    let steps = |mode| {
+        let mut line = Line::new(Values::from_values(vec![
+            Value::new(0.0, 0.0),
+            Value::new(2.0, 1.0),
+        ]))
+        .step_mode(mode);
+        line.initialize(0.0..=2.0);
+        // The values themselves are kept, so that hovering reports the original samples.
+        assert_eq!(line.series.values.len(), 2);
+        line.series.stepped(mode)
+    };
+    assert_eq!(
+        steps(StepMode::After),
+        vec![
+            Value::new(0.0, 0.0),
+            Value::new(2.0, 0.0),
+            Value::new(2.0, 1.0)
+        ]
+    );
+    assert_eq!(
+        steps(StepMode::Before),
+        vec![
+            Value::new(0.0, 0.0),
+            Value::new(0.0, 1.0),
+            Value::new(2.0, 1.0)
+        ]
+    );
+    assert_eq!(steps(StepMode::Center).len(), 4);
+
+    // Hovering the middle of the step reports the value that is drawn there.
+    let mut line = Line::new(Values::from_values(vec![
+        Value::new(0.0, 0.0),
+        Value::new(4.0, 1.0),
+    ]))
+    .step_mode(StepMode::After);
+    line.initialize(0.0..=4.0);
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(400.0, 100.0));
+    let bounds = PlotBounds::from_min_max([0.0, 0.0], [4.0, 1.0]);
+    let transform = ScreenTransform::new(frame, bounds, false, false);
+    let pointer = transform.position_from_value(&Value::new(2.0, 0.05));
+    let closest = line.find_closest(pointer, &transform).unwrap();
+    assert_eq!(closest.index, 0);
+
+    let line = line.step_mode(StepMode::Before);
+    let pointer = transform.position_from_value(&Value::new(2.0, 0.95));
+    assert_eq!(line.find_closest(pointer, &transform).unwrap().index, 1);
+    let line = line.step_mode(StepMode::Center);
+    let pointer = transform.position_from_value(&Value::new(1.5, 0.95));
+    assert_eq!(line.find_closest(pointer, &transform).unwrap().index, 0);
+}
+
+#[test]
+fn test_line_fill_with_gap() {
+    let values = vec![
+        Value::new(0.0, 1.0),
+        Value::new(1.0, -1.0),
+        Value::new(2.0, f64::NAN),
+        Value::new(3.0, 1.0),
+        Value::new(4.0, 1.0),
+    ];
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(400.0, 100.0));
+    // Zoomed in so far that the reference line is far below the frame.
+    let bounds = PlotBounds::from_min_max([0.0, -2.0], [4.0, 2.0]);
+    let transform = ScreenTransform::new(frame, bounds, false, false);
+    let line = Line::new(Values::from_values(values))
+        .color(Color32::RED)
+        .fill(-1e9)
+        .signed_fill_alpha(0.5, 0.2);
+    let mut shapes = Vec::new();
+    crate::__run_test_ui(|ui| {
+        shapes.clear();
+        line.get_shapes(ui, &transform, &HighlightStyle::default(), &mut shapes);
+    });
+
+    let mesh = match &shapes[0] {
+        Shape::Mesh(mesh) => mesh,
+        _ => panic!("expected the fill first"),
+    };
+    // The segments next to the NaN value are not filled.
+    assert_eq!(mesh.indices.len(), 2 * 2 * 3);
+    assert!(mesh
+        .vertices
+        .iter()
+        .all(|v| v.pos.y.is_finite() && v.pos.y <= frame.bottom() + 1.0));
+    // The line is split at the gap.
+    assert_eq!(shapes.len(), 3);
+}
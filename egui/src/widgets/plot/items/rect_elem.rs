@@ -1,6 +1,5 @@
-use super::{Orientation, Value};
+use super::{HighlightStyle, Orientation, Value};
 use crate::plot::transform::{PlotBounds, ScreenTransform};
-use epaint::emath::NumExt;
 use epaint::{Color32, Rgba, Stroke};
 
 /// Trait that abstracts from rectangular 'Value'-like elements, such as bars or boxes
@@ -52,10 +51,14 @@ pub(super) trait RectElement {
 // ----------------------------------------------------------------------------
 // Helper functions
 
-pub(super) fn highlighted_color(mut stroke: Stroke, fill: Color32) -> (Stroke, Color32) {
-    stroke.width *= 2.0;
+pub(super) fn highlighted_color(
+    stroke: Stroke,
+    fill: Color32,
+    highlight: &HighlightStyle,
+) -> (Stroke, Color32) {
+    let stroke = highlight.stroke(stroke);
     let fill = Rgba::from(fill);
-    let fill_alpha = (2.0 * fill.a()).at_most(1.0);
+    let fill_alpha = highlight.fill_alpha(fill.a());
     let fill = fill.to_opaque().multiply(fill_alpha);
     (stroke, fill.into())
 }
@@ -66,23 +66,23 @@ impl LineStyle {
         &self,
         line: Vec<Pos2>,
         mut stroke: Stroke,
-        highlight: bool,
+        highlight: Option<&HighlightStyle>,
         shapes: &mut Vec<Shape>,
     ) {
         match line.len() {
             0 => {}
             1 => {
                 let mut radius = stroke.width / 2.0;
-                if highlight {
-                    radius *= 2f32.sqrt();
+                if let Some(highlight) = highlight {
+                    radius = highlight.radius(radius);
                 }
                 shapes.push(Shape::circle_filled(line[0], radius, stroke.color));
             }
             _ => {
                 match self {
                     LineStyle::Solid => {
-                        if highlight {
-                            stroke.width *= 2.0;
+                        if let Some(highlight) = highlight {
+                            stroke = highlight.stroke(stroke);
                         }
                         shapes.push(Shape::line(line, stroke));
                     }
@@ -90,14 +90,14 @@ impl LineStyle {
                         // Take the stroke width for the radius even though it's not "correct", otherwise
                         // the dots would become too small.
                         let mut radius = stroke.width;
-                        if highlight {
-                            radius *= 2f32.sqrt();
+                        if let Some(highlight) = highlight {
+                            radius = highlight.radius(radius);
                         }
                         shapes.extend(Shape::dotted_line(&line, stroke.color, *spacing, radius));
                     }
                     LineStyle::Dashed { length } => {
-                        if highlight {
-                            stroke.width *= 2.0;
+                        if let Some(highlight) = highlight {
+                            stroke = highlight.stroke(stroke);
                         }
                         let golden_ratio = (5.0_f32.sqrt() - 1.0) / 2.0; // 0.61803398875
                         shapes.extend(Shape::dashed_line(
@@ -113,6 +113,119 @@ impl LineStyle {
     }
 }
 
+/// How highlighted items are emphasized, e.g. when hovering their legend entry.
+///
+/// See [`crate::plot::Plot::highlight_style`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct HighlightStyle {
+    /// Factor for the width of lines and outlines. Radii of points and dots grow by its square
+    /// root, so that their area grows by the factor. Default: `2.0`.
+    pub width_factor: f32,
+    /// Factor for the opacity of filled areas, which is capped at fully opaque. Default: `2.0`.
+    pub fill_alpha_factor: f32,
+}
+
+impl Default for HighlightStyle {
+    fn default() -> Self {
+        Self {
+            width_factor: 2.0,
+            fill_alpha_factor: 2.0,
+        }
+    }
+}
+
+impl HighlightStyle {
+    /// Factor for the width of lines and outlines. Default: `2.0`.
+    pub fn width_factor(mut self, width_factor: f32) -> Self {
+        self.width_factor = width_factor;
+        self
+    }
+
+    /// Factor for the opacity of filled areas. Default: `2.0`.
+    pub fn fill_alpha_factor(mut self, fill_alpha_factor: f32) -> Self {
+        self.fill_alpha_factor = fill_alpha_factor;
+        self
+    }
+
+    /// No emphasis at all, highlighted items look like all others.
+    pub fn none() -> Self {
+        Self {
+            width_factor: 1.0,
+            fill_alpha_factor: 1.0,
+        }
+    }
+
+    pub(super) fn stroke(&self, mut stroke: Stroke) -> Stroke {
+        stroke.width *= self.width_factor;
+        stroke
+    }
+
+    /// The outline of highlighted items without lines or fills of their own, e.g. texts and
+    /// images, which is the given stroke with the default factor and vanishes without emphasis.
+    pub(super) fn outline(&self, mut stroke: Stroke) -> Stroke {
+        stroke.width *= (self.width_factor - 1.0).max(0.0);
+        stroke
+    }
+
+    pub(super) fn radius(&self, radius: f32) -> f32 {
+        radius * self.width_factor.max(0.0).sqrt()
+    }
+
+    pub(super) fn fill_alpha(&self, fill_alpha: f32) -> f32 {
+        (fill_alpha * self.fill_alpha_factor).clamp(0.0, 1.0)
+    }
+}
+
+/// The shape at the ends of lines and dashes.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum LineCap {
+    /// The line ends exactly at its end points.
+    Butt,
+    /// The line ends in a half circle around each end point.
+    Round,
+    /// The line extends beyond each end point by half its width.
+    Square,
+}
+
+impl Default for LineCap {
+    fn default() -> Self {
+        Self::Butt
+    }
+}
+
+impl LineCap {
+    /// Adds caps to the open paths and line segments among the shapes, e.g. the output of
+    /// [`LineStyle::style_line`].
+    pub(super) fn apply(self, shapes: &mut Vec<Shape>) {
+        let mut caps = Vec::new();
+        for shape in shapes.iter_mut() {
+            let (points, stroke) = match shape {
+                Shape::LineSegment { points, stroke } => (&mut points[..], *stroke),
+                Shape::Path(path) if !path.closed => (&mut path.points[..], path.stroke),
+                _ => continue,
+            };
+            if points.len() < 2 || stroke.width <= 0.0 {
+                continue;
+            }
+            let radius = stroke.width / 2.0;
+            let last = points.len() - 1;
+            for (end, next) in [(0, 1), (last, last - 1)] {
+                match self {
+                    LineCap::Butt => {}
+                    LineCap::Round => {
+                        caps.push(Shape::circle_filled(points[end], radius, stroke.color));
+                    }
+                    LineCap::Square => {
+                        let direction = (points[end] - points[next]).normalized();
+                        points[end] += direction * radius;
+                    }
+                }
+            }
+        }
+        shapes.extend(caps);
+    }
+}
+
 impl ToString for LineStyle {
     fn to_string(&self) -> String {
         match self {
@@ -125,6 +238,173 @@ impl ToString for LineStyle {
 
 // ----------------------------------------------------------------------------
 
+/// How to fill an area, e.g. of a [`super::Polygon`] or a [`super::Bar`].
+///
+/// Patterns other than [`FillPattern::Solid`] are drawn as lines in the stroke color instead of a
+/// solid fill, which makes them distinguishable in print and in grayscale.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum FillPattern {
+    /// A solid fill in the fill color.
+    Solid,
+    /// Horizontal lines, `spacing` points apart.
+    Horizontal { spacing: f32 },
+    /// Vertical lines, `spacing` points apart.
+    Vertical { spacing: f32 },
+    /// Lines rising from left to right, `spacing` points apart.
+    Diagonal { spacing: f32 },
+    /// Lines in both diagonal directions, `spacing` points apart.
+    CrossHatch { spacing: f32 },
+}
+
+impl Default for FillPattern {
+    fn default() -> Self {
+        Self::Solid
+    }
+}
+
+impl FillPattern {
+    /// [`Self::Horizontal`] with a spacing of 6 points.
+    pub fn horizontal() -> Self {
+        Self::Horizontal { spacing: 6.0 }
+    }
+
+    /// [`Self::Vertical`] with a spacing of 6 points.
+    pub fn vertical() -> Self {
+        Self::Vertical { spacing: 6.0 }
+    }
+
+    /// [`Self::Diagonal`] with a spacing of 6 points.
+    pub fn diagonal() -> Self {
+        Self::Diagonal { spacing: 6.0 }
+    }
+
+    /// [`Self::CrossHatch`] with a spacing of 6 points.
+    pub fn cross_hatch() -> Self {
+        Self::CrossHatch { spacing: 6.0 }
+    }
+
+    /// Adds the pattern lines for the given convex polygon (in screen coordinates).
+    /// Does nothing for [`FillPattern::Solid`].
+    pub(super) fn hatch(&self, polygon: &[Pos2], stroke: Stroke, shapes: &mut Vec<Shape>) {
+        let frac_1_sqrt_2 = std::f32::consts::FRAC_1_SQRT_2;
+        let diagonal = Vec2::new(frac_1_sqrt_2, -frac_1_sqrt_2);
+        let anti_diagonal = Vec2::new(frac_1_sqrt_2, frac_1_sqrt_2);
+        match *self {
+            FillPattern::Solid => {}
+            FillPattern::Horizontal { spacing } => {
+                hatch_convex(polygon, Vec2::X, spacing, stroke, shapes);
+            }
+            FillPattern::Vertical { spacing } => {
+                hatch_convex(polygon, Vec2::Y, spacing, stroke, shapes);
+            }
+            FillPattern::Diagonal { spacing } => {
+                hatch_convex(polygon, diagonal, spacing, stroke, shapes);
+            }
+            FillPattern::CrossHatch { spacing } => {
+                hatch_convex(polygon, diagonal, spacing, stroke, shapes);
+                hatch_convex(polygon, anti_diagonal, spacing, stroke, shapes);
+            }
+        }
+    }
+}
+
+/// Fills a convex polygon with parallel lines along the unit vector `dir`, clipped to the polygon.
+fn hatch_convex(
+    polygon: &[Pos2],
+    dir: Vec2,
+    spacing: f32,
+    stroke: Stroke,
+    shapes: &mut Vec<Shape>,
+) {
+    if polygon.len() < 3 || spacing <= 0.0 {
+        return;
+    }
+    let dot = |p: Pos2, v: Vec2| p.x * v.x + p.y * v.y;
+    let normal = dir.rot90();
+    let offset = |p: Pos2| dot(p, normal);
+    let (min, max) = polygon
+        .iter()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), p| {
+            (min.min(offset(*p)), max.max(offset(*p)))
+        });
+
+    // Align the lines to a global grid so the pattern does not move with the shape.
+    let mut line_offset = (min / spacing).ceil() * spacing;
+    while line_offset <= max {
+        let mut start = None::<(f32, Pos2)>;
+        let mut end = None::<(f32, Pos2)>;
+        for (i, a) in polygon.iter().enumerate() {
+            let b = polygon[(i + 1) % polygon.len()];
+            let (da, db) = (offset(*a) - line_offset, offset(b) - line_offset);
+            if (da <= 0.0 && db >= 0.0) || (da >= 0.0 && db <= 0.0) {
+                let t = if da == db { 0.0 } else { da / (da - db) };
+                let point = *a + t * (b - *a);
+                let along = dot(point, dir);
+                if start.map_or(true, |(s, _)| along < s) {
+                    start = Some((along, point));
+                }
+                if end.map_or(true, |(e, _)| along > e) {
+                    end = Some((along, point));
+                }
+            }
+        }
+        if let (Some((_, p0)), Some((_, p1))) = (start, end) {
+            shapes.push(Shape::line_segment([p0, p1], stroke));
+        }
+        line_offset += spacing;
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Determines how arrows with coincident origin and tip are drawn.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ZeroLengthMode {
+    /// Don't draw zero-length arrows.
+    Skip,
+    /// Draw a dot at the origin of zero-length arrows.
+    Dot,
+}
+
+impl Default for ZeroLengthMode {
+    fn default() -> Self {
+        Self::Skip
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// How a line connects consecutive values, see [`super::Line::step_mode`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StepMode {
+    /// Connect the values with straight segments.
+    None,
+    /// Each value holds back to the previous one, i.e. the line steps to the next value right
+    /// after the current one.
+    Before,
+    /// Each value holds until the next one, e.g. for sampled states.
+    After,
+    /// The line steps halfway between consecutive values.
+    Center,
+}
+
+impl Default for StepMode {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// The side of a threshold, see [`super::Line::fill_where`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FillSide {
+    Above,
+    Below,
+}
+
+// ----------------------------------------------------------------------------
+
 /// Determines whether a plot element is vertically or horizontally oriented.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Orientation {
@@ -230,6 +510,30 @@ impl Values {
         Self::from_values(values)
     }
 
+    /// From complex samples given as `(re, im)`, for an Argand or Nyquist diagram: the x-values
+    /// are the real parts and the y-values the imaginary parts.
+    pub fn from_complex_reim(samples: &[(f64, f64)]) -> Self {
+        Self::from_values_iter(samples.iter().map(|&(re, im)| Value::new(re, im)))
+    }
+
+    /// From complex samples given as `(re, im)`, as two series of the magnitude and the phase
+    /// (in radians, between `-π` and `π`) of each sample. The x-values are the indices of the
+    /// samples.
+    pub fn from_complex_magphase(samples: &[(f64, f64)]) -> (Self, Self) {
+        let magnitude = samples
+            .iter()
+            .enumerate()
+            .map(|(i, &(re, im))| Value::new(i as f64, re.hypot(im)));
+        let phase = samples
+            .iter()
+            .enumerate()
+            .map(|(i, &(re, im))| Value::new(i as f64, im.atan2(re)));
+        (
+            Self::from_values_iter(magnitude),
+            Self::from_values_iter(phase),
+        )
+    }
+
     /// Returns true if there are no data points available and there is no function to generate any.
     pub(crate) fn is_empty(&self) -> bool {
         self.generator.is_none() && self.values.is_empty()
@@ -253,6 +557,71 @@ impl Values {
         }
     }
 
+    /// Collapse consecutive values sharing the same x-coordinate into the last of them, so that
+    /// the series has no vertical segments.
+    pub(super) fn remove_vertical_segments(&mut self) {
+        let mut collapsed: Vec<Value> = Vec::with_capacity(self.values.len());
+        for value in self.values.drain(..) {
+            match collapsed.last_mut() {
+                Some(last) if last.x == value.x => *last = value,
+                _ => collapsed.push(value),
+            }
+        }
+        self.values = collapsed;
+    }
+
+    /// The values with the corners of a staircase inserted between consecutive ones, so that
+    /// straight segments through them draw the steps.
+    pub(super) fn stepped(&self, mode: StepMode) -> Vec<Value> {
+        if mode == StepMode::None || self.values.len() < 2 {
+            return self.values.clone();
+        }
+        let mut steps = Vec::with_capacity(self.values.len() * 3);
+        for pair in self.values.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            steps.push(a);
+            match mode {
+                StepMode::None => {}
+                StepMode::Before => steps.push(Value::new(a.x, b.y)),
+                StepMode::After => steps.push(Value::new(b.x, a.y)),
+                StepMode::Center => {
+                    let x = (a.x + b.x) / 2.0;
+                    steps.push(Value::new(x, a.y));
+                    steps.push(Value::new(x, b.y));
+                }
+            }
+        }
+        steps.extend(self.values.last().copied());
+        steps
+    }
+
+    /// Remove the values that are not needed to draw the series within the given x-range. Values
+    /// in the range are kept, and so are their neighbors outside of it, so that segments leaving
+    /// the range are still drawn.
+    pub(super) fn clip_to_x_range(&mut self, x_range: RangeInclusive<f64>) {
+        let (min, max) = (*x_range.start(), *x_range.end());
+        let overlaps = |a: &Value, b: &Value| a.x.min(b.x) <= max && a.x.max(b.x) >= min;
+        // Compact in place: the write index never overtakes the read index, so `previous` and
+        // the next value are still the original ones.
+        let mut kept = 0;
+        let mut previous: Option<Value> = None;
+        for i in 0..self.values.len() {
+            let value = self.values[i];
+            let keep = x_range.contains(&value.x)
+                || previous.map_or(false, |previous| overlaps(&previous, &value))
+                || self
+                    .values
+                    .get(i + 1)
+                    .map_or(false, |next| overlaps(&value, next));
+            previous = Some(value);
+            if keep {
+                self.values[kept] = value;
+                kept += 1;
+            }
+        }
+        self.values.truncate(kept);
+    }
+
     /// Returns the intersection of two ranges if they intersect.
     fn range_intersection(
         range1: &RangeInclusive<f64>,
@@ -303,8 +672,7 @@ impl MarkerShape {
             Self::Right,
             Self::Asterisk,
         ]
-        .iter()
-        .copied()
+        .into_iter()
     }
 }
 
@@ -335,11 +703,27 @@ struct ExplicitGenerator {
 
 // ----------------------------------------------------------------------------
 
-/// Result of [`super::PlotItem::find_closest()`] search, identifies an element inside the item for immediate use
-pub(crate) struct ClosestElem {
+/// Identifies the element of a plot item closest to the pointer, e.g. for
+/// [`crate::plot::Plot::on_item_double_click`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClosestElem {
     /// Position of hovered-over value (or bar/box-plot/...) in PlotItem
     pub index: usize,
 
     /// Squared distance from the mouse cursor (needed to compare against other PlotItems, which might be nearer)
     pub dist_sq: f32,
+
+    /// The id given to the element by the user, e.g. with [`super::Points::ids`], or else its
+    /// index.
+    pub user_id: u64,
+}
+
+impl ClosestElem {
+    pub(crate) fn new(index: usize, dist_sq: f32) -> Self {
+        Self {
+            index,
+            dist_sq,
+            user_id: index as u64,
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use std::{collections::BTreeMap, string::String};
+use std::{collections::BTreeMap, string::String, sync::Arc};
 
 use epaint::ahash::AHashSet;
 
@@ -28,12 +28,35 @@ impl Corner {
     }
 }
 
+/// Where to place the plot legend, see [`Legend::position`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LegendPosition {
+    /// In a corner of the plot area, on top of the items.
+    Inside(Corner),
+
+    /// To the right of the plot area, which is narrowed to make room for it. Entries that don't
+    /// fit into the height of the plot wrap into further columns.
+    RightOutside,
+
+    /// Below the plot area, which is shortened to make room for it. Entries that don't fit into
+    /// the width of the plot wrap into further rows.
+    BottomOutside,
+}
+
+impl From<Corner> for LegendPosition {
+    fn from(corner: Corner) -> Self {
+        Self::Inside(corner)
+    }
+}
+
 /// The configuration for a plot legend.
 #[derive(Clone, PartialEq)]
 pub struct Legend {
     pub text_style: TextStyle,
     pub background_alpha: f32,
-    pub position: Corner,
+    pub position: LegendPosition,
+    pub margin: Vec2,
+    pub searchable: bool,
 }
 
 impl Default for Legend {
@@ -41,7 +64,9 @@ impl Default for Legend {
         Self {
             text_style: TextStyle::Body,
             background_alpha: 0.75,
-            position: Corner::RightTop,
+            position: LegendPosition::Inside(Corner::RightTop),
+            margin: Vec2::splat(4.0),
+            searchable: false,
         }
     }
 }
@@ -59,9 +84,25 @@ impl Legend {
         self
     }
 
-    /// In which corner to place the legend. Default: `Corner::RightTop`.
-    pub fn position(mut self, corner: Corner) -> Self {
-        self.position = corner;
+    /// Where to place the legend, either in a [`Corner`] of the plot or beside it, see
+    /// [`LegendPosition`]. Default: `Corner::RightTop`.
+    pub fn position(mut self, position: impl Into<LegendPosition>) -> Self {
+        self.position = position.into();
+        self
+    }
+
+    /// The space between the legend and the edges of the plot. Increase it to keep the legend
+    /// clear of the axis tick labels drawn along the edges. Default: `4.0` on both axes.
+    pub fn margin(mut self, margin: impl Into<Vec2>) -> Self {
+        self.margin = margin.into();
+        self
+    }
+
+    /// Show a search field above the entries that narrows them down to the names containing the
+    /// search text, ignoring case. Useful for plots with many items. Items that are filtered out
+    /// keep their visibility. Default: `false`.
+    pub fn searchable(mut self, searchable: bool) -> Self {
+        self.searchable = searchable;
         self
     }
 }
@@ -71,6 +112,8 @@ struct LegendEntry {
     color: Color32,
     checked: bool,
     hovered: bool,
+    /// Whether the entry is only there for items that were dropped for having no values.
+    empty: bool,
 }
 
 impl LegendEntry {
@@ -79,22 +122,36 @@ impl LegendEntry {
             color,
             checked,
             hovered: false,
+            empty: false,
         }
     }
 
-    fn ui(&mut self, ui: &mut Ui, text: String, text_style: &TextStyle) -> Response {
+    fn galley(&self, ui: &Ui, name: &str, text_style: &TextStyle) -> Arc<Galley> {
+        let text = if self.empty {
+            format!("{} (empty)", name)
+        } else {
+            name.to_owned()
+        };
+        let font_id = text_style.resolve(ui.style());
+        ui.fonts()
+            .layout_delayed_color(text, font_id, f32::INFINITY)
+    }
+
+    /// The size of the entry: its icon, which is as large as the height of the text, and text.
+    fn size(&self, ui: &Ui, name: &str, text_style: &TextStyle) -> Vec2 {
+        let text_size = self.galley(ui, name, text_style).size();
+        vec2(1.2 * text_size.y, 0.0) + text_size
+    }
+
+    fn ui(&mut self, ui: &mut Ui, name: &str, text_style: &TextStyle) -> Response {
+        let galley = self.galley(ui, name, text_style);
         let Self {
             color,
             checked,
             hovered,
+            empty,
         } = self;
 
-        let font_id = text_style.resolve(ui.style());
-
-        let galley = ui
-            .fonts()
-            .layout_delayed_color(text, font_id, f32::INFINITY);
-
         let icon_size = galley.size().y;
         let icon_spacing = icon_size / 5.0;
         let total_extra = vec2(icon_size + icon_spacing, 0.0);
@@ -145,7 +202,12 @@ impl LegendEntry {
         };
 
         let text_position = pos2(text_position_x, rect.center().y - 0.5 * galley.size().y);
-        painter.galley_with_color(text_position, galley, visuals.text_color());
+        let text_color = if *empty {
+            ui.visuals().weak_text_color()
+        } else {
+            visuals.text_color()
+        };
+        painter.galley_with_color(text_position, galley, text_color);
 
         *checked ^= response.clicked_by(PointerButton::Primary);
         *hovered = response.hovered();
@@ -159,23 +221,37 @@ pub(super) struct LegendWidget {
     rect: Rect,
     entries: BTreeMap<String, LegendEntry>,
     config: Legend,
+    /// The text in the search field, see [`Legend::searchable`].
+    filter: String,
+    /// For a legend outside of the plot, the height of its columns or the width of its rows,
+    /// at which the entries wrap.
+    wrap_extent: f32,
 }
 
+/// The inner margin of the legend background.
+const FRAME_MARGIN: Vec2 = vec2(8.0, 4.0);
+
+/// The width of the search field, see [`Legend::searchable`].
+const SEARCH_WIDTH: f32 = 100.0;
+
 impl LegendWidget {
-    /// Create a new legend from items, the names of items that are hidden and the style of the
-    /// text. Returns `None` if the legend has no entries.
+    /// Create a new legend from items, the names of items that were dropped for having no values,
+    /// the names of items that are hidden, the style of the text and the text of the search
+    /// field. Returns `None` if the legend has no entries.
     pub(super) fn try_new(
         rect: Rect,
         config: Legend,
         items: &[Box<dyn PlotItem>],
+        empty_items: &[String],
         hidden_items: &AHashSet<String>,
+        filter: String,
     ) -> Option<Self> {
         // Collect the legend entries. If multiple items have the same name, they share a
         // checkbox. If their colors don't match, we pick a neutral color for the checkbox.
         let mut entries: BTreeMap<String, LegendEntry> = BTreeMap::new();
         items
             .iter()
-            .filter(|item| !item.name().is_empty())
+            .filter(|item| !item.name().is_empty() && item.show_in_legend())
             .for_each(|item| {
                 entries
                     .entry(item.name().to_string())
@@ -191,13 +267,85 @@ impl LegendWidget {
                         LegendEntry::new(color, checked)
                     });
             });
+        for name in empty_items {
+            entries.entry(name.clone()).or_insert_with(|| LegendEntry {
+                empty: true,
+                ..LegendEntry::new(Color32::TRANSPARENT, !hidden_items.contains(name))
+            });
+        }
         (!entries.is_empty()).then(|| Self {
             rect,
             entries,
             config,
+            filter,
+            wrap_extent: f32::INFINITY,
         })
     }
 
+    /// Takes the space for a legend outside of the plot from `plot_rect` and returns the
+    /// remaining plot area. A legend inside the plot leaves it unchanged.
+    pub(super) fn reserve_space(&mut self, ui: &Ui, plot_rect: Rect) -> Rect {
+        // Along the axis in which the legend is placed next to the plot, the entries are
+        // wrapped along the other one.
+        let axis = match self.config.position {
+            LegendPosition::Inside(_) => return plot_rect,
+            LegendPosition::RightOutside => 0,
+            LegendPosition::BottomOutside => 1,
+        };
+        let search_height = self.search_height(ui);
+        let outer_margin = 2.0 * (self.config.margin + FRAME_MARGIN) + Vec2::splat(2.0);
+        self.wrap_extent = (plot_rect.size()[1 - axis]
+            - outer_margin[1 - axis]
+            - if axis == 0 { search_height } else { 0.0 })
+        .at_least(0.0);
+
+        let sizes: Vec<Vec2> = self
+            .entries
+            .iter()
+            .map(|(name, entry)| entry.size(ui, name, &self.config.text_style))
+            .collect();
+        let spacing = ui.spacing().item_spacing;
+        let mut size = Vec2::ZERO;
+        let mut start = 0;
+        for count in wrap_lengths(
+            sizes.iter().map(|size| size[1 - axis]),
+            self.wrap_extent,
+            spacing[1 - axis],
+        ) {
+            let group = &sizes[start..start + count];
+            start += count;
+            let along = group.iter().map(|size| size[1 - axis]).sum::<f32>()
+                + spacing[1 - axis] * (count - 1) as f32;
+            let across = group.iter().map(|size| size[axis]).fold(0.0, f32::max);
+            size[1 - axis] = size[1 - axis].max(along);
+            size[axis] += across + if size[axis] > 0.0 { spacing[axis] } else { 0.0 };
+        }
+        if self.config.searchable {
+            size.x = size.x.max(SEARCH_WIDTH);
+            size.y += search_height;
+        }
+        let size = (size + outer_margin).min(plot_rect.size());
+
+        let mut remaining = plot_rect;
+        if axis == 0 {
+            remaining.max.x -= size.x;
+            self.rect = Rect::from_min_max(pos2(remaining.max.x, plot_rect.top()), plot_rect.max);
+        } else {
+            remaining.max.y -= size.y;
+            self.rect = Rect::from_min_max(pos2(plot_rect.left(), remaining.max.y), plot_rect.max);
+        }
+        remaining
+    }
+
+    /// The height of the search field including the spacing below it, if there is one.
+    fn search_height(&self, ui: &Ui) -> f32 {
+        if self.config.searchable {
+            ui.spacing().interact_size.y + ui.spacing().item_spacing.y
+        } else {
+            0.0
+        }
+    }
+
     // Get the names of the hidden items.
     pub fn get_hidden_items(&self) -> AHashSet<String> {
         self.entries
@@ -214,6 +362,11 @@ impl LegendWidget {
             .find(|(_, entry)| entry.hovered)
             .map(|(name, _)| name.to_string())
     }
+
+    // Get the text of the search field.
+    pub fn get_filter(&self) -> &str {
+        &self.filter
+    }
 }
 
 impl Widget for &mut LegendWidget {
@@ -222,24 +375,25 @@ impl Widget for &mut LegendWidget {
             rect,
             entries,
             config,
+            filter,
+            wrap_extent,
         } = self;
 
         let main_dir = match config.position {
-            Corner::LeftTop | Corner::RightTop => Direction::TopDown,
-            Corner::LeftBottom | Corner::RightBottom => Direction::BottomUp,
+            LegendPosition::Inside(Corner::LeftBottom | Corner::RightBottom) => Direction::BottomUp,
+            _ => Direction::TopDown,
         };
         let cross_align = match config.position {
-            Corner::LeftTop | Corner::LeftBottom => Align::LEFT,
-            Corner::RightTop | Corner::RightBottom => Align::RIGHT,
+            LegendPosition::Inside(Corner::RightTop | Corner::RightBottom) => Align::RIGHT,
+            _ => Align::LEFT,
         };
         let layout = Layout::from_main_dir_and_cross_align(main_dir, cross_align);
-        let legend_pad = 4.0;
-        let legend_rect = rect.shrink(legend_pad);
+        let legend_rect = rect.shrink2(config.margin);
         let mut legend_ui = ui.child_ui(legend_rect, layout);
         legend_ui
             .scope(|ui| {
                 let background_frame = Frame {
-                    margin: vec2(8.0, 4.0),
+                    margin: FRAME_MARGIN,
                     corner_radius: ui.style().visuals.window_corner_radius,
                     shadow: epaint::Shadow::default(),
                     fill: ui.style().visuals.extreme_bg_color,
@@ -248,14 +402,130 @@ impl Widget for &mut LegendWidget {
                 .multiply_with_opacity(config.background_alpha);
                 background_frame
                     .show(ui, |ui| {
-                        entries
+                        // The search field goes above the entries, also in bottom-up layouts.
+                        let search = |ui: &mut Ui, filter: &mut String| {
+                            config.searchable.then(|| {
+                                ui.add(
+                                    TextEdit::singleline(filter)
+                                        .hint_text("Search")
+                                        .desired_width(SEARCH_WIDTH),
+                                )
+                            })
+                        };
+                        let search_response = if main_dir == Direction::TopDown {
+                            search(ui, filter)
+                        } else {
+                            None
+                        };
+                        let needle = if config.searchable {
+                            filter.to_lowercase()
+                        } else {
+                            String::new()
+                        };
+                        let mut shown: Vec<(&String, &mut LegendEntry)> = entries
                             .iter_mut()
-                            .map(|(name, entry)| entry.ui(ui, name.clone(), &config.text_style))
-                            .reduce(|r1, r2| r1.union(r2))
-                            .unwrap()
+                            .filter(|(name, _)| name.to_lowercase().contains(&needle))
+                            .collect();
+                        let entries_response = match config.position {
+                            LegendPosition::Inside(_) => shown
+                                .into_iter()
+                                .map(|(name, entry)| entry.ui(ui, name, &config.text_style))
+                                .reduce(|r1, r2| r1.union(r2)),
+                            LegendPosition::RightOutside | LegendPosition::BottomOutside => {
+                                wrapped_entries_ui(ui, &mut shown, config, *wrap_extent)
+                            }
+                        };
+                        let search_response = search_response.or_else(|| search(ui, filter));
+                        match (search_response, entries_response) {
+                            (Some(r1), Some(r2)) => r1.union(r2),
+                            (r1, r2) => r1.or(r2).unwrap(),
+                        }
                     })
                     .inner
             })
             .inner
     }
 }
+
+/// Shows the entries of a legend outside of the plot in columns of at most `wrap_extent` height
+/// for [`LegendPosition::RightOutside`], or rows of at most that width otherwise.
+fn wrapped_entries_ui(
+    ui: &mut Ui,
+    entries: &mut [(&String, &mut LegendEntry)],
+    config: &Legend,
+    wrap_extent: f32,
+) -> Option<Response> {
+    let columns = config.position == LegendPosition::RightOutside;
+    let axis = if columns { 1 } else { 0 };
+    let lengths = wrap_lengths(
+        entries
+            .iter()
+            .map(|(name, entry)| entry.size(ui, name, &config.text_style)[axis]),
+        wrap_extent,
+        ui.spacing().item_spacing[axis],
+    );
+
+    let mut response: Option<Response> = None;
+    let mut group_ui = |ui: &mut Ui, group: &mut [(&String, &mut LegendEntry)]| {
+        for (name, entry) in group {
+            let entry_response = entry.ui(ui, name, &config.text_style);
+            response = Some(match response.take() {
+                Some(response) => response.union(entry_response),
+                None => entry_response,
+            });
+        }
+    };
+    let mut rest = entries;
+    let layout = if columns {
+        Layout::left_to_right().with_cross_align(Align::Min)
+    } else {
+        Layout::top_down(Align::Min)
+    };
+    ui.with_layout(layout, |ui| {
+        for count in lengths {
+            let (group, tail) = std::mem::take(&mut rest).split_at_mut(count);
+            rest = tail;
+            if columns {
+                ui.vertical(|ui| group_ui(ui, group));
+            } else {
+                ui.horizontal(|ui| group_ui(ui, group));
+            }
+        }
+    });
+    response
+}
+
+/// Splits items of the given lengths into consecutive groups whose lengths, including the
+/// `spacing` between the items, add up to at most `max_length`, and returns the number of items
+/// in each group. Every group has at least one item.
+fn wrap_lengths(lengths: impl Iterator<Item = f32>, max_length: f32, spacing: f32) -> Vec<usize> {
+    let mut counts = Vec::new();
+    let mut current = 0.0;
+    for length in lengths {
+        match counts.last_mut() {
+            Some(count) if current + spacing + length <= max_length => {
+                *count += 1;
+                current += spacing + length;
+            }
+            _ => {
+                counts.push(1);
+                current = length;
+            }
+        }
+    }
+    counts
+}
+
+#[test]
+fn test_wrap_lengths() {
+    let lengths = [10.0, 10.0, 10.0, 30.0, 5.0];
+    assert_eq!(
+        wrap_lengths(lengths.iter().copied(), 25.0, 2.0),
+        vec![2, 1, 1, 1]
+    );
+    assert_eq!(wrap_lengths(lengths.iter().copied(), 100.0, 2.0), vec![5]);
+    assert_eq!(
+        wrap_lengths(std::iter::empty(), 25.0, 2.0),
+        Vec::<usize>::new()
+    );
+}
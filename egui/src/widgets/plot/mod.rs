@@ -1,25 +1,33 @@
 //! Simple plotting library.
 
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, rc::Rc, sync::Arc};
 
 use crate::*;
-use epaint::ahash::AHashSet;
+use epaint::ahash::{AHashMap, AHashSet};
 use epaint::color::Hsva;
 use epaint::util::FloatOrd;
+use epaint::ClippedShape;
 use items::PlotItem;
 use legend::LegendWidget;
-use transform::{PlotBounds, ScreenTransform};
 
+pub use grid::PlotGrid;
 pub use items::{
-    Arrows, Bar, BarChart, BoxElem, BoxPlot, BoxSpread, HLine, Line, LineStyle, MarkerShape,
-    Orientation, PlotImage, Points, Polygon, Text, VLine, Value, Values,
+    format_magnitude_aware, format_power_of_ten, grayscale, viridis, Arrows, Bar, BarChart,
+    BoxElem, BoxPlot, BoxSpread, Circle, ClosestElem, FillPattern, FillSide, HLine, HSpan, HeatMap,
+    HighlightStyle, Line, LineCap, LineStyle, MarkerShape, Orientation, PathText, PlotImage,
+    Points, Polygon, Ribbon, StackedArea, StepMode, Text, VLine, VSpan, Value, Values,
+    ZeroLengthMode,
 };
-pub use legend::{Corner, Legend};
+pub use legend::{Corner, Legend, LegendPosition};
+pub use transform::{nice_bounds, AxisScale, PlotBounds, ScreenTransform};
 
 use self::items::{num_decimals_with_max_digits, HoverConfig};
 
+mod clip;
+mod grid;
 mod items;
 mod legend;
+mod svg;
 mod transform;
 
 type HoverFormatterFn = dyn Fn(&HoverConfig, &str, &Value) -> String;
@@ -28,6 +36,17 @@ type HoverFormatter = Box<HoverFormatterFn>;
 type AxisFormatterFn = dyn Fn(f64) -> String;
 type AxisFormatter = Option<Box<AxisFormatterFn>>;
 
+type GridRendererFn = dyn Fn(&GridContext<'_>, &mut Vec<Shape>);
+type GridSpacerFn = dyn Fn(GridInput) -> Vec<GridMark>;
+type OverlayFn = dyn Fn(&mut Ui, Rect);
+type ItemDoubleClickFn = dyn Fn(&str, &ClosestElem) -> Option<PlotBounds>;
+
+/// How close the pointer has to be to an element of an item to hover or double click it.
+const INTERACT_RADIUS: f32 = 16.0;
+
+/// How long the transition to the bounds of [`Plot::on_item_double_click`] takes, in seconds.
+const ITEM_ZOOM_DURATION: f64 = 0.3;
+
 // ----------------------------------------------------------------------------
 
 /// Information about the plot that has to persist between frames.
@@ -41,6 +60,52 @@ struct PlotMemory {
     last_screen_transform: ScreenTransform,
     /// Allows to remember the first click position when performing a boxed zoom
     last_click_pos_for_zoom: Option<Pos2>,
+    /// Where the selection of [`Plot::allow_selection`] that is being dragged out started.
+    selection_start: Option<Pos2>,
+    /// A view change requested via [`PlotResponse`], applied on the next frame.
+    view_action: Option<ViewAction>,
+    /// Screen position of [`Plot::anchor_value`] relative to the frame, from the last frame.
+    anchor_offset: Option<Vec2>,
+    /// The union of all automatic data bounds since the last reset, for [`UpdateMode::OnlyGrow`].
+    grown_bounds: PlotBounds,
+    /// When the automatic bounds were last updated, for [`UpdateMode::Debounced`].
+    last_auto_bounds_update: f64,
+    /// Whether the current drag was claimed by [`Plot::capture_drag`].
+    drag_captured: bool,
+    /// The start bounds, target bounds and start time of a transition started by
+    /// [`Plot::on_item_double_click`].
+    item_zoom: Option<(PlotBounds, PlotBounds, f64)>,
+    /// The text in the search field of the legend, see [`Legend::searchable`].
+    legend_filter: String,
+    /// The range of the secondary y axis for [`Self::last_screen_transform`].
+    secondary_range: Option<SecondaryRange>,
+    /// The shapes of the items from the last frame, see [`Plot::cache_shapes`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    shape_cache: ShapeCache,
+    /// The names of the empty items that were already logged, see [`Plot::warn_on_empty_items`].
+    warned_empty_items: AHashSet<String>,
+}
+
+/// The shapes of the items with a [`PlotItem::content_hash`] by their index and hash, see
+/// [`Plot::cache_shapes`]. The index tells apart items that were given the same hash.
+#[derive(Clone, Default)]
+struct ShapeCache(AHashMap<(usize, u64), CachedShapes>);
+
+#[derive(Clone)]
+struct CachedShapes {
+    highlighted: bool,
+    transform: ScreenTransform,
+    shapes: Arc<Vec<Shape>>,
+}
+
+/// A view change that is deferred to the next frame.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ViewAction {
+    /// Return to automatic bounds, like double-clicking does.
+    ResetBounds,
+    /// Fit the bounds to the content once, without enabling automatic bounds.
+    AutoFit,
 }
 
 impl PlotMemory {
@@ -110,6 +175,149 @@ impl LinkedAxisGroup {
     }
 }
 
+/// Synchronizes the hover crosshair of multiple plots: while one plot of the group is hovered, the
+/// others show a faint vertical ruler at the same x-coordinate. Can be added while building a plot
+/// with [`Plot::link_hover`]. This only synchronizes the crosshair, use [`LinkedAxisGroup`] to
+/// also share the bounds. Contains an internal state, meaning that this object should be stored by
+/// the user between frames.
+#[derive(Clone, Default, PartialEq)]
+pub struct HoverSyncGroup {
+    pub(crate) hovered: Rc<RefCell<Option<(Id, f64)>>>,
+}
+
+impl HoverSyncGroup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update the hover state for the plot with the given id, and return the x-coordinate hovered
+    /// in another plot of the group, if any.
+    fn sync(&self, plot_id: Id, hovered_x: Option<f64>) -> Option<f64> {
+        let mut hovered = self.hovered.borrow_mut();
+        match hovered_x {
+            Some(x) => {
+                *hovered = Some((plot_id, x));
+                None
+            }
+            None => match *hovered {
+                Some((id, _)) if id == plot_id => {
+                    *hovered = None;
+                    None
+                }
+                Some((_, x)) => Some(x),
+                None => None,
+            },
+        }
+    }
+}
+
+/// How often the automatic bounds of a plot follow its content, see [`Plot::auto_bounds_update`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UpdateMode {
+    /// Fit the bounds to the content on every frame.
+    EveryFrame,
+
+    /// The bounds only ever grow to include new content, and never shrink until they are reset
+    /// (e.g. by double-clicking the plot).
+    OnlyGrow,
+
+    /// Fit the bounds to the content at most once every given number of seconds.
+    Debounced(f64),
+}
+
+/// Where the bounds of a plot in the current frame came from, see [`PlotResponse::bounds_source`].
+///
+/// If several apply, the last one in the order of the variants wins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundsSource {
+    /// The bounds of the last frame were kept.
+    Memory,
+
+    /// The bounds were taken from a [`LinkedAxisGroup`].
+    LinkedGroup,
+
+    /// The bounds were fitted to the content.
+    AutoBounds,
+
+    /// The bounds are moving to the target of [`Plot::on_item_double_click`].
+    ItemZoom,
+
+    /// The bounds were set with [`PlotUi::set_plot_bounds`].
+    Explicit,
+
+    /// The bounds were moved to keep the [`Plot::anchor_value`] in place.
+    Anchor,
+
+    /// The bounds were moved by dragging the plot.
+    Drag,
+
+    /// The bounds were set by a boxed zoom.
+    BoxedZoom,
+
+    /// The bounds were changed by zooming or scrolling, or by [`PlotUi::zoom`].
+    Zoom,
+}
+
+/// One of the two axes of a plot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+}
+
+/// The y axis an item is plotted against, see [`Line::on_secondary_axis`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum YAxis {
+    /// The y axis on the left, which is also used for dragging, zooming and the grid.
+    Primary,
+
+    /// The y axis on the right, with its own range that is fitted to its items and follows the
+    /// primary axis when dragging or zooming.
+    Secondary,
+}
+
+/// Relates the range of the secondary y axis to the primary one.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct SecondaryRange {
+    /// A range of the primary y axis.
+    primary: [f64; 2],
+    /// The range of the secondary y axis while the primary one shows `primary`.
+    secondary: [f64; 2],
+}
+
+impl SecondaryRange {
+    /// The range of the secondary axis while the primary one shows `range`. On non-linear axes,
+    /// the ranges are related in the space mapped by the scale.
+    fn map(&self, range: [f64; 2], scale: AxisScale) -> [f64; 2] {
+        let from = scale.forward(self.primary[0])..=scale.forward(self.primary[1]);
+        let to = scale.forward(self.secondary[0])..=scale.forward(self.secondary[1]);
+        range.map(|y| scale.inverse(remap(scale.forward(y), from.clone(), to.clone())))
+    }
+
+    /// The transform for items on the secondary axis, given the one for the primary axes.
+    fn transform(&self, transform: &ScreenTransform) -> ScreenTransform {
+        let mut secondary = transform.clone();
+        let bounds = transform.bounds();
+        let [min, max] = self.map([bounds.min[1], bounds.max[1]], transform.scales()[1]);
+        secondary.bounds_mut().min[1] = min;
+        secondary.bounds_mut().max[1] = max;
+        secondary
+    }
+}
+
+/// The transform to draw the item with, see [`YAxis`].
+fn item_transform(
+    item: &dyn PlotItem,
+    transform: &ScreenTransform,
+    secondary: Option<&SecondaryRange>,
+) -> ScreenTransform {
+    match secondary {
+        Some(secondary) if item.on_secondary_axis() => secondary.transform(transform),
+        _ => transform.clone(),
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum HoverLine {
     None,
@@ -141,6 +349,178 @@ impl Default for HoverLine {
 
 // ----------------------------------------------------------------------------
 
+/// A position on an axis where the plot places a gridline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridMark {
+    /// Value on the axis, in plot coordinates.
+    pub value: f64,
+
+    /// The step size of the coarsest grid this mark is part of. Larger step sizes denote more
+    /// prominent gridlines. For non-linear [`AxisScale`]s it is given in the space mapped by the
+    /// scale, e.g. in decades.
+    pub step_size: f64,
+}
+
+/// Passed to the functions set with [`Plot::x_grid_spacer`] and [`Plot::y_grid_spacer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridInput {
+    /// The visible range of the axis, in plot coordinates.
+    pub bounds: (f64, f64),
+
+    /// The distance in plot coordinates that corresponds to the minimum spacing between
+    /// gridlines, see [`Plot::min_tick_spacing`]. Marks with a smaller step size are drawn
+    /// invisibly faint. For non-linear [`AxisScale`]s it is given in the space mapped by the
+    /// scale.
+    pub base_step_size: f64,
+}
+
+/// Passed to the function set with [`Plot::grid_renderer`].
+pub struct GridContext<'a> {
+    /// The axis the gridlines are for.
+    pub axis: Axis,
+
+    /// The gridlines the plot computed for this axis.
+    pub marks: &'a [GridMark],
+
+    /// The transform between plot and screen coordinates.
+    pub transform: &'a ScreenTransform,
+
+    /// The screen rectangle of the plot area.
+    pub frame: Rect,
+}
+
+// ----------------------------------------------------------------------------
+
+/// The shapes of the plot as drawn in one frame, see [`PlotUi::export_shapes`].
+///
+/// egui does not have access to the pixels on screen, so rasterizing the plot, e.g. to save it as
+/// an image, is up to the backend: it can crop [`Self::rect`] out of its captured frame, or render
+/// [`Self::shapes`] offscreen.
+#[derive(Clone, Debug)]
+pub struct PlotShapes {
+    /// The region of the whole plot widget on the screen, in points.
+    pub rect: Rect,
+    /// The number of physical pixels per point, to find [`Self::rect`] in the captured frame.
+    pub pixels_per_point: f32,
+    /// The shapes of the plot with their clip rects, like the output of
+    /// [`crate::Context::end_frame`]. The hover rulers are not included.
+    pub shapes: Vec<ClippedShape>,
+}
+
+/// What [`Plot::show`] returns.
+pub struct PlotResponse<R> {
+    /// What the user closure returned.
+    pub inner: R,
+
+    /// The response of the plot.
+    pub response: Response,
+
+    /// The names of the items that are currently hidden via the legend, sorted by name.
+    pub hidden_items: Vec<String>,
+
+    /// The region and shapes of the plot, if [`PlotUi::export_shapes`] was called.
+    pub shapes: Option<PlotShapes>,
+
+    /// Where the bounds of the plot in this frame came from. Useful to debug unexpected jumps of
+    /// the bounds.
+    pub bounds_source: BoundsSource,
+
+    /// The movement of the pointer in this frame, in points, if a drag claimed by
+    /// [`Plot::capture_drag`] is in progress. Use [`Self::inverse`] to convert positions to plot
+    /// coordinates.
+    pub captured_drag_delta: Option<Vec2>,
+
+    /// Where the tick labels of the x and y axis were drawn, relative to the top left corner of
+    /// [`Self::response`], e.g. to align a color bar or other labels next to the plot.
+    pub axis_label_rects: [Vec<Rect>; 2],
+
+    /// The id of the element under the pointer, see [`ClosestElem::user_id`].
+    pub hovered_id: Option<u64>,
+
+    /// The id of the element that was clicked in this frame, see [`ClosestElem::user_id`].
+    pub clicked_id: Option<u64>,
+
+    /// The value of the element under the pointer in plot coordinates, e.g. the hovered point of
+    /// a line. Items without values per element, e.g. spans, report `None`.
+    pub hovered_value: Option<Value>,
+
+    /// The value of the element that was clicked in this frame, see [`Self::hovered_value`].
+    pub clicked_value: Option<Value>,
+
+    /// The name of the item with the element under the pointer, which is empty for items without
+    /// a name.
+    pub nearest_item: Option<String>,
+
+    /// The region in plot coordinates that was selected in this frame, see
+    /// [`Plot::allow_selection`].
+    pub selection: Option<PlotBounds>,
+
+    plot_id: Id,
+    transform: ScreenTransform,
+}
+
+impl<R> PlotResponse<R> {
+    /// The screen position of the given plot coordinate, using the transform the plot was drawn
+    /// with. Useful to align widgets and annotations outside of the plot with its content.
+    pub fn transform_pos(&self, value: Value) -> Pos2 {
+        self.transform.position_from_value(&value)
+    }
+
+    /// The screen rectangle of the plot area in which the items are drawn. It excludes the
+    /// [`Plot::left_margin`].
+    pub fn frame_rect(&self) -> Rect {
+        *self.transform.frame()
+    }
+
+    /// The plot coordinate at the given screen position, using the transform the plot was drawn
+    /// with. The inverse of [`Self::transform_pos`].
+    pub fn inverse(&self, pos: Pos2) -> Value {
+        self.transform.value_from_position(pos)
+    }
+
+    /// Reset the plot to automatic bounds, the same way double-clicking it does.
+    ///
+    /// Since the plot has already been shown, this takes effect on the following frame.
+    pub fn reset_bounds(&self) {
+        self.request_view_action(ViewAction::ResetBounds);
+    }
+
+    /// Fit the plot bounds to its content once. Unlike [`Self::reset_bounds`], the bounds will
+    /// not follow the content afterwards.
+    ///
+    /// Since the plot has already been shown, this takes effect on the following frame.
+    pub fn auto_fit(&self) {
+        self.request_view_action(ViewAction::AutoFit);
+    }
+
+    fn request_view_action(&self, action: ViewAction) {
+        let ctx = &self.response.ctx;
+        if let Some(mut memory) = PlotMemory::load(ctx, self.plot_id) {
+            memory.view_action = Some(action);
+            memory.store(ctx, self.plot_id);
+            ctx.request_repaint();
+        }
+    }
+}
+
+impl<R> std::ops::Deref for PlotResponse<R> {
+    type Target = Response;
+
+    fn deref(&self) -> &Response {
+        &self.response
+    }
+}
+
+impl<R> From<PlotResponse<R>> for InnerResponse<R> {
+    /// Drops everything but [`PlotResponse::inner`] and [`PlotResponse::response`], for code
+    /// written against the [`InnerResponse`] that [`Plot::show`] used to return.
+    fn from(plot_response: PlotResponse<R>) -> Self {
+        InnerResponse::new(plot_response.inner, plot_response.response)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// A 2D plot, e.g. a graph of a function.
 ///
 /// `Plot` supports multiple lines and points.
@@ -162,26 +542,74 @@ pub struct Plot {
     center_x_axis: bool,
     center_y_axis: bool,
     allow_zoom: bool,
+    scroll_requires_frame_hover: bool,
+    zoom_step: Option<f32>,
+    zoom_bounds: Option<(Vec2, Vec2)>,
     allow_drag: bool,
+    capture_drag: Option<Box<dyn Fn(Value) -> bool>>,
+    sense: Sense,
+    static_mode: bool,
+    locked: bool,
+    allow_auto_bounds: bool,
+    allow_double_click_reset: bool,
     min_auto_bounds: PlotBounds,
     margin_fraction: Vec2,
+    degenerate_bounds_margin: f64,
+    bounds_range: Option<PlotBounds>,
+    scroll_beyond_data: [bool; 2],
+    auto_bounds_update: UpdateMode,
     allow_boxed_zoom: bool,
+    on_box_select: Option<Box<dyn FnOnce(PlotBounds)>>,
+    item_double_click: Option<Box<ItemDoubleClickFn>>,
     boxed_zoom_pointer_button: PointerButton,
+    allow_selection: bool,
+    selection_pointer_button: PointerButton,
+    boxed_zoom_axes: [bool; 2],
     linked_axes: Option<LinkedAxisGroup>,
+    hover_sync: Option<HoverSyncGroup>,
+    anchor_value: Option<Value>,
 
     min_size: Vec2,
     width: Option<f32>,
     height: Option<f32>,
     data_aspect: Option<f32>,
+    aspect_anchor: Option<Axis>,
     view_aspect: Option<f32>,
+    left_margin: f32,
+    title: Option<String>,
 
     hover_line: HoverLine,
     show_hover_label: bool,
+    stacked_hover: bool,
+    info_box: Option<Corner>,
+    hover_cursor: Option<CursorIcon>,
     hover_formatter: HoverFormatter,
+    highlight_style: HighlightStyle,
     axis_formatters: [AxisFormatter; 2],
+    secondary_axis_formatter: AxisFormatter,
+    axis_units: [String; 2],
+    avoid_label_overlap: bool,
+    declutter_labels: bool,
+    grid_renderer: Option<Box<GridRendererFn>>,
+    grid_color: Option<Color32>,
+    grid_opacity: f32,
+    gridlines_at_labels_only: bool,
+    grid_base: [i64; 2],
+    grid_spacers: [Option<Box<GridSpacerFn>>; 2],
+    min_tick_spacing: [f32; 2],
     legend_config: Option<Legend>,
     show_background: bool,
     show_axes: [bool; 2],
+    axis_scales: [AxisScale; 2],
+    magnifier: Option<(Key, f32)>,
+    overlay: Option<Box<OverlayFn>>,
+    overlay_above_hover: bool,
+    now_line: Option<(f64, String, Stroke)>,
+    stable_colors: bool,
+    warn_on_empty_items: bool,
+    auto_marker_cycle: Vec<MarkerShape>,
+    max_shapes: Option<usize>,
+    cache_shapes: bool,
 }
 
 impl Plot {
@@ -193,27 +621,75 @@ impl Plot {
             center_x_axis: false,
             center_y_axis: false,
             allow_zoom: true,
+            scroll_requires_frame_hover: false,
+            zoom_step: None,
+            zoom_bounds: None,
             allow_drag: true,
+            capture_drag: None,
+            sense: Sense::drag(),
+            static_mode: false,
+            locked: false,
+            allow_auto_bounds: true,
+            allow_double_click_reset: true,
             min_auto_bounds: PlotBounds::NOTHING,
             margin_fraction: Vec2::splat(0.05),
+            degenerate_bounds_margin: 0.1,
+            bounds_range: None,
+            scroll_beyond_data: [true; 2],
+            auto_bounds_update: UpdateMode::EveryFrame,
             allow_boxed_zoom: true,
+            on_box_select: None,
+            item_double_click: None,
             boxed_zoom_pointer_button: PointerButton::Secondary,
+            allow_selection: false,
+            selection_pointer_button: PointerButton::Middle,
+            boxed_zoom_axes: [true; 2],
             linked_axes: None,
+            hover_sync: None,
+            anchor_value: None,
 
             min_size: Vec2::splat(64.0),
             width: None,
             height: None,
             data_aspect: None,
+            aspect_anchor: None,
             view_aspect: None,
+            left_margin: 0.0,
+            title: None,
 
             hover_line: HoverLine::XY,
             show_hover_label: true,
+            stacked_hover: false,
+            info_box: None,
+            hover_cursor: Some(CursorIcon::Crosshair),
             hover_formatter: Plot::default_hover_formatter(),
+            highlight_style: HighlightStyle::default(),
 
             axis_formatters: [None, None], // [None; 2] requires Copy
+            secondary_axis_formatter: None,
+            axis_units: Default::default(),
+            avoid_label_overlap: false,
+            declutter_labels: false,
+            grid_renderer: None,
+            grid_color: None,
+            grid_opacity: 0.15,
+            gridlines_at_labels_only: false,
+            grid_base: [10; 2],
+            grid_spacers: [None, None],
+            min_tick_spacing: [6.0; 2],
             legend_config: None,
             show_background: true,
             show_axes: [true; 2],
+            axis_scales: [AxisScale::Linear; 2],
+            magnifier: None,
+            overlay: None,
+            overlay_above_hover: false,
+            now_line: None,
+            stable_colors: false,
+            warn_on_empty_items: false,
+            auto_marker_cycle: Vec::new(),
+            max_shapes: None,
+            cache_shapes: false,
         }
     }
 
@@ -226,6 +702,14 @@ impl Plot {
         self
     }
 
+    /// Which axis keeps its range when enforcing [`Self::data_aspect`]; the other axis is adjusted.
+    /// By default the y-axis is kept only if this plot is part of a [`LinkedAxisGroup`] that links
+    /// only the y-axis, otherwise the x-axis is kept.
+    pub fn aspect_anchor(mut self, axis: Axis) -> Self {
+        self.aspect_anchor = Some(axis);
+        self
+    }
+
     /// width / height ratio of the plot region.
     /// By default no fixed aspect ratio is set (and width/height will fill the ui it is in).
     pub fn view_aspect(mut self, view_aspect: f32) -> Self {
@@ -233,6 +717,24 @@ impl Plot {
         self
     }
 
+    /// Reserve space of the given width (in points) to the left of the plot area, in which the
+    /// tick labels of the y-axis are drawn. Give stacked plots the same margin to line up their
+    /// plot areas and y-axes, regardless of the widths of their labels. Default: `0.0`, which
+    /// draws the labels inside the plot area.
+    pub fn left_margin(mut self, left_margin: f32) -> Self {
+        self.left_margin = left_margin;
+        self
+    }
+
+    /// A title, drawn centered above the plot area in the [`TextStyle::Heading`] of the style.
+    /// The space for it is taken from the top of the plot, which keeps at least its
+    /// [`Self::min_size`]. The title is part of [`PlotResponse::shapes`] and of
+    /// [`Self::to_svg`]. Default: no title.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
     /// Width of plot. By default a plot will fill the ui it is in.
     /// If you set [`Self::view_aspect`], the width can be calculated from the height.
     pub fn width(mut self, width: f32) -> Self {
@@ -279,6 +781,32 @@ impl Plot {
         self
     }
 
+    /// Only zoom and scroll when the pointer is over the plot area, not anywhere over the widget,
+    /// e.g. not over the [`Self::left_margin`]. Default: `false`.
+    pub fn scroll_requires_frame_hover(mut self, on: bool) -> Self {
+        self.scroll_requires_frame_hover = on;
+        self
+    }
+
+    /// The zoom factor applied by a single discrete zoom action, such as [`PlotUi::zoom_in`].
+    ///
+    /// When set, zooming with the scroll wheel or trackpad is also quantized to this step, which
+    /// can help to tame sensitive trackpads. Pinch gestures are not quantized. Steps that are not
+    /// greater than `1.0` are ignored. Default: no quantization and a step of `1.25`.
+    pub fn zoom_step(mut self, zoom_step: f32) -> Self {
+        self.zoom_step = Some(zoom_step).filter(|zoom_step| *zoom_step > 1.0);
+        self
+    }
+
+    /// Limit zooming so that the width and height of the visible bounds, in data units, stay
+    /// within `min_span` and `max_span`. A zoom that would cross a limit stops at it, while a boxed
+    /// zoom and the target of [`Self::on_item_double_click`] are widened or narrowed around their
+    /// center. Panning and explicitly set bounds are not limited. Default: no limits.
+    pub fn zoom_bounds(mut self, min_span: Vec2, max_span: Vec2) -> Self {
+        self.zoom_bounds = Some((min_span, max_span));
+        self
+    }
+
     /// Whether to allow zooming in the plot by dragging out a box with the secondary mouse button.
     ///
     /// Default: `true`.
@@ -287,18 +815,115 @@ impl Plot {
         self
     }
 
+    /// Use the box dragged out for a boxed zoom as a selection instead: when the box is released,
+    /// `select` is called with its bounds in plot coordinates and the plot doesn't zoom.
+    ///
+    /// This uses the same gesture as the boxed zoom, including [`Self::boxed_zoom_axes`], so it
+    /// does nothing if [`Self::allow_boxed_zoom`] is turned off.
+    pub fn on_box_select(mut self, select: impl FnOnce(PlotBounds) + 'static) -> Self {
+        self.on_box_select = Some(Box::new(select));
+        self
+    }
+
+    /// Zoom to an element of an item when it is double clicked, e.g. to drill down into a bar.
+    ///
+    /// `target` is called with the name of the item under the pointer and the element within it,
+    /// and returns the bounds to move to, e.g. a neighborhood of the element. The plot moves there
+    /// in a short transition. If no element is under the pointer, or `target` returns `None`, a
+    /// double click resets the bounds as usual.
+    pub fn on_item_double_click(
+        mut self,
+        target: impl Fn(&str, &ClosestElem) -> Option<PlotBounds> + 'static,
+    ) -> Self {
+        self.item_double_click = Some(Box::new(target));
+        self
+    }
+
     /// Config the button pointer to use for boxed zooming. Default: `Secondary`
     pub fn boxed_zoom_pointer_button(mut self, boxed_zoom_pointer_button: PointerButton) -> Self {
         self.boxed_zoom_pointer_button = boxed_zoom_pointer_button;
         self
     }
 
+    /// Whether a rectangle can be dragged out to select a region of the plot, e.g. for brushing
+    /// and linking between plots. Unlike [`Self::on_box_select`], this is independent of the
+    /// boxed zoom. When the rectangle is released, its bounds are returned in
+    /// [`PlotResponse::selection`], and the view doesn't change. Default: `false`.
+    ///
+    /// See [`Self::selection_pointer_button`] for the button that drags out the rectangle.
+    pub fn allow_selection(mut self, on: bool) -> Self {
+        self.allow_selection = on;
+        self
+    }
+
+    /// The button to drag out a selection with, see [`Self::allow_selection`]. Use a different
+    /// button than for dragging the plot and the boxed zoom. Default: `Middle`.
+    pub fn selection_pointer_button(mut self, button: PointerButton) -> Self {
+        self.selection_pointer_button = button;
+        self
+    }
+
+    /// Which axes a zoom box constrains, e.g. `[true, false]` to zoom into a range of x while
+    /// keeping the y range. The box spans the whole plot along the other axes.
+    /// Default: `[true; 2]`.
+    pub fn boxed_zoom_axes(mut self, axes: [bool; 2]) -> Self {
+        self.boxed_zoom_axes = axes;
+        self
+    }
+
     /// Whether to allow dragging in the plot to move the bounds. Default: `true`.
     pub fn allow_drag(mut self, on: bool) -> Self {
         self.allow_drag = on;
         self
     }
 
+    /// Let the app claim drags, e.g. to move an annotation. The function is called with the plot
+    /// coordinate where a drag with the primary button starts. If it returns `true`, the plot
+    /// doesn't pan for that drag and reports its movement in
+    /// [`PlotResponse::captured_drag_delta`] instead.
+    pub fn capture_drag(mut self, capture: impl Fn(Value) -> bool + 'static) -> Self {
+        self.capture_drag = Some(Box::new(capture));
+        self
+    }
+
+    /// How the plot area responds to the pointer. Default: `Sense::drag()`.
+    ///
+    /// With the default, `response.clicked()` and `response.double_clicked()` are only reported
+    /// if the pointer did not move much while pressed. Use `Sense::click_and_drag()` to sense
+    /// clicks reliably (e.g. for click-to-select tools) while keeping dragging for panning.
+    /// Without drag sensing, panning and boxed zooming are not available.
+    pub fn sense(mut self, sense: Sense) -> Self {
+        self.sense = sense;
+        self
+    }
+
+    /// Show a fully static plot, e.g. for thumbnails and print previews. It only senses hovering,
+    /// ignores dragging, zooming and double clicks, and neither loads nor stores its memory, so
+    /// the bounds always come from the content (or [`Self::include_x`] and friends).
+    /// This overrides [`Self::allow_drag`], [`Self::allow_zoom`], [`Self::allow_boxed_zoom`] and
+    /// [`Self::sense`]. Default: `false`.
+    pub fn static_mode(mut self, on: bool) -> Self {
+        self.static_mode = on;
+        self
+    }
+
+    /// Lock the view, e.g. for presentations: while locked, the plot ignores dragging, zooming
+    /// and double clicks, but still shows hover readouts. Unlike [`Self::static_mode`], the plot
+    /// keeps its memory, so the view stays as it was and the lock can be toggled at any time.
+    /// Default: `false`.
+    pub fn locked(mut self, locked: bool) -> Self {
+        self.locked = locked;
+        self
+    }
+
+    fn effective_sense(&self) -> Sense {
+        if self.static_mode {
+            Sense::hover()
+        } else {
+            self.sense
+        }
+    }
+
     /// Provide a function to customize the on-hovel label for the x and y axis
     ///
     /// ```
@@ -328,27 +953,55 @@ impl Plot {
         self
     }
 
+    /// Use a hover formatter like the default one, but which switches to scientific notation for
+    /// very large and very small values, see [`format_magnitude_aware`]. This suits data spanning
+    /// many orders of magnitude.
+    pub fn magnitude_aware_hover_formatter(mut self) -> Self {
+        self.hover_formatter = Box::new(|config, name, value| {
+            Self::hover_text(config, name, value, format_magnitude_aware)
+        });
+        self
+    }
+
+    /// How highlighted items are emphasized, e.g. when hovering their entry in the legend.
+    /// Default: [`HighlightStyle::default`], which doubles line widths and fill opacity.
+    ///
+    /// Texts, images and heat maps have no lines or fills of their own to emphasize. They get an
+    /// outline instead, whose width grows with [`HighlightStyle::width_factor`] beyond `1.0`.
+    pub fn highlight_style(mut self, highlight_style: HighlightStyle) -> Self {
+        self.highlight_style = highlight_style;
+        self
+    }
+
     fn default_hover_formatter() -> Box<dyn Fn(&HoverConfig, &str, &Value) -> String> {
         Box::new(|config, name, value| {
-            let mut prefix = String::new();
+            Self::hover_text(config, name, value, |coordinate| {
+                let decimals = num_decimals_with_max_digits(coordinate, 6);
+                format!("{:.*}", decimals, coordinate)
+            })
+        })
+    }
 
-            if !name.is_empty() {
-                prefix = format!("{}\n", name);
-            }
+    /// The label of the default hover formatters: the name, if any, and the coordinates shown by
+    /// the hover line, each formatted by `format`.
+    fn hover_text(
+        config: &HoverConfig,
+        name: &str,
+        value: &Value,
+        format: impl Fn(f64) -> String,
+    ) -> String {
+        let mut prefix = String::new();
+
+        if !name.is_empty() {
+            prefix = format!("{}\n", name);
+        }
 
-            let x_decimals = num_decimals_with_max_digits(value.x, 6);
-            let y_decimals = num_decimals_with_max_digits(value.y, 6);
-
-            match config.hover_line {
-                HoverLine::None => format!(""),
-                HoverLine::X => format!("{}x = {:.*}", prefix, x_decimals, value.x),
-                HoverLine::Y => format!("{}y = {:.*}", prefix, y_decimals, value.y),
-                HoverLine::XY => format!(
-                    "{}x = {:.*}\ny = {:.*}",
-                    prefix, x_decimals, value.x, y_decimals, value.y
-                ),
-            }
-        })
+        match config.hover_line {
+            HoverLine::None => String::new(),
+            HoverLine::X => format!("{}x = {}", prefix, format(value.x)),
+            HoverLine::Y => format!("{}y = {}", prefix, format(value.y)),
+            HoverLine::XY => format!("{}x = {}\ny = {}", prefix, format(value.x), format(value.y)),
+        }
     }
 
     /// Whether to show a label when hovering on axis
@@ -357,6 +1010,31 @@ impl Plot {
         self
     }
 
+    /// When hovering, list the values of all items at the pointer's x-coordinate in a single
+    /// label instead of only showing the closest item. This is useful for stacked charts, where
+    /// each layer contributes to the total. For bar charts the value of each bar (rather than
+    /// its stacked top) is listed. Default: `false`.
+    pub fn stacked_hover(mut self, stacked_hover: bool) -> Self {
+        self.stacked_hover = stacked_hover;
+        self
+    }
+
+    /// Show a box in the given corner of the plot with the name and value of the hovered item,
+    /// or the coordinate of the pointer if no item is hovered. Unlike the hover label, it doesn't
+    /// follow the pointer, which makes the values easier to read. The values are formatted with
+    /// [`Self::hover_formatter`]. Default: `None`.
+    pub fn info_box(mut self, corner: Corner) -> Self {
+        self.info_box = Some(corner);
+        self
+    }
+
+    /// The cursor icon to show while hovering the plot, if any of the hover lines are shown.
+    /// Use `None` to leave the cursor to the rest of the app. Default: `Some(CursorIcon::Crosshair)`.
+    pub fn hover_cursor(mut self, hover_cursor: Option<CursorIcon>) -> Self {
+        self.hover_cursor = hover_cursor;
+        self
+    }
+
     /// Provide a function to customize the labels for the X axis.
     ///
     /// This is useful for custom input domains, e.g. date/time.
@@ -381,81 +1059,384 @@ impl Plot {
         self
     }
 
-    /// Expand bounds to include the given x value.
-    /// For instance, to always show the y axis, call `plot.include_x(0.0)`.
-    pub fn include_x(mut self, x: impl Into<f64>) -> Self {
-        self.min_auto_bounds.extend_with_x(x.into());
+    /// Provide a function to customize the labels of the secondary y axis, see
+    /// [`Self::y_axis_formatter`] and [`YAxis::Secondary`].
+    pub fn secondary_y_axis_formatter(mut self, func: impl Fn(f64) -> String + 'static) -> Self {
+        self.secondary_axis_formatter = Some(Box::new(func));
         self
     }
 
-    /// Expand bounds to include the given y value.
-    /// For instance, to always show the x axis, call `plot.include_y(0.0)`.
-    pub fn include_y(mut self, y: impl Into<f64>) -> Self {
-        self.min_auto_bounds.extend_with_y(y.into());
+    /// A unit appended to each label of the x axis, e.g. `"s"`. It is also appended to the labels
+    /// of a custom [`Self::x_axis_formatter`], except for empty ones. Default: none.
+    pub fn x_axis_unit(mut self, unit: impl Into<String>) -> Self {
+        self.axis_units[0] = unit.into();
         self
     }
 
-    /// Show a legend including all named items.
-    pub fn legend(mut self, legend: Legend) -> Self {
-        self.legend_config = Some(legend);
+    /// A unit appended to each label of the y axis, e.g. `"V"`. It is also appended to the labels
+    /// of a custom [`Self::y_axis_formatter`], except for empty ones. Default: none.
+    pub fn y_axis_unit(mut self, unit: impl Into<String>) -> Self {
+        self.axis_units[1] = unit.into();
         self
     }
 
-    /// Whether or not to show the background `Rect`.
-    /// Can be useful to disable if the plot is overlaid over existing content.
-    /// Default: `true`.
-    pub fn show_background(mut self, show: bool) -> Self {
-        self.show_background = show;
+    /// Drop every other axis label until the labels of each axis don't overlap anymore, e.g. for
+    /// long labels of a custom formatter. Default: `false`.
+    pub fn avoid_label_overlap(mut self, on: bool) -> Self {
+        self.avoid_label_overlap = on;
         self
     }
 
-    /// Show the axes.
-    /// Can be useful to disable if the plot is overlaid over an existing grid or content.
-    /// Default: `[true; 2]`.
-    pub fn show_axes(mut self, show: [bool; 2]) -> Self {
-        self.show_axes = show;
+    /// Hide [`Text`] items that would overlap a previously added one, e.g. when labeling many
+    /// points. Earlier texts take priority. A hidden text is still shown while the pointer is
+    /// over it. Default: `false`.
+    pub fn declutter_labels(mut self, on: bool) -> Self {
+        self.declutter_labels = on;
         self
     }
 
-    /// Add a [`LinkedAxisGroup`] so that this plot will share the bounds with other plots that have this
-    /// group assigned. A plot cannot belong to more than one group.
-    pub fn link_axis(mut self, group: LinkedAxisGroup) -> Self {
-        self.linked_axes = Some(group);
+    /// Provide a function to draw the gridlines yourself, instead of the default gridlines.
+    ///
+    /// The function is called once per shown axis with the computed [`GridMark`]s and should add
+    /// its shapes to the given `Vec`. The axis labels are still drawn by the plot.
+    pub fn grid_renderer(
+        mut self,
+        renderer: impl Fn(&GridContext<'_>, &mut Vec<Shape>) + 'static,
+    ) -> Self {
+        self.grid_renderer = Some(Box::new(renderer));
         self
     }
 
-    /// Interact with and add items to the plot and finally draw it.
-    pub fn show<R>(self, ui: &mut Ui, build_fn: impl FnOnce(&mut PlotUi) -> R) -> InnerResponse<R> {
-        let Self {
-            id_source,
-            center_x_axis,
-            center_y_axis,
-            allow_zoom,
-            allow_drag,
-            allow_boxed_zoom,
-            boxed_zoom_pointer_button: boxed_zoom_pointer,
-            min_auto_bounds,
-            margin_fraction,
-            width,
-            height,
-            min_size,
-            data_aspect,
-            view_aspect,
-            mut hover_line,
-            show_hover_label,
-            hover_formatter,
-            axis_formatters,
-            legend_config,
-            show_background,
-            show_axes,
-            linked_axes,
-        } = self;
+    /// Color of the gridlines. By default a gray that matches the current theme is used.
+    pub fn grid_color(mut self, color: impl Into<Color32>) -> Self {
+        self.grid_color = Some(color.into());
+        self
+    }
 
-        // Determine the size of the plot in the UI
-        let size = {
-            let width = width
-                .unwrap_or_else(|| {
-                    if let (Some(height), Some(aspect)) = (height, view_aspect) {
+    /// Maximum opacity of the gridlines, reached by the most widely spaced lines. Denser lines
+    /// fade out towards zero opacity. Default: `0.15`.
+    pub fn grid_opacity(mut self, opacity: f32) -> Self {
+        self.grid_opacity = opacity;
+        self
+    }
+
+    /// Only draw gridlines where there is also an axis label, for a cleaner look without the
+    /// denser, fainter gridlines in between. Default: `false`.
+    pub fn gridlines_at_labels_only(mut self, on: bool) -> Self {
+        self.gridlines_at_labels_only = on;
+        self
+    }
+
+    /// The base of the grid on each axis: the spacing of the gridlines is a power of the base,
+    /// and every `base`-th and `base²`-th line is more prominent. E.g. use `60` for an axis of
+    /// seconds or `2` for binary data. The base is clamped to `2..=1_000_000`.
+    /// Default: `[10; 2]`.
+    ///
+    /// Axes with [`AxisScale::SymLog`] always use base 10.
+    pub fn grid_base(mut self, base: [i64; 2]) -> Self {
+        // Keeps `base²` from overflowing when picking the prominent gridlines.
+        self.grid_base = base.map(|base| base.clamp(2, 1_000_000));
+        self
+    }
+
+    /// Place the gridlines and labels of the x axis at the marks returned by the given function
+    /// instead of on the automatic grid. The opacity of each gridline and label is derived from
+    /// the [`GridMark::step_size`] of the mark, as for the automatic grid.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::plot::{GridMark, Plot};
+    /// // A prominent line at every A and a fainter one at every semitone in between.
+    /// Plot::new("notes")
+    ///     .x_grid_spacer(|input| {
+    ///         (-48..=48)
+    ///             .map(|n: i32| GridMark {
+    ///                 value: 440.0 * 2.0_f64.powf(n as f64 / 12.0),
+    ///                 step_size: input.base_step_size * if n % 12 == 0 { 100.0 } else { 1.0 },
+    ///             })
+    ///             .collect()
+    ///     })
+    ///     .show(ui, |_plot_ui| ());
+    /// # });
+    /// ```
+    pub fn x_grid_spacer(mut self, spacer: impl Fn(GridInput) -> Vec<GridMark> + 'static) -> Self {
+        self.grid_spacers[0] = Some(Box::new(spacer));
+        self
+    }
+
+    /// Place the gridlines and labels of the y axis at the marks returned by the given function,
+    /// see [`Self::x_grid_spacer`]. The secondary y axis always uses the automatic grid.
+    pub fn y_grid_spacer(mut self, spacer: impl Fn(GridInput) -> Vec<GridMark> + 'static) -> Self {
+        self.grid_spacers[1] = Some(Box::new(spacer));
+        self
+    }
+
+    /// The minimum distance between neighboring gridlines on each axis, in points, which decides
+    /// the finest step size of the grid. Increase it for an axis with wide labels to get fewer,
+    /// wider spaced ticks. Values below `1.0` are clamped to `1.0`. Default: `[6.0; 2]`.
+    pub fn min_tick_spacing(mut self, spacing: [f32; 2]) -> Self {
+        // A zero spacing would make the step size zero and produce endless gridlines.
+        self.min_tick_spacing = spacing.map(|spacing| spacing.max(1.0));
+        self
+    }
+
+    /// Whether to fit the bounds to the content automatically. With `false`, the content is only
+    /// fitted once when the plot has no valid bounds yet, e.g. on its first frame, or when it is
+    /// reset, so that the bounds can be controlled with [`PlotUi::set_plot_bounds`].
+    /// Default: `true`.
+    pub fn auto_bounds(mut self, auto_bounds: bool) -> Self {
+        self.allow_auto_bounds = auto_bounds;
+        self
+    }
+
+    /// Whether double clicking the plot resets it to automatic bounds. Disable it if the bounds
+    /// are controlled by the application, see [`PlotUi::set_plot_bounds`]. Default: `true`.
+    pub fn allow_double_click_reset(mut self, on: bool) -> Self {
+        self.allow_double_click_reset = on;
+        self
+    }
+
+    /// Expand bounds to include the given x value.
+    /// For instance, to always show the y axis, call `plot.include_x(0.0)`.
+    pub fn include_x(mut self, x: impl Into<f64>) -> Self {
+        self.min_auto_bounds.extend_with_x(x.into());
+        self
+    }
+
+    /// Expand bounds to include the given y value.
+    /// For instance, to always show the x axis, call `plot.include_y(0.0)`.
+    pub fn include_y(mut self, y: impl Into<f64>) -> Self {
+        self.min_auto_bounds.extend_with_y(y.into());
+        self
+    }
+
+    /// The margin added around the content when the bounds are set automatically, as a fraction
+    /// of the size of the content. Set it to zero to fit the content exactly. Default: `0.05`.
+    ///
+    /// Bounds that are not set automatically, e.g. after dragging or zooming, are not affected.
+    pub fn margin_fraction(mut self, margin_fraction: Vec2) -> Self {
+        self.margin_fraction = margin_fraction;
+        self
+    }
+
+    /// The margin shown on each side of the content along an axis on which all of its values are
+    /// equal, e.g. for a flat line, when the bounds are set automatically. It is relative to the
+    /// magnitude of the value, but at least the given margin itself. Default: `0.1`.
+    ///
+    /// Without it, the auto-bounds of such content would have no extent along that axis.
+    pub fn degenerate_bounds_margin(mut self, margin: f64) -> Self {
+        self.degenerate_bounds_margin = margin;
+        self
+    }
+
+    /// The range of values that may ever be shown, e.g. `0.0..=1.0` for probabilities. Each edge
+    /// of the bounds is clamped to this range after panning, zooming and fitting to the content,
+    /// so the plot never shows anything outside of it.
+    pub fn bounds_range(
+        mut self,
+        x: std::ops::RangeInclusive<f64>,
+        y: std::ops::RangeInclusive<f64>,
+    ) -> Self {
+        self.bounds_range = Some(PlotBounds::from_min_max(
+            [*x.start(), *y.start()],
+            [*x.end(), *y.end()],
+        ));
+        self
+    }
+
+    /// Whether panning and zooming out may move the view beyond the content along the x and y
+    /// axis, respectively. Where it is not allowed, the view always stays within the automatic
+    /// bounds of the content, i.e. the content including its margins, e.g. `[false, true]` for
+    /// a time series with a limited x range and a free y axis. Zooming in is not limited.
+    /// Default: `[true; 2]`.
+    ///
+    /// The view may have a different aspect ratio than [`Self::data_aspect`] when it is
+    /// clamped.
+    pub fn allow_scroll_beyond_data(mut self, allow: [bool; 2]) -> Self {
+        self.scroll_beyond_data = allow;
+        self
+    }
+
+    /// How often the automatic bounds follow the content, which can reduce jitter in plots of
+    /// streaming data. Default: [`UpdateMode::EveryFrame`].
+    ///
+    /// Note that with [`UpdateMode::Debounced`], the bounds are only updated when the plot is
+    /// repainted.
+    pub fn auto_bounds_update(mut self, mode: UpdateMode) -> Self {
+        self.auto_bounds_update = mode;
+        self
+    }
+
+    /// Show a legend including all named items.
+    pub fn legend(mut self, legend: Legend) -> Self {
+        self.legend_config = Some(legend);
+        self
+    }
+
+    /// Whether or not to show the background `Rect`.
+    /// Can be useful to disable if the plot is overlaid over existing content.
+    /// Default: `true`.
+    pub fn show_background(mut self, show: bool) -> Self {
+        self.show_background = show;
+        self
+    }
+
+    /// Show the axes.
+    /// Can be useful to disable if the plot is overlaid over an existing grid or content.
+    /// Default: `[true; 2]`.
+    pub fn show_axes(mut self, show: [bool; 2]) -> Self {
+        self.show_axes = show;
+        self
+    }
+
+    /// How the values along the x and y axes are mapped to screen positions, e.g.
+    /// `[AxisScale::Linear, AxisScale::SymLog { linthresh: 1.0 }]`. Dragging and zooming work in
+    /// the mapped space, so a decade of a logarithmic scale always keeps its size on screen.
+    /// Default: `[AxisScale::Linear; 2]`.
+    pub fn axis_scales(mut self, scales: [AxisScale; 2]) -> Self {
+        self.axis_scales = scales;
+        self
+    }
+
+    /// Use an [`AxisScale::Log`] for the x and/or y axis, e.g. for data spanning many orders of
+    /// magnitude. Unless a formatter is set, the labels of the logarithmic axes are formatted with
+    /// [`format_power_of_ten`]. Shorthand for [`Self::axis_scales`]; axes set to `false` keep
+    /// their scale.
+    pub fn log_axis(mut self, log: [bool; 2]) -> Self {
+        for (scale, log) in self.axis_scales.iter_mut().zip(log) {
+            if log {
+                *scale = AxisScale::Log;
+            }
+        }
+        self
+    }
+
+    /// Show a magnified inset around the pointer while `key` is held down, e.g. to inspect dense
+    /// regions of the plot. `zoom` is the magnification factor of the inset. Default: off.
+    pub fn magnifier(mut self, key: Key, zoom: f32) -> Self {
+        self.magnifier = Some((key, zoom));
+        self
+    }
+
+    /// Draw on top of the items with the given function, e.g. to show that the data is loading
+    /// or stale. It is called with a `Ui` covering the plot frame and the rect of the frame.
+    /// Default: no overlay.
+    pub fn overlay(mut self, overlay: impl Fn(&mut Ui, Rect) + 'static) -> Self {
+        self.overlay = Some(Box::new(overlay));
+        self
+    }
+
+    /// Whether the [`Self::overlay`] is drawn on top of the hover rulers and label, instead of
+    /// below them. Default: `false`.
+    pub fn overlay_above_hover(mut self, above: bool) -> Self {
+        self.overlay_above_hover = above;
+        self
+    }
+
+    /// Derive the automatic color of each named item from its name, instead of from the order in
+    /// which the items are added. A series then keeps its color when other series appear or
+    /// disappear. Items without a name are still colored in order. Default: `false`.
+    ///
+    /// Different names may map to the same color.
+    pub fn stable_colors(mut self, stable_colors: bool) -> Self {
+        self.stable_colors = stable_colors;
+        self
+    }
+
+    /// Log a warning for each named item that is dropped because it has no values, e.g. when a
+    /// filter produced no data, and keep a legend entry for it that is marked as empty.
+    /// The warning is logged once per item until it has values again, and requires the `log`
+    /// feature. Default: `false`, which drops such items silently.
+    pub fn warn_on_empty_items(mut self, warn: bool) -> Self {
+        self.warn_on_empty_items = warn;
+        self
+    }
+
+    /// Give each [`Points`] series without an explicit [`Points::shape`] the next marker shape of
+    /// this list, in the order in which the series are added, e.g. to tell series apart in
+    /// monochrome prints. Default: empty, which means that all such series use circles.
+    ///
+    /// For example, `plot.auto_marker_cycle(MarkerShape::all().collect())`.
+    pub fn auto_marker_cycle(mut self, shapes: Vec<MarkerShape>) -> Self {
+        self.auto_marker_cycle = shapes;
+        self
+    }
+
+    /// Limit the number of shapes drawn for all items together in each frame, as a safety valve
+    /// against data that would otherwise freeze the app, e.g. millions of points. Items are drawn
+    /// in the order in which they were added, and no more shapes are generated once the limit is
+    /// reached. Default: `None`, i.e. no limit.
+    pub fn max_shapes(mut self, max_shapes: Option<usize>) -> Self {
+        self.max_shapes = max_shapes;
+        self
+    }
+
+    /// Reuse the shapes of items with a content hash, e.g. [`Line::content_hash`], from the last
+    /// frame, as long as their hash, highlighting and the bounds and size of the plot are
+    /// unchanged. Shapes are cached per item, in the order the items are added, so several
+    /// items may share a hash. This saves computing the shapes of large static items each frame.
+    /// Panning and zooming compute the shapes anew. Default: `false`.
+    pub fn cache_shapes(mut self, on: bool) -> Self {
+        self.cache_shapes = on;
+        self
+    }
+
+    /// Show a labeled vertical marker line at `x`, e.g. for the current time in a live plot.
+    ///
+    /// Unlike a [`VLine`] it is always drawn on top of all items, has its label at the top of the
+    /// plot area and is not part of the legend.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn now_line(
+        mut self,
+        x: impl Into<f64>,
+        label: impl ToString,
+        stroke: impl Into<Stroke>,
+    ) -> Self {
+        self.now_line = Some((x.into(), label.to_string(), stroke.into()));
+        self
+    }
+
+    /// Add a [`LinkedAxisGroup`] so that this plot will share the bounds with other plots that have this
+    /// group assigned. A plot cannot belong to more than one group.
+    pub fn link_axis(mut self, group: LinkedAxisGroup) -> Self {
+        self.linked_axes = Some(group);
+        self
+    }
+
+    /// Add a [`HoverSyncGroup`] so that hovering this plot shows a crosshair at the same x in the
+    /// other plots of the group, and vice versa. A plot cannot belong to more than one group.
+    pub fn link_hover(mut self, group: HoverSyncGroup) -> Self {
+        self.hover_sync = Some(group);
+        self
+    }
+
+    /// Keep the given plot coordinate at a fixed screen position. Whenever the value changes
+    /// (e.g. because it follows a feature in live data), the bounds are translated so that the new
+    /// value appears where the previous one was. Dragging and zooming still work, and move the
+    /// screen position at which the value is kept.
+    ///
+    /// Automatic bounds are disabled while anchored. Default: `None`.
+    pub fn anchor_value(mut self, value: Option<Value>) -> Self {
+        self.anchor_value = value;
+        self
+    }
+
+    /// Interact with and add items to the plot and finally draw it.
+    pub fn show<R>(self, ui: &mut Ui, build_fn: impl FnOnce(&mut PlotUi) -> R) -> PlotResponse<R> {
+        let Self {
+            width,
+            height,
+            min_size,
+            view_aspect,
+            ..
+        } = self;
+        let title_height = title_height(ui, &self.title);
+
+        // Determine the size of the plot in the UI
+        let size = {
+            let width = width
+                .unwrap_or_else(|| {
+                    if let (Some(height), Some(aspect)) = (height, view_aspect) {
                         height * aspect
                     } else {
                         ui.available_size_before_wrap().x
@@ -466,21 +1447,291 @@ impl Plot {
             let height = height
                 .unwrap_or_else(|| {
                     if let Some(aspect) = view_aspect {
-                        width / aspect
+                        width / aspect + title_height
                     } else {
                         ui.available_size_before_wrap().y
                     }
                 })
-                .at_least(min_size.y);
+                .at_least(min_size.y + title_height);
             vec2(width, height)
         };
 
         // Allocate the space.
-        let (rect, response) = ui.allocate_exact_size(size, Sense::drag());
+        let (rect, response) = ui.allocate_exact_size(size, self.effective_sense());
+
+        self.show_impl(ui, rect, response, build_fn)
+    }
+
+    /// Like [`Self::show`], but draws the plot into the given `rect` instead of allocating space
+    /// based on the size settings of the plot.
+    ///
+    /// This is useful for custom layouts, e.g. synchronized panels that need pixel-exact alignment.
+    /// [`Self::width`], [`Self::height`], [`Self::min_size`] and [`Self::view_aspect`] are ignored.
+    pub fn show_in_rect<R>(
+        self,
+        ui: &mut Ui,
+        rect: Rect,
+        build_fn: impl FnOnce(&mut PlotUi) -> R,
+    ) -> PlotResponse<R> {
+        let response = ui.allocate_rect(rect, self.effective_sense());
+        self.show_impl(ui, rect, response, build_fn)
+    }
+
+    /// Render the plot with the given `bounds` and `size` (in points) to an SVG document,
+    /// e.g. for vector graphics in reports. Nothing is drawn into the `ui`, which is only used
+    /// for its style and fonts.
+    ///
+    /// The background, axes and items are included, but not the legend or any hover
+    /// information. Bézier curves and images are not supported and are left out.
+    pub fn to_svg(
+        self,
+        ui: &mut Ui,
+        bounds: PlotBounds,
+        size: Vec2,
+        build_fn: impl FnOnce(&mut PlotUi),
+    ) -> String {
+        let title_height = title_height(ui, &self.title);
+        let Self {
+            id_source,
+            center_x_axis,
+            center_y_axis,
+            data_aspect,
+            aspect_anchor,
+            show_hover_label,
+            stacked_hover,
+            hover_formatter,
+            highlight_style,
+            axis_formatters,
+            secondary_axis_formatter,
+            axis_units,
+            avoid_label_overlap,
+            declutter_labels,
+            grid_renderer,
+            grid_color,
+            grid_opacity,
+            gridlines_at_labels_only,
+            grid_base,
+            grid_spacers,
+            min_tick_spacing,
+            show_background,
+            show_axes,
+            axis_scales,
+            now_line,
+            left_margin,
+            title,
+            stable_colors,
+            auto_marker_cycle,
+            max_shapes,
+            margin_fraction,
+            degenerate_bounds_margin,
+            ..
+        } = self;
+
+        let rect = Rect::from_min_max(pos2(left_margin, title_height), size.to_pos2());
+        // The rect is in SVG coordinates, not on screen, so don't interact with the `ui`.
+        let response = Response {
+            ctx: ui.ctx().clone(),
+            layer_id: ui.layer_id(),
+            // Not the id of the plot itself, which may be shown in the same frame.
+            id: ui.make_persistent_id(id_source).with("svg"),
+            rect,
+            sense: Sense::hover(),
+            enabled: false,
+            hovered: false,
+            clicked: Default::default(),
+            double_clicked: Default::default(),
+            dragged: false,
+            drag_released: false,
+            is_pointer_button_down_on: false,
+            interact_pointer_pos: None,
+            changed: false,
+        };
+        let mut transform = ScreenTransform::new(rect, bounds, center_x_axis, center_y_axis)
+            .with_scales(axis_scales);
+        if let Some(data_aspect) = data_aspect {
+            transform.set_aspect(data_aspect as f64, aspect_anchor == Some(Axis::Y));
+        }
+
+        let mut plot_ui = PlotUi {
+            items: Vec::new(),
+            next_auto_color_idx: 0,
+            auto_marker_cycle,
+            next_auto_marker_idx: 0,
+            last_screen_transform: transform.clone(),
+            response,
+            ctx: ui.ctx().clone(),
+            zoom_step: 1.25,
+            zoom_request: None,
+            shapes_requested: false,
+            stable_colors,
+            warn_on_empty_items: false,
+            empty_items: Vec::new(),
+            last_secondary_range: None,
+            bounds_request: None,
+        };
+        build_fn(&mut plot_ui);
+        let mut items = plot_ui.items;
+        let secondary_range = fit_secondary_axis(
+            &items,
+            axis_scales[1],
+            degenerate_bounds_margin,
+            margin_fraction.y,
+        )
+        .map(|secondary| SecondaryRange {
+            primary: [bounds.min[1], bounds.max[1]],
+            secondary,
+        });
+        for item in &mut items {
+            item.initialize(transform.bounds().range_x());
+        }
+
+        let mut shapes = Vec::new();
+        if show_background {
+            shapes.push(Shape::Rect(background(ui, rect)));
+        }
+        let prepared = PreparedPlot {
+            items,
+            hover_line: HoverLine::None,
+            show_hover_label,
+            stacked_hover,
+            info_box: None,
+            hover_formatter,
+            highlight_style,
+            axis_formatters,
+            secondary_axis_formatter,
+            secondary_range,
+            axis_units,
+            avoid_label_overlap,
+            declutter_labels,
+            grid_renderer,
+            grid_color,
+            grid_opacity,
+            gridlines_at_labels_only,
+            grid_base,
+            grid_spacers,
+            min_tick_spacing,
+            show_axes,
+            magnifier: None,
+            overlay: None,
+            overlay_above_hover: false,
+            now_line,
+            synced_hover_x: None,
+            left_margin,
+            title,
+            max_shapes,
+            shape_cache: None,
+            new_shape_cache: Default::default(),
+            transform,
+        };
+        let (frame_shapes, margin_shapes, _) = prepared.to_shapes(ui);
+        shapes.extend(frame_shapes);
+        shapes.extend(margin_shapes);
+
+        svg::shapes_to_svg(&shapes, size)
+    }
+
+    fn show_impl<R>(
+        self,
+        ui: &mut Ui,
+        rect: Rect,
+        response: Response,
+        build_fn: impl FnOnce(&mut PlotUi) -> R,
+    ) -> PlotResponse<R> {
+        let title_height = title_height(ui, &self.title);
+        let Self {
+            id_source,
+            center_x_axis,
+            center_y_axis,
+            allow_zoom,
+            scroll_requires_frame_hover,
+            zoom_step,
+            zoom_bounds,
+            allow_drag,
+            capture_drag,
+            sense: _,
+            static_mode,
+            locked,
+            allow_auto_bounds,
+            allow_double_click_reset,
+            allow_boxed_zoom,
+            mut on_box_select,
+            item_double_click,
+            boxed_zoom_pointer_button: boxed_zoom_pointer,
+            boxed_zoom_axes,
+            allow_selection,
+            selection_pointer_button,
+            min_auto_bounds,
+            margin_fraction,
+            degenerate_bounds_margin,
+            bounds_range,
+            scroll_beyond_data,
+            auto_bounds_update,
+            width: _,
+            height: _,
+            min_size: _,
+            data_aspect,
+            aspect_anchor,
+            view_aspect: _,
+            left_margin,
+            title,
+            mut hover_line,
+            show_hover_label,
+            stacked_hover,
+            info_box,
+            hover_cursor,
+            hover_formatter,
+            highlight_style,
+            axis_formatters,
+            secondary_axis_formatter,
+            axis_units,
+            avoid_label_overlap,
+            declutter_labels,
+            grid_renderer,
+            grid_color,
+            grid_opacity,
+            gridlines_at_labels_only,
+            grid_base,
+            grid_spacers,
+            min_tick_spacing,
+            legend_config,
+            show_background,
+            show_axes,
+            axis_scales,
+            magnifier,
+            overlay,
+            overlay_above_hover,
+            now_line,
+            linked_axes,
+            hover_sync,
+            anchor_value,
+            stable_colors,
+            warn_on_empty_items,
+            auto_marker_cycle,
+            max_shapes,
+            cache_shapes,
+        } = self;
+
+        let widget_rect = rect;
+        // The plot area, excluding the margin for the labels and the title.
+        let rect = Rect::from_min_max(
+            pos2(rect.left() + left_margin, rect.top() + title_height),
+            rect.max,
+        );
 
         // Load or initialize the memory.
         let plot_id = ui.make_persistent_id(id_source);
-        let mut memory = PlotMemory::load(ui.ctx(), plot_id).unwrap_or_else(|| PlotMemory {
+        // Plots with the same id would share their memory, which leads to confusing behavior.
+        ui.ctx().register_interaction_id(plot_id, widget_rect);
+        // A static or locked plot ignores all input, a static one also keeps no memory.
+        let allow_zoom = allow_zoom && !static_mode && !locked;
+        let allow_drag = allow_drag && !static_mode && !locked;
+        let allow_boxed_zoom = allow_boxed_zoom && !static_mode && !locked;
+        let stored_memory = if static_mode {
+            None
+        } else {
+            PlotMemory::load(ui.ctx(), plot_id)
+        };
+        let mut memory = stored_memory.unwrap_or_else(|| PlotMemory {
             auto_bounds: !min_auto_bounds.is_valid(),
             hovered_entry: None,
             hidden_items: Default::default(),
@@ -490,8 +1741,20 @@ impl Plot {
                 min_auto_bounds,
                 center_x_axis,
                 center_y_axis,
-            ),
+            )
+            .with_scales(axis_scales),
             last_click_pos_for_zoom: None,
+            selection_start: None,
+            view_action: None,
+            anchor_offset: None,
+            grown_bounds: PlotBounds::NOTHING,
+            last_auto_bounds_update: 0.0,
+            drag_captured: false,
+            item_zoom: None,
+            legend_filter: String::new(),
+            secondary_range: None,
+            shape_cache: ShapeCache::default(),
+            warned_empty_items: Default::default(),
         });
 
         // If the min bounds changed, recalculate everything.
@@ -502,7 +1765,9 @@ impl Plot {
                 min_auto_bounds,
                 ..memory
             };
-            memory.clone().store(ui.ctx(), plot_id);
+            if !static_mode {
+                memory.clone().store(ui.ctx(), plot_id);
+            }
         }
 
         let PlotMemory {
@@ -511,6 +1776,17 @@ impl Plot {
             mut hidden_items,
             last_screen_transform,
             mut last_click_pos_for_zoom,
+            mut selection_start,
+            view_action,
+            anchor_offset,
+            mut grown_bounds,
+            mut last_auto_bounds_update,
+            mut drag_captured,
+            mut item_zoom,
+            mut legend_filter,
+            secondary_range,
+            shape_cache,
+            mut warned_empty_items,
             ..
         } = memory;
 
@@ -518,31 +1794,70 @@ impl Plot {
         let mut plot_ui = PlotUi {
             items: Vec::new(),
             next_auto_color_idx: 0,
+            auto_marker_cycle,
+            next_auto_marker_idx: 0,
             last_screen_transform,
             response,
             ctx: ui.ctx().clone(),
+            zoom_step: zoom_step.unwrap_or(1.25),
+            zoom_request: None,
+            shapes_requested: false,
+            stable_colors,
+            warn_on_empty_items,
+            empty_items: Vec::new(),
+            last_secondary_range: secondary_range,
+            bounds_request: None,
         };
         let inner = build_fn(&mut plot_ui);
         let PlotUi {
             mut items,
             mut response,
             last_screen_transform,
+            zoom_request,
+            shapes_requested,
+            empty_items,
+            bounds_request,
             ..
         } = plot_ui;
 
-        // Background
-        if show_background {
-            ui.painter().sub_region(rect).add(epaint::RectShape {
-                rect,
-                corner_radius: 2.0,
-                fill: ui.visuals().extreme_bg_color,
-                stroke: ui.visuals().widgets.noninteractive.bg_stroke,
-            });
+        // Warn about each empty item once, until it has values again.
+        warned_empty_items.retain(|name| empty_items.iter().any(|(empty, _)| empty == name));
+        for (name, _) in &empty_items {
+            if warned_empty_items.insert(name.clone()) {
+                #[cfg(feature = "log")]
+                log::warn!(
+                    "egui: The plot item {:?} has no values and is not shown",
+                    name
+                );
+            }
         }
+        let empty_items: Vec<String> = empty_items
+            .into_iter()
+            .filter(|(_, show_in_legend)| *show_in_legend)
+            .map(|(name, _)| name)
+            .collect();
 
         // --- Legend ---
-        let legend = legend_config
-            .and_then(|config| LegendWidget::try_new(rect, config, &items, &hidden_items));
+        let mut legend = legend_config.and_then(|config| {
+            LegendWidget::try_new(
+                rect,
+                config,
+                &items,
+                &empty_items,
+                &hidden_items,
+                legend_filter.clone(),
+            )
+        });
+        // A legend outside of the plot area takes its space from it.
+        let rect = match &mut legend {
+            Some(legend) => legend.reserve_space(ui, rect),
+            None => rect,
+        };
+
+        // Background
+        if show_background {
+            ui.painter().sub_region(rect).add(background(ui, rect));
+        }
         // Don't show hover cursor when hovering over legend.
         if hovered_entry.is_some() {
             hover_line = HoverLine::None;
@@ -561,6 +1876,7 @@ impl Plot {
 
         // --- Bound computation ---
         let mut bounds = *last_screen_transform.bounds();
+        let mut bounds_source = BoundsSource::Memory;
 
         // Transfer the bounds from a link group.
         if let Some(axes) = linked_axes.as_ref() {
@@ -575,38 +1891,145 @@ impl Plot {
                 }
                 // Turn off auto bounds to keep it from overriding what we just set.
                 auto_bounds = false;
+                bounds_source = BoundsSource::LinkedGroup;
             }
         }
 
-        // Allow double clicking to reset to automatic bounds.
-        auto_bounds |= response.double_clicked_by(PointerButton::Primary);
-
-        // Set bounds automatically based on content.
-        if auto_bounds || !bounds.is_valid() {
-            bounds = min_auto_bounds;
-            items
-                .iter()
-                .for_each(|item| bounds.merge(&item.get_bounds()));
-            bounds.add_relative_margin(margin_fraction);
+        // Allow double clicking to zoom to an item or to reset to automatic bounds, and apply a
+        // view change requested on the previous frame.
+        let double_clicked = !locked && response.double_clicked_by(PointerButton::Primary);
+        let mut zoomed_to_item = false;
+        if let (true, Some(target), Some(pointer)) =
+            (double_clicked, &item_double_click, response.hover_pos())
+        {
+            let target = closest_item(
+                &items,
+                pointer,
+                &last_screen_transform,
+                secondary_range.as_ref(),
+            )
+            .and_then(|(item, elem)| target(item.name(), &elem));
+            if let Some(mut target) = target {
+                if let Some((min_span, max_span)) = zoom_bounds {
+                    target = last_screen_transform.limit_bounds(target, min_span, max_span);
+                }
+                item_zoom = Some((bounds, target, ui.input().time));
+                zoomed_to_item = true;
+            }
         }
-
-        let mut transform = ScreenTransform::new(rect, bounds, center_x_axis, center_y_axis);
-
-        // Enforce equal aspect ratio.
-        if let Some(data_aspect) = data_aspect {
-            let preserve_y = linked_axes
-                .as_ref()
-                .map_or(false, |group| group.link_y && !group.link_x);
-            transform.set_aspect(data_aspect as f64, preserve_y);
+        let reset_bounds = (double_clicked && !zoomed_to_item && allow_double_click_reset)
+            || view_action == Some(ViewAction::ResetBounds);
+        if reset_bounds {
+            item_zoom = None;
         }
+        auto_bounds = (auto_bounds || reset_bounds) && allow_auto_bounds;
+        let fit_once = view_action == Some(ViewAction::AutoFit);
 
-        // Dragging
-        if allow_drag && response.dragged_by(PointerButton::Primary) {
-            response = response.on_hover_cursor(CursorIcon::Grabbing);
-            transform.translate_bounds(-response.drag_delta());
+        // An anchored value takes precedence over automatic bounds.
+        if anchor_value.is_some() && anchor_offset.is_some() {
             auto_bounds = false;
         }
 
+        // Set bounds automatically based on content.
+        let mut refit_secondary = false;
+        if auto_bounds || reset_bounds || fit_once || !bounds.is_valid() {
+            let now = ui.input().time;
+            let refit = reset_bounds || fit_once || !bounds.is_valid();
+            let update = match auto_bounds_update {
+                UpdateMode::EveryFrame | UpdateMode::OnlyGrow => true,
+                UpdateMode::Debounced(interval) => {
+                    refit || now - last_auto_bounds_update >= interval
+                }
+            };
+            if update {
+                let mut data_bounds = content_bounds(&items, min_auto_bounds);
+                refit_secondary = true;
+                if auto_bounds_update == UpdateMode::OnlyGrow {
+                    if refit {
+                        grown_bounds = PlotBounds::NOTHING;
+                    }
+                    grown_bounds.merge(&data_bounds);
+                    data_bounds = grown_bounds;
+                }
+                fit_log_axes(&mut data_bounds, axis_scales, &items);
+                bounds = with_margins(
+                    data_bounds,
+                    axis_scales,
+                    degenerate_bounds_margin,
+                    margin_fraction,
+                );
+                last_auto_bounds_update = now;
+                bounds_source = BoundsSource::AutoBounds;
+            }
+        }
+
+        // Move towards the bounds of a double clicked item.
+        if let Some((start, target, start_time)) = item_zoom {
+            let t = ((ui.input().time - start_time) / ITEM_ZOOM_DURATION).clamp(0.0, 1.0);
+            let t = t * t * (3.0 - 2.0 * t);
+            for axis in 0..2 {
+                bounds.min[axis] = lerp(start.min[axis]..=target.min[axis], t);
+                bounds.max[axis] = lerp(start.max[axis]..=target.max[axis], t);
+            }
+            if t < 1.0 {
+                ui.ctx().request_repaint();
+            } else {
+                item_zoom = None;
+            }
+            auto_bounds = false;
+            bounds_source = BoundsSource::ItemZoom;
+        }
+
+        // Bounds set from within the build function take precedence over all of the above.
+        if let Some(requested) = bounds_request {
+            bounds = requested;
+            auto_bounds = false;
+            item_zoom = None;
+            bounds_source = BoundsSource::Explicit;
+        }
+
+        let mut transform = ScreenTransform::new(rect, bounds, center_x_axis, center_y_axis)
+            .with_scales(axis_scales);
+
+        // Enforce equal aspect ratio.
+        if let Some(data_aspect) = data_aspect {
+            let preserve_y = match aspect_anchor {
+                Some(axis) => axis == Axis::Y,
+                None => linked_axes
+                    .as_ref()
+                    .map_or(false, |group| group.link_y && !group.link_x),
+            };
+            transform.set_aspect(data_aspect as f64, preserve_y);
+        }
+
+        // Move the anchored value to where it was shown on the last frame.
+        if let (Some(value), Some(offset), None) = (anchor_value, anchor_offset, bounds_request) {
+            let pos = transform.position_from_value(&value);
+            transform.translate_bounds(pos - (rect.min + offset));
+            bounds_source = BoundsSource::Anchor;
+        }
+
+        // Dragging, unless the app claimed the drag.
+        if !locked && response.drag_started() && response.dragged_by(PointerButton::Primary) {
+            let origin = ui.input().pointer.press_origin();
+            drag_captured = match (&capture_drag, origin) {
+                (Some(capture), Some(origin)) => capture(transform.value_from_position(origin)),
+                _ => false,
+            };
+        } else if !response.dragged() {
+            drag_captured = false;
+        }
+        let mut captured_drag_delta = None;
+        if drag_captured && response.dragged_by(PointerButton::Primary) {
+            captured_drag_delta = Some(response.drag_delta());
+        } else if allow_drag && response.dragged_by(PointerButton::Primary) {
+            response = response.on_hover_cursor(CursorIcon::Grabbing);
+            transform.translate_bounds(-response.drag_delta());
+            auto_bounds = false;
+            item_zoom = None;
+            bounds_source = BoundsSource::Drag;
+        }
+
         // Zooming
         let mut boxed_zoom_rect = None;
         if allow_boxed_zoom {
@@ -620,8 +2043,18 @@ impl Plot {
             if let (Some(box_start_pos), Some(box_end_pos)) = (box_start_pos, box_end_pos) {
                 // while dragging prepare a Shape and draw it later on top of the plot
                 if response.dragged_by(boxed_zoom_pointer) {
-                    response = response.on_hover_cursor(CursorIcon::ZoomIn);
-                    let rect = epaint::Rect::from_two_pos(box_start_pos, box_end_pos);
+                    response = response.on_hover_cursor(if on_box_select.is_some() {
+                        CursorIcon::Crosshair
+                    } else {
+                        CursorIcon::ZoomIn
+                    });
+                    let mut rect = epaint::Rect::from_two_pos(box_start_pos, box_end_pos);
+                    for (axis, constrained) in boxed_zoom_axes.iter().enumerate() {
+                        if !constrained {
+                            rect.min[axis] = transform.frame().min[axis];
+                            rect.max[axis] = transform.frame().max[axis];
+                        }
+                    }
                     boxed_zoom_rect = Some((
                         epaint::RectShape::stroke(
                             rect,
@@ -639,13 +2072,28 @@ impl Plot {
                 if response.drag_released() {
                     let box_start_pos = transform.value_from_position(box_start_pos);
                     let box_end_pos = transform.value_from_position(box_end_pos);
-                    let new_bounds = PlotBounds {
-                        min: [box_start_pos.x, box_end_pos.y],
-                        max: [box_end_pos.x, box_start_pos.y],
-                    };
-                    if new_bounds.is_valid() {
+                    let mut new_bounds = *transform.bounds();
+                    if boxed_zoom_axes[0] {
+                        new_bounds.min[0] = box_start_pos.x;
+                        new_bounds.max[0] = box_end_pos.x;
+                    }
+                    if boxed_zoom_axes[1] {
+                        new_bounds.min[1] = box_end_pos.y;
+                        new_bounds.max[1] = box_start_pos.y;
+                    }
+                    if let Some(select) = on_box_select.take() {
+                        // The box may have been dragged out in any direction.
+                        let mut selection = PlotBounds::NOTHING;
+                        selection.extend_with(&Value::new(new_bounds.min[0], new_bounds.min[1]));
+                        selection.extend_with(&Value::new(new_bounds.max[0], new_bounds.max[1]));
+                        select(selection);
+                    } else if new_bounds.is_valid() {
+                        if let Some((min_span, max_span)) = zoom_bounds {
+                            new_bounds = transform.limit_bounds(new_bounds, min_span, max_span);
+                        }
                         *transform.bounds_mut() = new_bounds;
                         auto_bounds = false;
+                        bounds_source = BoundsSource::BoxedZoom;
                     } else {
                         auto_bounds = true;
                     }
@@ -655,74 +2103,477 @@ impl Plot {
             }
         }
 
+        // Selecting a region, which leaves the view as it is.
+        let mut selection_rect = None;
+        let mut selection = None;
+        if allow_selection && !static_mode {
+            if response.drag_started() && response.dragged_by(selection_pointer_button) {
+                selection_start = response.hover_pos();
+            }
+            if let (Some(start), Some(end)) = (selection_start, response.hover_pos()) {
+                if response.dragged_by(selection_pointer_button) {
+                    let visuals = &ui.visuals().selection;
+                    selection_rect = Some(epaint::RectShape {
+                        rect: Rect::from_two_pos(start, end),
+                        corner_radius: 0.0,
+                        fill: visuals.bg_fill.linear_multiply(0.3),
+                        stroke: visuals.stroke,
+                    });
+                }
+                if response.drag_released() {
+                    let mut bounds = PlotBounds::NOTHING;
+                    bounds.extend_with(&transform.value_from_position(start));
+                    bounds.extend_with(&transform.value_from_position(end));
+                    selection = Some(bounds).filter(PlotBounds::is_valid);
+                }
+            }
+            // A drag that is released outside of the plot selects nothing.
+            if response.drag_released() {
+                selection_start = None;
+            }
+        }
+
         if allow_zoom {
-            if let Some(hover_pos) = response.hover_pos() {
-                let zoom_factor = if data_aspect.is_some() {
+            let hover_pos = response
+                .hover_pos()
+                .filter(|pos| !scroll_requires_frame_hover || transform.frame().contains(*pos));
+            if let Some(hover_pos) = hover_pos {
+                let mut zoom_factor = if data_aspect.is_some() {
                     Vec2::splat(ui.input().zoom_delta())
                 } else {
                     ui.input().zoom_delta_2d()
                 };
+                // A pinch gesture follows the fingers, so only quantize scrolling.
+                let pinching = ui.input().multi_touch().is_some();
+                if let (Some(zoom_step), false) = (zoom_step, pinching) {
+                    let quantize = |factor: f32| {
+                        if factor > 1.0 {
+                            zoom_step
+                        } else if factor < 1.0 {
+                            1.0 / zoom_step
+                        } else {
+                            1.0
+                        }
+                    };
+                    zoom_factor = vec2(quantize(zoom_factor.x), quantize(zoom_factor.y));
+                }
+                if let Some((min_span, max_span)) = zoom_bounds {
+                    zoom_factor = transform.limit_zoom(zoom_factor, hover_pos, min_span, max_span);
+                }
                 if zoom_factor != Vec2::splat(1.0) {
                     transform.zoom(zoom_factor, hover_pos);
                     auto_bounds = false;
+                    item_zoom = None;
+                    bounds_source = BoundsSource::Zoom;
                 }
 
                 let scroll_delta = ui.input().scroll_delta;
                 if scroll_delta != Vec2::ZERO {
                     transform.translate_bounds(-scroll_delta);
                     auto_bounds = false;
+                    bounds_source = BoundsSource::Zoom;
                 }
             }
         }
 
+        // Zoom requested from within the build function.
+        if let Some(zoom_factor) = zoom_request {
+            let center = transform.frame().center();
+            let mut zoom_factor = Vec2::splat(zoom_factor);
+            if let Some((min_span, max_span)) = zoom_bounds {
+                zoom_factor = transform.limit_zoom(zoom_factor, center, min_span, max_span);
+            }
+            transform.zoom(zoom_factor, center);
+            auto_bounds = false;
+            bounds_source = BoundsSource::Zoom;
+        }
+
+        // Keep the view within the content where it may not leave it.
+        if scroll_beyond_data.contains(&false) {
+            let mut data_bounds = content_bounds(&items, min_auto_bounds);
+            fit_log_axes(&mut data_bounds, axis_scales, &items);
+            let data_bounds = with_margins(
+                data_bounds,
+                axis_scales,
+                degenerate_bounds_margin,
+                margin_fraction,
+            );
+            // Shift in the space in which the bounds are drawn, so that the view keeps its size
+            // on screen on non-linear axes.
+            let mut bounds = transform.bounds().map_axes(axis_scales, AxisScale::forward);
+            bounds.shift_into(
+                &data_bounds.map_axes(axis_scales, AxisScale::forward),
+                scroll_beyond_data.map(|allow| !allow),
+            );
+            *transform.bounds_mut() = bounds.map_axes(axis_scales, AxisScale::inverse);
+        }
+
+        // Never show anything outside of the allowed range.
+        if let Some(range) = bounds_range {
+            transform.bounds_mut().clamp_edges(&range);
+        }
+
+        // The secondary y axis follows the primary one, unless it is fitted along with it.
+        let secondary_range = if items.iter().any(|item| item.on_secondary_axis()) {
+            let bounds = transform.bounds();
+            let primary = [bounds.min[1], bounds.max[1]];
+            match secondary_range.filter(|_| !refit_secondary) {
+                Some(last) => Some(SecondaryRange {
+                    primary,
+                    secondary: last.map(primary, axis_scales[1]),
+                }),
+                None => fit_secondary_axis(
+                    &items,
+                    axis_scales[1],
+                    degenerate_bounds_margin,
+                    margin_fraction.y,
+                )
+                .map(|secondary| SecondaryRange { primary, secondary }),
+            }
+        } else {
+            None
+        };
+
         // Initialize values from functions.
-        items
-            .iter_mut()
-            .for_each(|item| item.initialize(transform.bounds().range_x()));
+        for item in &mut items {
+            item.initialize(transform.bounds().range_x());
+        }
+
+        // The element under the pointer, to report it in the response.
+        let hovered = response.hover_pos().and_then(|pointer| {
+            closest_item(&items, pointer, &transform, secondary_range.as_ref())
+        });
+        let hovered_id = hovered.map(|(_, elem)| elem.user_id);
+        let hovered_value = hovered.and_then(|(item, elem)| item.element_value(elem.index));
+        let nearest_item = hovered.map(|(item, _)| item.name().to_owned());
+        let clicked_id = hovered_id.filter(|_| response.clicked());
+        let clicked_value = hovered_value.filter(|_| response.clicked());
+
+        // Share the hovered x-coordinate with the other plots of the group.
+        let synced_hover_x = hover_sync.as_ref().and_then(|group| {
+            let hovered_x = response
+                .hover_pos()
+                .map(|pos| transform.value_from_position(pos).x);
+            group.sync(plot_id, hovered_x)
+        });
 
+        let new_shape_cache = Rc::new(RefCell::new(ShapeCache::default()));
         let prepared = PreparedPlot {
             items,
             hover_line,
             show_hover_label,
+            stacked_hover,
+            info_box,
             hover_formatter,
+            highlight_style,
             axis_formatters,
+            secondary_axis_formatter,
+            secondary_range,
+            axis_units,
+            avoid_label_overlap,
+            declutter_labels,
+            grid_renderer,
+            grid_color,
+            grid_opacity,
+            gridlines_at_labels_only,
+            grid_base,
+            grid_spacers,
+            min_tick_spacing,
             show_axes,
+            magnifier,
+            overlay,
+            overlay_above_hover,
+            now_line,
+            synced_hover_x,
+            left_margin,
+            title,
+            max_shapes,
+            shape_cache: cache_shapes.then(|| shape_cache),
+            new_shape_cache: new_shape_cache.clone(),
             transform: transform.clone(),
         };
-        prepared.ui(ui, &response);
+        let exported_shapes = shapes_requested.then(|| {
+            let ui_clip_rect = ui.clip_rect();
+            let clipped = |clip_rect: Rect, shapes: Vec<Shape>| {
+                let clip_rect = clip_rect.intersect(ui_clip_rect);
+                shapes
+                    .into_iter()
+                    .map(move |shape| ClippedShape(clip_rect, shape))
+            };
+            let mut shapes = Vec::new();
+            if show_background {
+                shapes.extend(clipped(rect, vec![Shape::Rect(background(ui, rect))]));
+            }
+            let (frame_shapes, margin_shapes, _) = prepared.to_shapes(ui);
+            shapes.extend(clipped(*prepared.transform.frame(), frame_shapes));
+            shapes.extend(clipped(prepared.margin_rect(ui), margin_shapes));
+            PlotShapes {
+                rect: widget_rect,
+                pixels_per_point: ui.ctx().pixels_per_point(),
+                shapes,
+            }
+        });
+        let axis_label_rects = prepared.ui(ui, &response).map(|rects| {
+            rects
+                .into_iter()
+                .map(|rect| rect.translate(-widget_rect.min.to_vec2()))
+                .collect()
+        });
+        let shape_cache = new_shape_cache.take();
 
         if let Some(boxed_zoom_rect) = boxed_zoom_rect {
             ui.painter().sub_region(rect).add(boxed_zoom_rect.0);
             ui.painter().sub_region(rect).add(boxed_zoom_rect.1);
         }
+        if let Some(selection_rect) = selection_rect {
+            ui.painter().sub_region(rect).add(selection_rect);
+        }
 
         if let Some(mut legend) = legend {
             ui.add(&mut legend);
             hidden_items = legend.get_hidden_items();
             hovered_entry = legend.get_hovered_entry_name();
+            legend_filter = legend.get_filter().to_owned();
         }
 
         if let Some(group) = linked_axes.as_ref() {
             group.set(*transform.bounds());
         }
 
+        let anchor_offset =
+            anchor_value.map(|value| transform.position_from_value(&value) - rect.min);
+
+        let mut hidden_item_names: Vec<String> = hidden_items.iter().cloned().collect();
+        hidden_item_names.sort();
+
         let memory = PlotMemory {
             auto_bounds,
             hovered_entry,
             hidden_items,
             min_auto_bounds,
-            last_screen_transform: transform,
+            last_screen_transform: transform.clone(),
             last_click_pos_for_zoom,
+            selection_start,
+            view_action: None,
+            anchor_offset,
+            grown_bounds,
+            last_auto_bounds_update,
+            drag_captured,
+            item_zoom,
+            legend_filter,
+            secondary_range,
+            shape_cache,
+            warned_empty_items,
         };
-        memory.store(ui.ctx(), plot_id);
+        if !static_mode {
+            memory.store(ui.ctx(), plot_id);
+        }
 
-        let response = if !matches!(hover_line, HoverLine::None) {
-            response.on_hover_cursor(CursorIcon::Crosshair)
-        } else {
-            response
+        let response = match hover_cursor {
+            Some(cursor) if !matches!(hover_line, HoverLine::None) => {
+                response.on_hover_cursor(cursor)
+            }
+            _ => response,
         };
 
-        InnerResponse { inner, response }
+        PlotResponse {
+            inner,
+            response,
+            hidden_items: hidden_item_names,
+            shapes: exported_shapes,
+            bounds_source,
+            captured_drag_delta,
+            axis_label_rects,
+            hovered_id,
+            clicked_id,
+            hovered_value,
+            clicked_value,
+            nearest_item,
+            selection,
+            plot_id,
+            transform,
+        }
+    }
+}
+
+/// The bounds of the content of the primary axes for the automatic bounds, including
+/// `min_auto_bounds`. Items on the secondary y axis only contribute their x range.
+fn content_bounds(items: &[Box<dyn PlotItem>], min_auto_bounds: PlotBounds) -> PlotBounds {
+    let mut bounds = min_auto_bounds;
+    for item in items {
+        let item_bounds = item.get_bounds();
+        if !item.on_secondary_axis() {
+            bounds.merge(&item_bounds);
+        } else if item_bounds.is_valid() {
+            // Only the x axis is shared.
+            bounds.extend_with_x(item_bounds.min[0]);
+            bounds.extend_with_x(item_bounds.max[0]);
+        }
+    }
+    bounds
+}
+
+/// The automatic bounds of the given content bounds. The margins are added in the space in which
+/// the bounds are drawn, so that they have the same size on screen on non-linear axes.
+fn with_margins(
+    data_bounds: PlotBounds,
+    axis_scales: [AxisScale; 2],
+    degenerate_bounds_margin: f64,
+    margin_fraction: Vec2,
+) -> PlotBounds {
+    let mut bounds = data_bounds.map_axes(axis_scales, AxisScale::forward);
+    bounds.expand_degenerate(degenerate_bounds_margin);
+    bounds.add_relative_margin(margin_fraction);
+    bounds.map_axes(axis_scales, AxisScale::inverse)
+}
+
+/// The height reserved above the plot area for the title, if any, see [`Plot::title`].
+fn title_height(ui: &Ui, title: &Option<String>) -> f32 {
+    if title.is_some() {
+        ui.fonts()
+            .row_height(&TextStyle::Heading.resolve(ui.style()))
+            + ui.spacing().item_spacing.y
+    } else {
+        0.0
+    }
+}
+
+/// The color of the gridlines and axis labels with the given opacity.
+fn color_from_alpha(ui: &Ui, alpha: f32) -> Color32 {
+    if ui.visuals().dark_mode {
+        Rgba::from_white_alpha(alpha).into()
+    } else {
+        Rgba::from_black_alpha((4.0 * alpha).at_most(1.0)).into()
+    }
+}
+
+/// The automatic range of the secondary y axis, fitted to its items like the primary axes.
+fn fit_secondary_axis(
+    items: &[Box<dyn PlotItem>],
+    scale: AxisScale,
+    degenerate_bounds_margin: f64,
+    margin_fraction: f32,
+) -> Option<[f64; 2]> {
+    let mut bounds = PlotBounds::NOTHING;
+    for item in items.iter().filter(|item| item.on_secondary_axis()) {
+        let item_bounds = item.get_bounds();
+        bounds.extend_with_y(item_bounds.min[1]);
+        bounds.extend_with_y(item_bounds.max[1]);
+    }
+    // Only the y axis is of interest.
+    bounds.extend_with_x(0.0);
+    bounds.extend_with_x(1.0);
+    if !bounds.is_finite() {
+        return None;
+    }
+    let scales = [AxisScale::Linear, scale];
+    let mut bounds = bounds.map_axes(scales, AxisScale::forward);
+    bounds.expand_degenerate(degenerate_bounds_margin);
+    bounds.add_relative_margin(vec2(0.0, margin_fraction));
+    let bounds = bounds.map_axes(scales, AxisScale::inverse);
+    Some([bounds.min[1], bounds.max[1]])
+}
+
+/// Keeps the automatic bounds of [`AxisScale::Log`] axes positive by leaving out non-positive
+/// values.
+fn fit_log_axes(bounds: &mut PlotBounds, scales: [AxisScale; 2], items: &[Box<dyn PlotItem>]) {
+    for (axis, scale) in scales.iter().enumerate() {
+        if *scale != AxisScale::Log || !bounds.is_valid() || bounds.min[axis] > 0.0 {
+            continue;
+        }
+        let smallest_positive = items
+            .iter()
+            .filter_map(|item| item.values())
+            .flatten()
+            .map(|value| if axis == 0 { value.x } else { value.y })
+            .filter(|value| *value > 0.0)
+            .min_by_key(|value| value.ord());
+        if bounds.max[axis] > 0.0 {
+            bounds.min[axis] = smallest_positive.unwrap_or(bounds.max[axis] / 10.0);
+        } else {
+            bounds.min[axis] = 1.0;
+            bounds.max[axis] = 10.0;
+        }
+    }
+}
+
+/// The screen rect of the [`PlotItem::clip_data`] of the item, if any.
+fn data_clip_rect(item: &dyn PlotItem, transform: &ScreenTransform) -> Option<Rect> {
+    let bounds = item.clip_data()?;
+    let [min_x, min_y] = bounds.min();
+    let [max_x, max_y] = bounds.max();
+    Some(transform.rect_from_values(&Value::new(min_x, min_y), &Value::new(max_x, max_y)))
+}
+
+/// The item element closest to the pointer, if it is within [`INTERACT_RADIUS`].
+fn closest_item<'a>(
+    items: &'a [Box<dyn PlotItem>],
+    pointer: Pos2,
+    transform: &ScreenTransform,
+    secondary: Option<&SecondaryRange>,
+) -> Option<(&'a dyn PlotItem, ClosestElem)> {
+    items
+        .iter()
+        .filter_map(|item| {
+            let item = &**item;
+            let transform = item_transform(item, transform, secondary);
+            // The parts outside of the clip data are not drawn, so they can't be hovered.
+            if let Some(clip_rect) = data_clip_rect(item, &transform) {
+                if !clip_rect.contains(pointer) {
+                    return None;
+                }
+            }
+            Some(item).zip(item.find_closest(pointer, &transform))
+        })
+        .min_by_key(|(_, elem)| elem.dist_sq.ord())
+        .filter(|(_, elem)| elem.dist_sq <= INTERACT_RADIUS.powi(2))
+        .map(|(item, elem)| {
+            let user_id = item.user_id(elem.index);
+            (item, ClosestElem { user_id, ..elem })
+        })
+}
+
+/// Drops every other label until no two neighboring labels overlap.
+fn drop_overlapping_labels(labels: &mut Vec<(Pos2, Arc<Galley>, bool)>) {
+    let rect = |(pos, galley, _): &(Pos2, Arc<Galley>, bool)| {
+        Rect::from_min_size(*pos, galley.size()).expand(1.0)
+    };
+    while labels.len() > 1
+        && labels
+            .windows(2)
+            .any(|w| rect(&w[0]).intersects(rect(&w[1])))
+    {
+        let mut index = 0;
+        labels.retain(|_| {
+            index += 1;
+            index % 2 == 1
+        });
+    }
+}
+
+/// Which of the data labels, e.g. of [`Text`] items, to hide so that the rest don't overlap.
+/// Labels are placed greedily in the given order, so earlier labels take priority.
+fn declutter(label_rects: impl Iterator<Item = Option<Rect>>) -> Vec<bool> {
+    let mut placed: Vec<Rect> = Vec::new();
+    label_rects
+        .map(|rect| match rect {
+            Some(rect) if placed.iter().any(|other| other.intersects(rect)) => true,
+            Some(rect) => {
+                placed.push(rect);
+                false
+            }
+            None => false,
+        })
+        .collect()
+}
+
+/// The frame drawn behind the plot if [`Plot::show_background`] is enabled.
+fn background(ui: &Ui, rect: Rect) -> epaint::RectShape {
+    epaint::RectShape {
+        rect,
+        corner_radius: 2.0,
+        fill: ui.visuals().extreme_bg_color,
+        stroke: ui.visuals().widgets.noninteractive.bg_stroke,
     }
 }
 
@@ -731,15 +2582,41 @@ impl Plot {
 pub struct PlotUi {
     items: Vec<Box<dyn PlotItem>>,
     next_auto_color_idx: usize,
+    auto_marker_cycle: Vec<MarkerShape>,
+    next_auto_marker_idx: usize,
     last_screen_transform: ScreenTransform,
     response: Response,
     ctx: Context,
+    zoom_step: f32,
+    zoom_request: Option<f32>,
+    shapes_requested: bool,
+    stable_colors: bool,
+    warn_on_empty_items: bool,
+    /// The names of the items that were dropped for having no values, and whether they are shown
+    /// in the legend.
+    empty_items: Vec<(String, bool)>,
+    last_secondary_range: Option<SecondaryRange>,
+    bounds_request: Option<PlotBounds>,
 }
 
 impl PlotUi {
-    fn auto_color(&mut self) -> Color32 {
-        let i = self.next_auto_color_idx;
-        self.next_auto_color_idx += 1;
+    /// Called when an item is dropped for having no values, see [`Plot::warn_on_empty_items`].
+    fn drop_empty(&mut self, item: &dyn PlotItem) {
+        if !self.warn_on_empty_items || item.name().is_empty() {
+            return;
+        }
+        self.empty_items
+            .push((item.name().to_owned(), item.show_in_legend()));
+    }
+
+    fn auto_color(&mut self, name: &str) -> Color32 {
+        let i = if self.stable_colors && !name.is_empty() {
+            // Any index is as good as another, as long as it is the same in every frame.
+            (Id::new(name).value() % 64) as usize
+        } else {
+            self.next_auto_color_idx += 1;
+            self.next_auto_color_idx - 1
+        };
         let golden_ratio = (5.0_f32.sqrt() - 1.0) / 2.0; // 0.61803398875
         let h = i as f32 * golden_ratio;
         Hsva::new(h, 0.85, 0.5, 1.0).into() // TODO: OkLab or some other perspective color space
@@ -756,6 +2633,19 @@ impl PlotUi {
         *self.last_screen_transform.bounds()
     }
 
+    /// Show exactly the given bounds in this frame, instead of the automatic bounds or those of
+    /// the last frame, e.g. for a time window picked by the user. Automatic bounds are turned
+    /// off, so the plot keeps the bounds on the next frames until it is dragged, zoomed or
+    /// reset. Invalid bounds are ignored.
+    ///
+    /// The bounds are still adjusted to [`Plot::data_aspect`] and [`Plot::bounds_range`]. Also
+    /// see [`Plot::auto_bounds`] and [`Plot::allow_double_click_reset`].
+    pub fn set_plot_bounds(&mut self, bounds: PlotBounds) {
+        if bounds.is_valid() {
+            self.bounds_request = Some(bounds);
+        }
+    }
+
     /// Returns `true` if the plot area is currently hovered.
     pub fn plot_hovered(&self) -> bool {
         self.response.hovered()
@@ -776,6 +2666,88 @@ impl PlotUi {
         Vec2::new(delta.x / dp_dv[0] as f32, delta.y / dp_dv[1] as f32)
     }
 
+    /// The pointer drag delta in screen coordinates.
+    pub fn pointer_drag_delta(&self) -> Vec2 {
+        self.response.drag_delta()
+    }
+
+    /// Returns `true` if the plot area is currently being dragged.
+    pub fn plot_dragged(&self) -> bool {
+        self.response.dragged()
+    }
+
+    /// The screen rectangle of the plot area as it was in the last frame.
+    pub fn frame_rect(&self) -> Rect {
+        *self.last_screen_transform.frame()
+    }
+
+    /// The screen transform as it was in the last frame. Use it to paint custom overlays at the
+    /// right screen coordinates from within the build closure.
+    pub fn transform(&self) -> ScreenTransform {
+        self.last_screen_transform.clone()
+    }
+
+    /// The values of the first item added so far that has the given name and is backed by
+    /// [`Values`], i.e. a line, points or a polygon. Useful to compute statistics, e.g. a trend line,
+    /// from the plotted data.
+    ///
+    /// Lines defined by an explicit callback only get their values when the plot is drawn, so
+    /// they have no values yet at this point.
+    pub fn values_of(&self, name: &str) -> Option<&[Value]> {
+        self.items
+            .iter()
+            .filter(|item| item.name() == name)
+            .find_map(|item| item.values())
+    }
+
+    /// The union of the bounds of all items added so far, regardless of the current view, e.g.
+    /// for a minimap. [`PlotBounds::NOTHING`] if no items were added.
+    ///
+    /// Unlike the automatic bounds, this does not include [`Plot::include_x`],
+    /// [`Plot::include_y`] or any margin. Lines defined by an explicit callback only get their
+    /// values when the plot is drawn, so they are not included.
+    pub fn content_bounds(&self) -> PlotBounds {
+        let mut bounds = PlotBounds::NOTHING;
+        for item in &self.items {
+            bounds.merge(&item.get_bounds());
+        }
+        bounds
+    }
+
+    /// The names and bounds (in plot coordinates) of all items added so far, in the order they
+    /// were added.
+    ///
+    /// Lines defined by an explicit callback only get their values when the plot is drawn, so
+    /// their bounds are not known yet at this point.
+    pub fn item_bounds(&self) -> Vec<(String, PlotBounds)> {
+        self.items
+            .iter()
+            .map(|item| (item.name().to_owned(), item.get_bounds()))
+            .collect()
+    }
+
+    /// Zoom the plot by the given factor around the center of the plot area. A factor larger than
+    /// `1.0` zooms in. Takes effect when the plot is drawn.
+    pub fn zoom(&mut self, zoom_factor: f32) {
+        *self.zoom_request.get_or_insert(1.0) *= zoom_factor;
+    }
+
+    /// Zoom in by one step, see [`Plot::zoom_step`].
+    pub fn zoom_in(&mut self) {
+        self.zoom(self.zoom_step);
+    }
+
+    /// Zoom out by one step, see [`Plot::zoom_step`].
+    pub fn zoom_out(&mut self) {
+        self.zoom(1.0 / self.zoom_step);
+    }
+
+    /// Return the region and shapes of the plot in [`PlotResponse::shapes`] of this frame, e.g.
+    /// for a "save as image" button. They are not rasterized, see [`PlotShapes`].
+    pub fn export_shapes(&mut self) {
+        self.shapes_requested = true;
+    }
+
     /// Transform the plot coordinates to screen coordinates.
     pub fn screen_from_plot(&self, position: Value) -> Pos2 {
         self.last_screen_transform.position_from_value(&position)
@@ -786,15 +2758,34 @@ impl PlotUi {
         self.last_screen_transform.value_from_position(position)
     }
 
+    /// Transform the plot coordinates of the given y axis to screen coordinates. Without items
+    /// on the secondary axis, both axes are the same.
+    pub fn screen_from_plot_on(&self, axis: YAxis, position: Value) -> Pos2 {
+        self.transform_of(axis).position_from_value(&position)
+    }
+
+    /// Transform the screen coordinates to plot coordinates of the given y axis.
+    pub fn plot_from_screen_on(&self, axis: YAxis, position: Pos2) -> Value {
+        self.transform_of(axis).value_from_position(position)
+    }
+
+    fn transform_of(&self, axis: YAxis) -> ScreenTransform {
+        match (axis, &self.last_secondary_range) {
+            (YAxis::Secondary, Some(secondary)) => secondary.transform(&self.last_screen_transform),
+            _ => self.last_screen_transform.clone(),
+        }
+    }
+
     /// Add a data line.
     pub fn line(&mut self, mut line: Line) {
         if line.series.is_empty() {
+            self.drop_empty(&line);
             return;
         };
 
         // Give the stroke an automatic color if no color has been assigned.
         if line.stroke.color == Color32::TRANSPARENT {
-            line.stroke.color = self.auto_color();
+            line.stroke.color = self.auto_color(&line.name);
         }
         self.items.push(Box::new(line));
     }
@@ -802,16 +2793,61 @@ impl PlotUi {
     /// Add a polygon. The polygon has to be convex.
     pub fn polygon(&mut self, mut polygon: Polygon) {
         if polygon.series.is_empty() {
+            self.drop_empty(&polygon);
             return;
         };
 
         // Give the stroke an automatic color if no color has been assigned.
         if polygon.stroke.color == Color32::TRANSPARENT {
-            polygon.stroke.color = self.auto_color();
+            polygon.stroke.color = self.auto_color(&polygon.name);
         }
         self.items.push(Box::new(polygon));
     }
 
+    /// Add a ribbon, i.e. a band between a lower and an upper value for each x-coordinate.
+    pub fn ribbon(&mut self, mut ribbon: Ribbon) {
+        if ribbon.lower.is_empty() {
+            self.drop_empty(&ribbon);
+            return;
+        };
+
+        // Give the stroke an automatic color if no color has been assigned.
+        if ribbon.stroke.color == Color32::TRANSPARENT {
+            ribbon.stroke.color = self.auto_color(&ribbon.name);
+        }
+        self.items.push(Box::new(ribbon));
+    }
+
+    /// Add the layers of a stacked area chart, from the bottom up. Each layer is stacked on top of
+    /// the ones before it, and gets an automatic color if none has been assigned.
+    pub fn stacked_area(&mut self, layers: impl IntoIterator<Item = StackedArea>) {
+        let mut layers_below = Vec::new();
+        for mut layer in layers {
+            if layer.series.is_empty() {
+                self.drop_empty(&layer);
+                continue;
+            }
+
+            layer.stack_on(&layers_below);
+            layers_below.push(layer.values().unwrap_or_default().to_vec());
+
+            // Give the stroke an automatic color if no color has been assigned.
+            if layer.stroke.color == Color32::TRANSPARENT {
+                layer.stroke.color = self.auto_color(&layer.name);
+            }
+            self.items.push(Box::new(layer));
+        }
+    }
+
+    /// Add a filled circle in plot coordinates.
+    pub fn circle(&mut self, mut circle: Circle) {
+        // Give the stroke an automatic color if no color has been assigned.
+        if circle.stroke.color == Color32::TRANSPARENT {
+            circle.stroke.color = self.auto_color(&circle.name);
+        }
+        self.items.push(Box::new(circle));
+    }
+
     /// Add a text.
     pub fn text(&mut self, text: Text) {
         if text.text.is_empty() {
@@ -821,15 +2857,38 @@ impl PlotUi {
         self.items.push(Box::new(text));
     }
 
+    /// Add a text that follows a path.
+    pub fn path_text(&mut self, path_text: PathText) {
+        if path_text.series.is_empty() {
+            self.drop_empty(&path_text);
+            return;
+        };
+        if path_text.text.is_empty() {
+            return;
+        };
+
+        self.items.push(Box::new(path_text));
+    }
+
     /// Add data points.
     pub fn points(&mut self, mut points: Points) {
         if points.series.is_empty() {
+            self.drop_empty(&points);
             return;
         };
 
         // Give the points an automatic color if no color has been assigned.
         if points.color == Color32::TRANSPARENT {
-            points.color = self.auto_color();
+            points.color = self.auto_color(&points.name);
+        }
+        // Likewise for the marker shape, if automatic shapes are enabled.
+        if points.shape.is_none()
+            && points.custom_marker.is_none()
+            && !self.auto_marker_cycle.is_empty()
+        {
+            let i = self.next_auto_marker_idx % self.auto_marker_cycle.len();
+            self.next_auto_marker_idx += 1;
+            points.shape = Some(self.auto_marker_cycle[i]);
         }
         self.items.push(Box::new(points));
     }
@@ -837,12 +2896,13 @@ impl PlotUi {
     /// Add arrows.
     pub fn arrows(&mut self, mut arrows: Arrows) {
         if arrows.origins.is_empty() || arrows.tips.is_empty() {
+            self.drop_empty(&arrows);
             return;
         };
 
         // Give the arrows an automatic color if no color has been assigned.
         if arrows.color == Color32::TRANSPARENT {
-            arrows.color = self.auto_color();
+            arrows.color = self.auto_color(&arrows.name);
         }
         self.items.push(Box::new(arrows));
     }
@@ -852,12 +2912,21 @@ impl PlotUi {
         self.items.push(Box::new(image));
     }
 
+    /// Add a heatmap.
+    pub fn heatmap(&mut self, heatmap: HeatMap) {
+        if heatmap.rows == 0 || heatmap.columns == 0 {
+            self.drop_empty(&heatmap);
+            return;
+        }
+        self.items.push(Box::new(heatmap));
+    }
+
     /// Add a horizontal line.
     /// Can be useful e.g. to show min/max bounds or similar.
     /// Always fills the full width of the plot.
     pub fn hline(&mut self, mut hline: HLine) {
         if hline.stroke.color == Color32::TRANSPARENT {
-            hline.stroke.color = self.auto_color();
+            hline.stroke.color = self.auto_color(&hline.name);
         }
         self.items.push(Box::new(hline));
     }
@@ -867,20 +2936,40 @@ impl PlotUi {
     /// Always fills the full height of the plot.
     pub fn vline(&mut self, mut vline: VLine) {
         if vline.stroke.color == Color32::TRANSPARENT {
-            vline.stroke.color = self.auto_color();
+            vline.stroke.color = self.auto_color(&vline.name);
         }
         self.items.push(Box::new(vline));
     }
 
+    /// Add a horizontal band. Bands are drawn behind all other items, in the order they were
+    /// added, and show their name when hovering them.
+    pub fn hspan(&mut self, mut hspan: HSpan) {
+        if hspan.stroke.color == Color32::TRANSPARENT {
+            hspan.stroke.color = self.auto_color(&hspan.name);
+        }
+        self.items.push(Box::new(hspan));
+    }
+
+    /// Add a vertical band. Bands are drawn behind all other items, in the order they were added,
+    /// and show their name when hovering them.
+    pub fn vspan(&mut self, mut vspan: VSpan) {
+        if vspan.stroke.color == Color32::TRANSPARENT {
+            vspan.stroke.color = self.auto_color(&vspan.name);
+        }
+        self.items.push(Box::new(vspan));
+    }
+
     /// Add a box plot diagram.
     pub fn box_plot(&mut self, mut box_plot: BoxPlot) {
         if box_plot.boxes.is_empty() {
+            self.drop_empty(&box_plot);
             return;
         }
 
         // Give the elements an automatic color if no color has been assigned.
         if box_plot.default_color == Color32::TRANSPARENT {
-            box_plot = box_plot.color(self.auto_color());
+            let color = self.auto_color(&box_plot.name);
+            box_plot = box_plot.color(color);
         }
         self.items.push(Box::new(box_plot));
     }
@@ -888,12 +2977,14 @@ impl PlotUi {
     /// Add a bar chart.
     pub fn bar_chart(&mut self, mut chart: BarChart) {
         if chart.bars.is_empty() {
+            self.drop_empty(&chart);
             return;
         }
 
         // Give the elements an automatic color if no color has been assigned.
         if chart.default_color == Color32::TRANSPARENT {
-            chart = chart.color(self.auto_color());
+            let color = self.auto_color(&chart.name);
+            chart = chart.color(color);
         }
         self.items.push(Box::new(chart));
     }
@@ -903,41 +2994,340 @@ struct PreparedPlot {
     items: Vec<Box<dyn PlotItem>>,
     hover_line: HoverLine,
     show_hover_label: bool,
+    stacked_hover: bool,
+    info_box: Option<Corner>,
     hover_formatter: HoverFormatter,
+    highlight_style: HighlightStyle,
     axis_formatters: [AxisFormatter; 2],
+    secondary_axis_formatter: AxisFormatter,
+    secondary_range: Option<SecondaryRange>,
+    axis_units: [String; 2],
+    avoid_label_overlap: bool,
+    declutter_labels: bool,
+    grid_renderer: Option<Box<GridRendererFn>>,
+    grid_color: Option<Color32>,
+    grid_opacity: f32,
+    gridlines_at_labels_only: bool,
+    grid_base: [i64; 2],
+    grid_spacers: [Option<Box<GridSpacerFn>>; 2],
+    min_tick_spacing: [f32; 2],
     show_axes: [bool; 2],
+    magnifier: Option<(Key, f32)>,
+    overlay: Option<Box<OverlayFn>>,
+    overlay_above_hover: bool,
+    now_line: Option<(f64, String, Stroke)>,
+    synced_hover_x: Option<f64>,
+    left_margin: f32,
+    title: Option<String>,
+    max_shapes: Option<usize>,
+    /// The shapes from the last frame, if [`Plot::cache_shapes`] is on.
+    shape_cache: Option<ShapeCache>,
+    /// The shapes of this frame, for the next one.
+    new_shape_cache: Rc<RefCell<ShapeCache>>,
     transform: ScreenTransform,
 }
 
 impl PreparedPlot {
-    fn ui(self, ui: &mut Ui, response: &Response) {
+    /// Draws the plot and returns the screen rects of the labels of each axis.
+    fn ui(self, ui: &mut Ui, response: &Response) -> [Vec<Rect>; 2] {
+        let (shapes, margin_shapes, label_rects) = self.to_shapes(ui);
+        let transform = &self.transform;
+
+        let mut hover_shapes = Vec::new();
+        if let Some(pointer) = response.hover_pos() {
+            self.hover(ui, pointer, &mut hover_shapes);
+
+            // Bring back decluttered labels under the pointer.
+            let hidden = self.hidden_labels(ui, transform);
+            for (item, hidden) in self.items.iter().zip(hidden) {
+                let under_pointer = item
+                    .label_rect(ui, transform)
+                    .map_or(false, |rect| rect.contains(pointer));
+                if hidden && under_pointer {
+                    item.get_shapes(ui, transform, &self.highlight_style, &mut hover_shapes);
+                }
+            }
+        } else if let Some(x) = self.synced_hover_x {
+            let frame = transform.frame();
+            let pos_x = transform.position_from_value(&Value::new(x, 0.0)).x;
+            let color = items::rulers_color(ui).linear_multiply(0.5);
+            hover_shapes.push(Shape::line_segment(
+                [pos2(pos_x, frame.top()), pos2(pos_x, frame.bottom())],
+                Stroke::new(1.0, color),
+            ));
+        }
+
+        ui.painter().sub_region(*transform.frame()).extend(shapes);
+        let frame = transform.frame();
+        ui.painter()
+            .sub_region(self.margin_rect(ui))
+            .extend(margin_shapes);
+
+        let show_overlay = |ui: &mut Ui| {
+            if let Some(overlay) = &self.overlay {
+                let mut overlay_ui = ui.child_ui(*frame, *ui.layout());
+                overlay_ui.set_clip_rect(frame.intersect(ui.clip_rect()));
+                overlay(&mut overlay_ui, *frame);
+            }
+        };
+        if !self.overlay_above_hover {
+            show_overlay(ui);
+        }
+        ui.painter().sub_region(*frame).extend(hover_shapes);
+        if self.overlay_above_hover {
+            show_overlay(ui);
+        }
+
+        if let (Some((key, zoom)), Some(pointer)) = (self.magnifier, response.hover_pos()) {
+            if ui.input().key_down(key) && transform.frame().contains(pointer) {
+                self.magnify(ui, pointer, zoom);
+            }
+        }
+
+        label_rects
+    }
+
+    /// The region of the frame together with the left margin and the title above it.
+    fn margin_rect(&self, ui: &Ui) -> Rect {
+        let frame = self.transform.frame();
+        Rect::from_min_max(
+            pos2(
+                frame.left() - self.left_margin,
+                frame.top() - title_height(ui, &self.title),
+            ),
+            frame.max,
+        )
+    }
+
+    /// The shapes of the axes and all items, without any interaction. The axis labels drawn in
+    /// the left margin (see [`Plot::left_margin`]) and the title are returned separately, as they
+    /// lie outside the plot frame. Also returns the screen rects of the labels of each axis.
+    fn to_shapes(&self, ui: &mut Ui) -> (Vec<Shape>, Vec<Shape>, [Vec<Rect>; 2]) {
         let mut shapes = Vec::new();
+        let mut margin_shapes = Vec::new();
+        let mut label_rects = [Vec::new(), Vec::new()];
+
+        if let Some(title) = &self.title {
+            let frame = self.transform.frame();
+            let galley = ui.painter().layout_no_wrap(
+                title.clone(),
+                TextStyle::Heading.resolve(ui.style()),
+                ui.visuals().strong_text_color(),
+            );
+            let pos = pos2(
+                frame.center().x - galley.size().x / 2.0,
+                frame.top() - title_height(ui, &self.title),
+            );
+            margin_shapes.push(Shape::galley(pos, galley));
+        }
 
-        for d in 0..2 {
+        for (d, rects) in label_rects.iter_mut().enumerate() {
             if self.show_axes[d] {
-                self.paint_axis(ui, d, &mut shapes);
+                self.paint_axis(ui, d, &mut shapes, &mut margin_shapes, rects);
             }
         }
+        if self.show_axes[1] {
+            self.paint_secondary_axis(ui, &mut shapes, &mut label_rects[1]);
+        }
 
         let transform = &self.transform;
 
         let mut plot_ui = ui.child_ui(*transform.frame(), Layout::default());
         plot_ui.set_clip_rect(*transform.frame());
-        for item in &self.items {
-            item.get_shapes(&mut plot_ui, transform, &mut shapes);
+        self.item_shapes(&mut plot_ui, transform, &mut shapes, true);
+
+        if let Some((x, label, stroke)) = &self.now_line {
+            let frame = transform.frame();
+            let pos_x = transform.position_from_value(&Value::new(*x, 0.0)).x;
+            shapes.push(Shape::line_segment(
+                [pos2(pos_x, frame.top()), pos2(pos_x, frame.bottom())],
+                *stroke,
+            ));
+            if !label.is_empty() {
+                shapes.push(Shape::text(
+                    &*ui.fonts(),
+                    pos2(pos_x + 3.0, frame.top() + 2.0),
+                    Align2::LEFT_TOP,
+                    label,
+                    TextStyle::Body.resolve(ui.style()),
+                    stroke.color,
+                ));
+            }
         }
 
-        if let Some(pointer) = response.hover_pos() {
-            self.hover(ui, pointer, &mut shapes);
+        (shapes, margin_shapes, label_rects)
+    }
+
+    /// For each item, whether it is a label hidden by [`Plot::declutter_labels`].
+    fn hidden_labels(&self, ui: &Ui, transform: &ScreenTransform) -> Vec<bool> {
+        if self.declutter_labels {
+            declutter(
+                self.items
+                    .iter()
+                    .map(|item| item.label_rect(ui, transform).map(|rect| rect.expand(1.0))),
+            )
+        } else {
+            vec![false; self.items.len()]
         }
+    }
 
-        ui.painter().sub_region(*transform.frame()).extend(shapes);
+    /// Appends the shapes of all items in z-order, clipped to their data region if they have one
+    /// and respecting [`Plot::max_shapes`]. With `use_cache`, the shapes are taken from and added
+    /// to the cache of [`Plot::cache_shapes`], if it is on.
+    fn item_shapes(
+        &self,
+        ui: &mut Ui,
+        transform: &ScreenTransform,
+        shapes: &mut Vec<Shape>,
+        use_cache: bool,
+    ) {
+        let start = shapes.len();
+        let hidden = self.hidden_labels(ui, transform);
+        // Spans are drawn behind the other items, each in the order in which they were added.
+        let mut order: Vec<usize> = (0..self.items.len()).collect();
+        order.sort_by_key(|&index| !self.items[index].in_background());
+        for index in order {
+            let item = &self.items[index];
+            if hidden[index] {
+                continue;
+            }
+            // Stop generating shapes once the limit is reached, instead of dropping them later.
+            let remaining = match self.max_shapes {
+                Some(max_shapes) => max_shapes.saturating_sub(shapes.len() - start),
+                None => usize::MAX,
+            };
+            if remaining == 0 {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(
+                    "egui: Plot items reached the limit of {:?} shapes, the rest is not drawn",
+                    self.max_shapes
+                );
+                break;
+            }
+            let item_start = shapes.len();
+            let transform = &item_transform(&**item, transform, self.secondary_range.as_ref());
+            let key = item
+                .content_hash()
+                .filter(|_| use_cache && self.shape_cache.is_some())
+                .map(|hash| (index, hash));
+            if let Some(cached) = key.and_then(|key| self.cached_shapes(key, &**item, transform)) {
+                shapes.extend(cached.shapes.iter().take(remaining).cloned());
+                if let Some(key) = key {
+                    self.new_shape_cache.borrow_mut().0.insert(key, cached);
+                }
+            } else {
+                item.get_shapes_limited(ui, transform, &self.highlight_style, shapes, remaining);
+                if let Some(clip_rect) = data_clip_rect(&**item, transform) {
+                    let item_shapes = shapes.split_off(item_start);
+                    clip::clip_shapes(item_shapes, clip_rect, shapes);
+                    shapes.truncate(item_start.saturating_add(remaining));
+                }
+                // The shapes of an item that was cut off depend on the items before it.
+                let complete = shapes.len() - item_start < remaining;
+                if let Some(key) = key.filter(|_| complete) {
+                    let cached = CachedShapes {
+                        highlighted: item.highlighted(),
+                        transform: transform.clone(),
+                        shapes: Arc::new(shapes[item_start..].to_vec()),
+                    };
+                    self.new_shape_cache.borrow_mut().0.insert(key, cached);
+                }
+            }
+        }
+    }
+
+    /// The cached shapes of the item with the given index and content hash, if they were computed
+    /// for the same highlighting and transform, in this frame or the last one.
+    fn cached_shapes(
+        &self,
+        key: (usize, u64),
+        item: &dyn PlotItem,
+        transform: &ScreenTransform,
+    ) -> Option<CachedShapes> {
+        let new_shape_cache = self.new_shape_cache.borrow();
+        let cached = new_shape_cache
+            .0
+            .get(&key)
+            .or_else(|| self.shape_cache.as_ref()?.0.get(&key))?;
+        (cached.highlighted == item.highlighted() && cached.transform == *transform)
+            .then(|| cached.clone())
+    }
+
+    /// Draws an inset around the `pointer` that shows the items magnified by `zoom`.
+    ///
+    /// The inset is centered on the pointer, but is moved to stay within the plot frame when the
+    /// pointer is close to its edges. It always magnifies the region around the pointer.
+    fn magnify(&self, ui: &mut Ui, pointer: Pos2, zoom: f32) {
+        let frame = *self.transform.frame();
+        let lens_size = Vec2::splat(0.4 * frame.size().min_elem());
+        let mut lens_rect = Rect::from_center_size(pointer, lens_size);
+        lens_rect = lens_rect.translate(vec2(
+            (frame.left() - lens_rect.left()).at_least(0.0)
+                + (frame.right() - lens_rect.right()).at_most(0.0),
+            (frame.top() - lens_rect.top()).at_least(0.0)
+                + (frame.bottom() - lens_rect.bottom()).at_most(0.0),
+        ));
+
+        let center = self.transform.value_from_position(pointer);
+        let center = [center.x, center.y];
+        let scales = self.transform.scales();
+        let dvalue_dpos = self.transform.dvalue_dpos();
+        let half_extent = [
+            (0.5 * lens_size.x / zoom) as f64 * dvalue_dpos[0].abs(),
+            (0.5 * lens_size.y / zoom) as f64 * dvalue_dpos[1].abs(),
+        ];
+        // The extent is given in the space mapped by the axis scales.
+        let bound = |axis: usize, sign: f64| {
+            let scale = scales[axis];
+            scale.inverse(scale.forward(center[axis]) + sign * half_extent[axis])
+        };
+        let bounds = PlotBounds::from_min_max(
+            [bound(0, -1.0), bound(1, -1.0)],
+            [bound(0, 1.0), bound(1, 1.0)],
+        );
+        let lens_transform =
+            ScreenTransform::new(lens_rect, bounds, false, false).with_scales(scales);
+
+        let mut shapes = vec![Shape::Rect(epaint::RectShape::filled(
+            lens_rect,
+            0.0,
+            ui.visuals().extreme_bg_color,
+        ))];
+        let mut lens_ui = ui.child_ui(lens_rect, Layout::default());
+        lens_ui.set_clip_rect(lens_rect);
+        self.item_shapes(&mut lens_ui, &lens_transform, &mut shapes, false);
+        shapes.push(Shape::rect_stroke(
+            lens_rect,
+            0.0,
+            ui.visuals().widgets.noninteractive.fg_stroke,
+        ));
+
+        ui.painter()
+            .sub_region(lens_rect.expand(1.0))
+            .extend(shapes);
     }
 
-    fn paint_axis(&self, ui: &Ui, axis: usize, shapes: &mut Vec<Shape>) {
+    fn paint_axis(
+        &self,
+        ui: &Ui,
+        axis: usize,
+        shapes: &mut Vec<Shape>,
+        margin_shapes: &mut Vec<Shape>,
+        label_rects: &mut Vec<Rect>,
+    ) {
         let Self {
             transform,
             axis_formatters,
+            axis_units,
+            avoid_label_overlap,
+            grid_renderer,
+            grid_color,
+            grid_opacity,
+            gridlines_at_labels_only,
+            grid_base,
+            grid_spacers,
+            min_tick_spacing,
+            left_margin,
             ..
         } = self;
 
@@ -945,24 +3335,40 @@ impl PreparedPlot {
 
         let font_id = TextStyle::Body.resolve(ui.style());
 
-        let base: i64 = 10;
-        let basef = base as f64;
-
-        let min_line_spacing_in_points = 6.0; // TODO: large enough for a wide label
-        let step_size = transform.dvalue_dpos()[axis] * min_line_spacing_in_points;
-        let step_size = basef.powi(step_size.abs().log(basef).ceil() as i32);
-
-        let step_size_in_points = (transform.dpos_dvalue()[axis] * step_size).abs() as f32;
+        let min_line_spacing_in_points = min_tick_spacing[axis] as f64;
+        let marks = if let Some(spacer) = &grid_spacers[axis] {
+            let input = GridInput {
+                bounds: (bounds.min[axis], bounds.max[axis]),
+                base_step_size: transform.dvalue_dpos()[axis].abs() * min_line_spacing_in_points,
+            };
+            let mut marks = spacer(input);
+            marks.retain(|mark| (bounds.min[axis]..=bounds.max[axis]).contains(&mark.value));
+            marks
+        } else {
+            Self::grid_marks(transform, axis, grid_base[axis], min_line_spacing_in_points)
+        };
 
         // Where on the cross-dimension to show the label values
         let value_cross = 0.0_f64.clamp(bounds.min[1 - axis], bounds.max[1 - axis]);
 
-        for i in 0.. {
-            let value_main = step_size * (bounds.min[axis] / step_size + i as f64).floor();
-            if value_main > bounds.max[axis] {
-                break;
-            }
+        if let Some(grid_renderer) = grid_renderer {
+            let context = GridContext {
+                axis: if axis == 0 { Axis::X } else { Axis::Y },
+                marks: &marks,
+                transform,
+                frame: *transform.frame(),
+            };
+            grid_renderer(&context, shapes);
+        }
 
+        // Adjacent marks can be formatted to the same text if the precision is too low for the
+        // step size. Only the first of them gets a label.
+        let mut last_label: Option<String> = None;
+        // The position and text of each label and whether it is drawn in the margin.
+        let mut labels: Vec<(Pos2, Arc<Galley>, bool)> = Vec::new();
+
+        for mark in &marks {
+            let value_main = mark.value;
             let value = if axis == 0 {
                 Value::new(value_main, value_cross)
             } else {
@@ -970,46 +3376,63 @@ impl PreparedPlot {
             };
             let pos_in_gui = transform.position_from_value(&value);
 
-            let n = (value_main / step_size).round() as i64;
-            let spacing_in_points = if n % (base * base) == 0 {
-                step_size_in_points * (basef * basef) as f32 // think line (multiple of 100)
-            } else if n % base == 0 {
-                step_size_in_points * basef as f32 // medium line (multiple of 10)
-            } else {
-                step_size_in_points // thin line
-            };
-
-            let line_alpha = remap_clamp(
-                spacing_in_points,
-                (min_line_spacing_in_points as f32)..=300.0,
-                0.0..=0.15,
-            );
+            let spacing_in_points = (transform.dpos_dvalue()[axis] * mark.step_size).abs() as f32;
+            let text_alpha = remap_clamp(spacing_in_points, 40.0..=150.0, 0.0..=0.4);
 
-            if line_alpha > 0.0 {
-                let line_color = color_from_alpha(ui, line_alpha);
+            if grid_renderer.is_none() && (!*gridlines_at_labels_only || text_alpha > 0.0) {
+                let line_alpha = remap_clamp(
+                    spacing_in_points,
+                    (min_line_spacing_in_points as f32)..=300.0,
+                    0.0..=*grid_opacity,
+                );
+
+                if line_alpha > 0.0 {
+                    let line_color = match grid_color {
+                        Some(color) => Rgba::from(*color).multiply(line_alpha).into(),
+                        None => color_from_alpha(ui, line_alpha),
+                    };
 
-                let mut p0 = pos_in_gui;
-                let mut p1 = pos_in_gui;
-                p0[1 - axis] = transform.frame().min[1 - axis];
-                p1[1 - axis] = transform.frame().max[1 - axis];
-                shapes.push(Shape::line_segment([p0, p1], Stroke::new(1.0, line_color)));
+                    let mut p0 = pos_in_gui;
+                    let mut p1 = pos_in_gui;
+                    p0[1 - axis] = transform.frame().min[1 - axis];
+                    p1[1 - axis] = transform.frame().max[1 - axis];
+                    shapes.push(Shape::line_segment([p0, p1], Stroke::new(1.0, line_color)));
+                }
             }
 
-            let text_alpha = remap_clamp(spacing_in_points, 40.0..=150.0, 0.0..=0.4);
-
             if text_alpha > 0.0 {
                 let color = color_from_alpha(ui, text_alpha);
 
-                let text: String = if let Some(formatter) = axis_formatters[axis].as_deref() {
+                let mut text: String = if let Some(formatter) = axis_formatters[axis].as_deref() {
                     formatter(value_main)
+                } else if transform.scales()[axis] == AxisScale::Log {
+                    format_power_of_ten(value_main)
                 } else {
                     emath::round_to_decimals(value_main, 5).to_string() // hack
                 };
+                if !text.is_empty() && !axis_units[axis].is_empty() {
+                    text = format!("{} {}", text, axis_units[axis]);
+                }
 
                 // Custom formatters can return empty string to signal "no label at this resolution"
-                if !text.is_empty() {
+                if !text.is_empty() && last_label.as_ref() != Some(&text) {
+                    last_label = Some(text.clone());
+
                     let galley = ui.painter().layout_no_wrap(text, font_id.clone(), color);
 
+                    if axis == 1 && *left_margin > 0.0 {
+                        // Right-align the label in the margin, next to its mark.
+                        let frame = transform.frame();
+                        let text_pos = pos2(
+                            frame.left() - galley.size().x - 2.0,
+                            (pos_in_gui.y - galley.size().y / 2.0)
+                                .at_most(frame.bottom() - galley.size().y)
+                                .at_least(frame.top()),
+                        );
+                        labels.push((text_pos, galley, true));
+                        continue;
+                    }
+
                     let mut text_pos = pos_in_gui + vec2(1.0, -galley.size().y);
 
                     // Make sure we see the labels, even if the axis is off-screen:
@@ -1017,62 +3440,1152 @@ impl PreparedPlot {
                         .at_most(transform.frame().max[1 - axis] - galley.size()[1 - axis] - 2.0)
                         .at_least(transform.frame().min[1 - axis] + 1.0);
 
-                    shapes.push(Shape::galley(text_pos, galley));
+                    labels.push((text_pos, galley, false));
                 }
             }
         }
 
-        fn color_from_alpha(ui: &Ui, alpha: f32) -> Color32 {
-            if ui.visuals().dark_mode {
-                Rgba::from_white_alpha(alpha).into()
+        if *avoid_label_overlap {
+            drop_overlapping_labels(&mut labels);
+        }
+        for (pos, galley, in_margin) in labels {
+            label_rects.push(Rect::from_min_size(pos, galley.size()));
+            if in_margin {
+                margin_shapes.push(Shape::galley(pos, galley));
             } else {
-                Rgba::from_black_alpha((4.0 * alpha).at_most(1.0)).into()
+                shapes.push(Shape::galley(pos, galley));
             }
         }
     }
 
+    /// Draws the labels of the secondary y axis, if any, along the right edge of the frame.
+    fn paint_secondary_axis(&self, ui: &Ui, shapes: &mut Vec<Shape>, label_rects: &mut Vec<Rect>) {
+        let transform = match &self.secondary_range {
+            Some(secondary) => secondary.transform(&self.transform),
+            None => return,
+        };
+        let frame = transform.frame();
+        let font_id = TextStyle::Body.resolve(ui.style());
+
+        let marks = Self::grid_marks(
+            &transform,
+            1,
+            self.grid_base[1],
+            self.min_tick_spacing[1] as f64,
+        );
+        for mark in marks {
+            let spacing_in_points = (transform.dpos_dvalue()[1] * mark.step_size).abs() as f32;
+            let text_alpha = remap_clamp(spacing_in_points, 40.0..=150.0, 0.0..=0.4);
+            if text_alpha <= 0.0 {
+                continue;
+            }
+            let text = if let Some(formatter) = self.secondary_axis_formatter.as_deref() {
+                formatter(mark.value)
+            } else if transform.scales()[1] == AxisScale::Log {
+                format_power_of_ten(mark.value)
+            } else {
+                emath::round_to_decimals(mark.value, 5).to_string()
+            };
+            if text.is_empty() {
+                continue;
+            }
+
+            let galley = ui.painter().layout_no_wrap(
+                text,
+                font_id.clone(),
+                color_from_alpha(ui, text_alpha),
+            );
+            let y = transform
+                .position_from_value(&Value::new(0.0, mark.value))
+                .y;
+            let pos = pos2(
+                frame.right() - galley.size().x - 1.0,
+                (y - galley.size().y)
+                    .at_most(frame.bottom() - galley.size().y - 2.0)
+                    .at_least(frame.top() + 1.0),
+            );
+            label_rects.push(Rect::from_min_size(pos, galley.size()));
+            shapes.push(Shape::galley(pos, galley));
+        }
+    }
+
+    /// Computes the grid marks for one axis: one mark per multiple of the smallest power of `base`
+    /// that is at least `min_line_spacing_in_points` wide, with multiples of `base` and `base²`
+    /// times the step size marked as more prominent.
+    fn grid_marks(
+        transform: &ScreenTransform,
+        axis: usize,
+        base: i64,
+        min_line_spacing_in_points: f64,
+    ) -> Vec<GridMark> {
+        match transform.scales()[axis] {
+            AxisScale::Linear => {}
+            AxisScale::SymLog { linthresh } => {
+                return Self::symlog_grid_marks(
+                    transform,
+                    axis,
+                    linthresh,
+                    min_line_spacing_in_points,
+                );
+            }
+            AxisScale::Log => {
+                return Self::log_grid_marks(transform, axis, min_line_spacing_in_points);
+            }
+        }
+
+        let bounds = transform.bounds();
+
+        let base = base.max(2);
+        let basef = base as f64;
+
+        let step_size = transform.dvalue_dpos()[axis] * min_line_spacing_in_points;
+        let step_size = basef.powi(step_size.abs().log(basef).ceil() as i32);
+
+        let mut marks = vec![];
+        for i in 0.. {
+            let value = step_size * (bounds.min[axis] / step_size + i as f64).floor();
+            if value > bounds.max[axis] {
+                break;
+            }
+
+            let n = (value / step_size).round() as i64;
+            let mark_step_size = if n % (base * base) == 0 {
+                step_size * basef * basef // thick line (multiple of base²)
+            } else if n % base == 0 {
+                step_size * basef // medium line (multiple of base)
+            } else {
+                step_size // thin line
+            };
+
+            marks.push(GridMark {
+                value,
+                step_size: mark_step_size,
+            });
+        }
+        marks
+    }
+
+    /// Grid marks for a [`AxisScale::SymLog`] axis: at zero and at `±linthresh` times powers of
+    /// ten, as well as decimal marks within the linear range if there is enough space for them.
+    fn symlog_grid_marks(
+        transform: &ScreenTransform,
+        axis: usize,
+        linthresh: f64,
+        min_line_spacing_in_points: f64,
+    ) -> Vec<GridMark> {
+        let scale = AxisScale::SymLog { linthresh };
+        let bounds = transform.bounds();
+        let (min, max) = (
+            scale.forward(bounds.min[axis]),
+            scale.forward(bounds.max[axis]),
+        );
+
+        // The step size in the space mapped by the scale, where each decade has size one.
+        let step_size = transform.dvalue_dpos()[axis].abs() * min_line_spacing_in_points;
+        let step_size = 10.0_f64.powi(step_size.log10().ceil() as i32);
+
+        let mut marks = vec![];
+
+        // Decades, thinned out if they are too dense.
+        let decade_step = step_size.max(1.0);
+        let first = (min / decade_step).ceil() as i64;
+        let last = (max / decade_step).floor() as i64;
+        for n in first..=last {
+            let prominence = if n % 10 == 0 { 10.0 } else { 1.0 };
+            marks.push(GridMark {
+                value: scale.inverse(n as f64 * decade_step),
+                step_size: decade_step * prominence,
+            });
+        }
+
+        // Finer marks within the linear range.
+        if step_size < 1.0 {
+            let first = (min.max(-1.0) / step_size).ceil() as i64;
+            let last = (max.min(1.0) / step_size).floor() as i64;
+            let decade = (1.0 / step_size).round() as i64;
+            for n in first..=last {
+                if n % decade != 0 {
+                    let prominence = if n % 10 == 0 { 10.0 } else { 1.0 };
+                    marks.push(GridMark {
+                        value: scale.inverse(n as f64 * step_size),
+                        step_size: step_size * prominence,
+                    });
+                }
+            }
+            marks.sort_by_key(|mark| mark.value.ord());
+        }
+
+        marks
+    }
+
+    /// Grid marks for a [`AxisScale::Log`] axis: at powers of ten, and at two and five times
+    /// them if there is enough space for them.
+    fn log_grid_marks(
+        transform: &ScreenTransform,
+        axis: usize,
+        min_line_spacing_in_points: f64,
+    ) -> Vec<GridMark> {
+        let scale = AxisScale::Log;
+        let bounds = transform.bounds();
+        let (min, max) = (
+            scale.forward(bounds.min[axis]),
+            scale.forward(bounds.max[axis]),
+        );
+
+        // The step size in decades.
+        let step_size = transform.dvalue_dpos()[axis].abs() * min_line_spacing_in_points;
+        // The smallest distance between marks at one, two and five times a power of ten.
+        let subdivision_step = 2.0_f64.log10();
+
+        let mut marks = vec![];
+        let decade_step = 10.0_f64.powi(step_size.log10().ceil() as i32).max(1.0);
+        let first = (min / decade_step).floor() as i64;
+        let last = (max / decade_step).ceil() as i64;
+        for n in first..=last {
+            let decade = n as f64 * decade_step;
+            if (min..=max).contains(&decade) {
+                let prominence = if n % 10 == 0 { 10.0 } else { 1.0 };
+                marks.push(GridMark {
+                    value: scale.inverse(decade),
+                    step_size: decade_step * prominence,
+                });
+            }
+            if decade_step == 1.0 && step_size <= subdivision_step {
+                for factor in [2.0, 5.0] {
+                    let value = scale.inverse(decade) * factor;
+                    if (min..=max).contains(&scale.forward(value)) {
+                        marks.push(GridMark {
+                            value,
+                            step_size: subdivision_step,
+                        });
+                    }
+                }
+            }
+        }
+        marks.sort_by_key(|mark| mark.value.ord());
+
+        if marks.len() < 2 {
+            // Zoomed in so far that the scale is almost linear: use decimal marks.
+            let (min, max) = (bounds.min[axis], bounds.max[axis]);
+            let size = transform.frame().size()[axis] as f64;
+            let step = (max - min) / size * min_line_spacing_in_points;
+            let step = 10.0_f64.powi(step.log10().ceil() as i32);
+            let first = (min / step).ceil() as i64;
+            let last = (max / step).floor() as i64;
+            marks = (first..=last)
+                .map(|n| {
+                    let value = n as f64 * step;
+                    let prominence = if n % 10 == 0 { 10.0 } else { 1.0 };
+                    GridMark {
+                        value,
+                        step_size: (scale.forward(value + step) - scale.forward(value))
+                            * prominence,
+                    }
+                })
+                .collect();
+        }
+        marks
+    }
+
     fn hover(&self, ui: &Ui, pointer: Pos2, shapes: &mut Vec<Shape>) {
         let Self {
             transform,
             hover_line,
             show_hover_label,
+            stacked_hover,
+            info_box,
             hover_formatter,
+            highlight_style,
             items,
             ..
         } = self;
 
-        if matches!(hover_line, HoverLine::None) && !show_hover_label {
+        if matches!(hover_line, HoverLine::None) && !show_hover_label && info_box.is_none() {
             return;
         }
 
-        let interact_radius_sq: f32 = (16.0f32).powi(2);
-
-        let candidates = items.iter().filter_map(|item| {
-            let item = &**item;
-            let closest = item.find_closest(pointer, transform);
-
-            Some(item).zip(closest)
-        });
-
-        let closest = candidates
-            .min_by_key(|(_, elem)| elem.dist_sq.ord())
-            .filter(|(_, elem)| elem.dist_sq <= interact_radius_sq);
+        let interact_radius = INTERACT_RADIUS;
+        let closest = closest_item(items, pointer, transform, self.secondary_range.as_ref());
 
         let plot = items::PlotConfig {
             ui,
             transform,
+            pointer,
             hover_config: HoverConfig {
                 hover_line: *hover_line,
                 show_hover_label: *show_hover_label,
             },
             hover_formatter,
+            highlight_style,
         };
 
+        if let Some(corner) = info_box {
+            let item_value = closest.as_ref().and_then(|(item, elem)| {
+                let value = match item.values() {
+                    Some(values) => values[elem.index],
+                    None => item.value_at_x(pointer.x, interact_radius, transform)?,
+                };
+                Some((item.name(), value))
+            });
+            let text = if let Some((name, value)) = item_value {
+                hover_formatter(&plot.hover_config, name, &value)
+            } else {
+                let value = transform.value_from_position(pointer);
+                hover_formatter(&plot.hover_config, &Self::area_names(items, &value), &value)
+            };
+            Self::info_box(ui, *corner, text, transform.frame(), shapes);
+        }
+        if matches!(hover_line, HoverLine::None) && !show_hover_label {
+            return;
+        }
+
+        if *stacked_hover {
+            let entries: Vec<_> = items
+                .iter()
+                .filter_map(|item| {
+                    let value = item.value_at_x(pointer.x, interact_radius, transform)?;
+                    Some((item.name(), value))
+                })
+                .collect();
+            if !entries.is_empty() {
+                items::stacked_rulers(pointer, &entries, &plot, shapes);
+                return;
+            }
+        }
+
         if let Some((item, elem)) = closest {
+            let transform = item_transform(item, transform, self.secondary_range.as_ref());
+            let plot = items::PlotConfig {
+                transform: &transform,
+                ..plot
+            };
             item.on_hover(elem, shapes, &plot);
         } else {
             let value = transform.value_from_position(pointer);
-            items::rulers_at_value(pointer, value, "", &plot, shapes);
+            let name = Self::area_names(items, &value);
+            items::rulers_at_value(pointer, value, &name, &plot, shapes);
         }
     }
+
+    /// The names of the items marking an area that contains the value, e.g. spans.
+    fn area_names(items: &[Box<dyn PlotItem>], value: &Value) -> String {
+        let names: Vec<&str> = items
+            .iter()
+            .filter(|item| !item.name().is_empty() && item.contains_value(value))
+            .map(|item| item.name())
+            .collect();
+        names.join(", ")
+    }
+
+    /// Draws the box of [`Plot::info_box`] with the given text in a corner of the frame.
+    fn info_box(ui: &Ui, corner: Corner, text: String, frame: &Rect, shapes: &mut Vec<Shape>) {
+        let visuals = ui.visuals();
+        let font_id = TextStyle::Body.resolve(ui.style());
+        let galley = ui
+            .painter()
+            .layout_no_wrap(text, font_id, visuals.text_color());
+        let padding = vec2(8.0, 4.0);
+        let size = galley.size() + 2.0 * padding;
+        let frame = frame.shrink(8.0);
+        let min = match corner {
+            Corner::LeftTop => frame.left_top(),
+            Corner::RightTop => pos2(frame.right() - size.x, frame.top()),
+            Corner::LeftBottom => pos2(frame.left(), frame.bottom() - size.y),
+            Corner::RightBottom => frame.right_bottom() - size,
+        };
+        let rect = Rect::from_min_size(min, size);
+        let corner_radius = visuals.window_corner_radius;
+        shapes.push(Shape::rect_filled(
+            rect,
+            corner_radius,
+            visuals.extreme_bg_color.linear_multiply(0.75),
+        ));
+        shapes.push(Shape::rect_stroke(
+            rect,
+            corner_radius,
+            visuals.window_stroke(),
+        ));
+        shapes.push(Shape::galley(rect.min + padding, galley));
+    }
+}
+
+#[cfg(test)]
+fn auto_bounds_of(plot: Plot, values: Vec<Value>) -> PlotBounds {
+    let ctx = Context::default();
+    let mut bounds = None;
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            let response = plot.show(ui, |plot_ui| {
+                plot_ui.line(Line::new(Values::from_values(values)));
+            });
+            bounds = Some(*response.transform.bounds());
+        });
+    });
+    bounds.unwrap()
+}
+
+#[test]
+fn test_margin_fraction() {
+    let values = vec![Value::new(-2.0, 1.0), Value::new(8.0, 3.0)];
+    let bounds = auto_bounds_of(
+        Plot::new("plot").margin_fraction(Vec2::ZERO),
+        values.clone(),
+    );
+    assert_eq!(bounds.min(), [-2.0, 1.0]);
+    assert_eq!(bounds.max(), [8.0, 3.0]);
+
+    // By default, there is some room around the content.
+    let bounds = auto_bounds_of(Plot::new("plot"), values);
+    assert!(bounds.min()[0] < -2.0 && bounds.min()[1] < 1.0);
+    assert!(bounds.max()[0] > 8.0 && bounds.max()[1] > 3.0);
+}
+
+#[test]
+fn test_constant_y_bounds() {
+    let values = (0..10).map(|i| Value::new(i as f64, 5.0)).collect();
+    let bounds = auto_bounds_of(Plot::new("plot").margin_fraction(Vec2::ZERO), values);
+    assert_eq!(bounds.min(), [0.0, 4.5]);
+    assert_eq!(bounds.max(), [9.0, 5.5]);
+
+    let values = (0..10).map(|i| Value::new(i as f64, 0.0)).collect();
+    let plot = Plot::new("plot")
+        .margin_fraction(Vec2::ZERO)
+        .degenerate_bounds_margin(2.0);
+    let bounds = auto_bounds_of(plot, values);
+    assert_eq!(bounds.min(), [0.0, -2.0]);
+    assert_eq!(bounds.max(), [9.0, 2.0]);
+}
+
+#[test]
+fn test_constant_x_bounds() {
+    let values = (0..10).map(|i| Value::new(-20.0, i as f64)).collect();
+    let bounds = auto_bounds_of(Plot::new("plot").margin_fraction(Vec2::ZERO), values);
+    assert_eq!(bounds.min(), [-22.0, 0.0]);
+    assert_eq!(bounds.max(), [-18.0, 9.0]);
+}
+
+#[test]
+fn test_bounds_range() {
+    let values = vec![Value::new(-2.0, 0.1), Value::new(8.0, 0.9)];
+    let plot = Plot::new("plot")
+        .margin_fraction(Vec2::splat(0.5))
+        .bounds_range(0.0..=f64::INFINITY, 0.0..=1.0);
+    let bounds = auto_bounds_of(plot, values);
+    assert_eq!(bounds.min(), [0.0, 0.0]);
+    assert_eq!(bounds.max(), [13.0, 1.0]);
+}
+
+#[test]
+fn test_zoom_bounds() {
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 100.0));
+    let bounds = PlotBounds::from_min_max([0.0, 1.0], [10.0, 1000.0]);
+    let transform = ScreenTransform::new(frame, bounds, false, false)
+        .with_scales([AxisScale::Linear, AxisScale::Log]);
+    let (min_span, max_span) = (vec2(4.0, 10.0), vec2(20.0, 1e4));
+
+    // Within the limits the zoom is applied unchanged.
+    let factor = transform.limit_zoom(vec2(1.25, 1.0), frame.center(), min_span, max_span);
+    assert_eq!(factor, vec2(1.25, 1.0));
+
+    // Otherwise it is applied up to the limit.
+    let mut zoomed = transform.clone();
+    let factor = transform.limit_zoom(vec2(10.0, 0.01), frame.center(), min_span, max_span);
+    zoomed.zoom(factor, frame.center());
+    let (min, max) = (zoomed.bounds().min(), zoomed.bounds().max());
+    assert!((max[0] - min[0] - 4.0).abs() < 1e-4);
+    assert!((max[1] - min[1] - 1e4).abs() < 1.0);
+
+    // Bounds that are set at once, e.g. by a boxed zoom, are limited around their center.
+    let boxed = PlotBounds::from_min_max([1.0, 10.0], [2.0, 1e6]);
+    let limited = transform.limit_bounds(boxed, min_span, max_span);
+    assert_eq!([limited.min()[0], limited.max()[0]], [-0.5, 3.5]);
+    // On the logarithmic axis, the center is kept in log space.
+    let (min, max) = (limited.min()[1], limited.max()[1]);
+    assert!((max - min - 1e4).abs() < 1.0);
+    assert!((min * max / 1e7 - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_scroll_beyond_data() {
+    let content = PlotBounds::from_min_max([0.0, 0.0], [10.0, 1.0]);
+
+    // Panning past the right edge moves the view back without changing its size.
+    let mut bounds = PlotBounds::from_min_max([8.0, 5.0], [12.0, 7.0]);
+    bounds.shift_into(&content, [true, false]);
+    assert_eq!(bounds.min(), [6.0, 5.0]);
+    assert_eq!(bounds.max(), [10.0, 7.0]);
+
+    // Zooming out is limited to the content.
+    let mut bounds = PlotBounds::from_min_max([-5.0, 0.0], [20.0, 1.0]);
+    bounds.shift_into(&content, [true, true]);
+    assert_eq!(bounds, content);
+}
+
+#[test]
+fn test_grid_base() {
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(600.0, 100.0));
+    let bounds = PlotBounds::from_min_max([0.0, 0.0], [3600.0, 1.0]);
+    let transform = ScreenTransform::new(frame, bounds, false, false);
+    let marks = PreparedPlot::grid_marks(&transform, 0, 60, 6.0);
+    let step_at = |value: f64| marks.iter().find(|m| m.value == value).unwrap().step_size;
+    assert_eq!(marks.len(), 61);
+    assert_eq!(step_at(60.0), 60.0);
+    assert_eq!(step_at(3600.0), 3600.0);
+
+    let plot = Plot::new("plot").grid_base([i64::MAX, 0]);
+    assert_eq!(plot.grid_base, [1_000_000, 2]);
+    let ctx = Context::default();
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            // Used to overflow when squaring the base.
+            plot.show(ui, |plot_ui| {
+                plot_ui.line(Line::new(Values::from_values(vec![
+                    Value::new(0.0, 0.0),
+                    Value::new(1e9, 1.0),
+                ])));
+            });
+        });
+    });
+}
+
+#[test]
+fn test_symlog_axis() {
+    let scale = AxisScale::SymLog { linthresh: 2.0 };
+    for value in [-5000.0, -2.0, -0.5, 0.0, 1.0, 2.0, 30.0, 1e9] {
+        let round_trip = scale.inverse(scale.forward(value));
+        assert!((round_trip - value).abs() <= 1e-9 * value.abs().max(1.0));
+    }
+
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 100.0));
+    let bounds = PlotBounds::from_min_max([-2000.0, -1.0], [2000.0, 1.0]);
+    let transform =
+        ScreenTransform::new(frame, bounds, false, false).with_scales([scale, AxisScale::Linear]);
+    // Each decade beyond the linear range takes up as much space as the linear range.
+    let x = |value: f64| transform.position_from_value(&Value::new(value, 0.0)).x;
+    assert!((x(0.0) - 50.0).abs() < 1e-3);
+    assert!(((x(2.0) - x(0.0)) - (x(20.0) - x(2.0))).abs() < 1e-3);
+
+    let marks: Vec<f64> = PreparedPlot::grid_marks(&transform, 0, 10, 6.0)
+        .iter()
+        .map(|mark| mark.value)
+        .collect();
+    for value in [-2000.0, -20.0, -2.0, 0.0, 2.0, 200.0, 2000.0] {
+        assert!(marks
+            .iter()
+            .any(|mark| (mark - value).abs() < 1e-9 * value.abs().max(1.0)));
+    }
+}
+
+#[test]
+fn test_declutter() {
+    let rect = |x: f32| Some(Rect::from_min_size(pos2(x, 0.0), vec2(10.0, 5.0)));
+    // The second label overlaps the first one, the third only the hidden second one.
+    let hidden = declutter(vec![rect(0.0), rect(5.0), None, rect(12.0)].into_iter());
+    assert_eq!(hidden, vec![false, true, false, false]);
+}
+
+#[test]
+fn test_drop_overlapping_labels() {
+    let ctx = Context::default();
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        let galley = ctx
+            .fonts()
+            .layout_no_wrap("100".into(), FontId::default(), Color32::WHITE);
+        let width = galley.size().x;
+        let labels = |spacing: f32| -> Vec<_> {
+            (0..7)
+                .map(|i| (pos2(i as f32 * spacing, 0.0), galley.clone(), false))
+                .collect()
+        };
+
+        // Labels that are far enough apart are all kept.
+        let mut apart = labels(2.0 * width);
+        drop_overlapping_labels(&mut apart);
+        assert_eq!(apart.len(), 7);
+
+        // Overlapping neighbors are thinned out to every other label, starting with the first.
+        let mut overlapping = labels(0.75 * width);
+        drop_overlapping_labels(&mut overlapping);
+        let xs: Vec<f32> = overlapping.iter().map(|(pos, _, _)| pos.x).collect();
+        assert_eq!(xs, vec![0.0, 1.5 * width, 3.0 * width, 4.5 * width]);
+    });
+}
+
+#[test]
+fn test_closest_item_user_id() {
+    let values = Values::from_values(vec![Value::new(0.0, 0.0), Value::new(1.0, 1.0)]);
+    let items: Vec<Box<dyn PlotItem>> = vec![Box::new(Points::new(values).ids([42, 7]))];
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 100.0));
+    let bounds = PlotBounds::from_min_max([0.0, 0.0], [1.0, 1.0]);
+    let transform = ScreenTransform::new(frame, bounds, false, false);
+
+    let pointer = transform.position_from_value(&Value::new(1.0, 1.0));
+    let (item, elem) = closest_item(&items, pointer, &transform, None).unwrap();
+    assert_eq!((elem.index, elem.user_id), (1, 7));
+    assert_eq!(item.element_value(elem.index), Some(Value::new(1.0, 1.0)));
+
+    let chart = BarChart::new(vec![Bar::new(0.0, 0.5), Bar::new(1.0, 0.8).horizontal()]);
+    assert_eq!(chart.element_value(1), Some(Value::new(0.8, 1.0)));
+}
+
+#[test]
+fn test_clip_data_hover() {
+    let values = Values::from_values(vec![Value::new(0.0, 0.0), Value::new(1.0, 1.0)]);
+    let clip = PlotBounds::from_min_max([0.5, 0.5], [1.5, 1.5]);
+    let items: Vec<Box<dyn PlotItem>> = vec![Box::new(Points::new(values).clip_data(Some(clip)))];
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 100.0));
+    let bounds = PlotBounds::from_min_max([0.0, 0.0], [1.0, 1.0]);
+    let transform = ScreenTransform::new(frame, bounds, false, false);
+
+    // The masked out point is not hovered.
+    let pointer = transform.position_from_value(&Value::new(0.0, 0.0));
+    assert!(closest_item(&items, pointer, &transform, None).is_none());
+    let pointer = transform.position_from_value(&Value::new(1.0, 1.0));
+    let (_, elem) = closest_item(&items, pointer, &transform, None).unwrap();
+    assert_eq!(elem.index, 1);
+}
+
+#[test]
+fn test_log_axis() {
+    let values = vec![
+        Value::new(0.0, 0.0),
+        Value::new(1.0, 1e-9),
+        Value::new(2.0, 1e3),
+    ];
+    let bounds = auto_bounds_of(Plot::new("plot").log_axis([false, true]), values);
+    // The zero is left out, and the margins are added in decades.
+    assert!(bounds.min()[1] > 0.0 && bounds.min()[1] < 1e-9);
+    assert!(bounds.max()[1] > 1e3 && bounds.max()[1] < 1e4);
+
+    let frame = Rect::from_min_size(Pos2::ZERO, vec2(100.0, 1000.0));
+    let bounds = PlotBounds::from_min_max([0.0, 1.0], [1.0, 1000.0]);
+    let transform = ScreenTransform::new(frame, bounds, false, false)
+        .with_scales([AxisScale::Linear, AxisScale::Log]);
+    let marks: Vec<f64> = PreparedPlot::grid_marks(&transform, 1, 10, 6.0)
+        .iter()
+        .map(|mark| mark.value)
+        .collect();
+    let expected = [1.0, 2.0, 5.0, 10.0, 20.0, 50.0, 100.0, 200.0, 500.0, 1000.0];
+    assert_eq!(marks.len(), expected.len());
+    for (mark, value) in marks.iter().zip(expected) {
+        assert!((mark - value).abs() < 1e-9 * value);
+    }
+
+    assert_eq!(format_power_of_ten(1e-9), "10^-9");
+    assert_eq!(format_power_of_ten(1000.0), "10^3");
+    assert_eq!(format_power_of_ten(20.0), "20");
+}
+
+#[test]
+fn test_secondary_axis() {
+    let ctx = Context::default();
+    let mut result = None;
+    // The transforms in `PlotUi` are those of the previous frame.
+    for _ in 0..2 {
+        let _ = ctx.run(RawInput::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let response = Plot::new("plot")
+                    .margin_fraction(Vec2::ZERO)
+                    .show(ui, |plot_ui| {
+                        let primary = vec![Value::new(0.0, 0.0), Value::new(1.0, 1.0)];
+                        let secondary = vec![Value::new(-1.0, 100.0), Value::new(2.0, 300.0)];
+                        plot_ui.line(Line::new(Values::from_values(primary)));
+                        plot_ui.line(
+                            Line::new(Values::from_values(secondary)).on_secondary_axis(true),
+                        );
+                        let top = plot_ui.screen_from_plot(Value::new(0.0, 1.0));
+                        plot_ui.plot_from_screen_on(YAxis::Secondary, top)
+                    });
+                result = Some((*response.transform.bounds(), response.inner));
+            });
+        });
+    }
+    let (bounds, top) = result.unwrap();
+    // The secondary items only extend the shared x axis.
+    assert_eq!(bounds.min(), [-1.0, 0.0]);
+    assert_eq!(bounds.max(), [2.0, 1.0]);
+    // The secondary axis is fitted to its own items.
+    assert!((top.y - 300.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_set_plot_bounds() {
+    let ctx = Context::default();
+    let window = PlotBounds::from_min_max([10.0, -1.0], [20.0, 1.0]);
+    let mut shown = Vec::new();
+    for frame in 0..2 {
+        let _ = ctx.run(RawInput::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let response = Plot::new("plot").show(ui, |plot_ui| {
+                    let values = vec![Value::new(0.0, 0.0), Value::new(1.0, 5.0)];
+                    plot_ui.line(Line::new(Values::from_values(values)));
+                    if frame == 0 {
+                        plot_ui.set_plot_bounds(window);
+                    }
+                });
+                shown.push((*response.transform.bounds(), response.bounds_source));
+            });
+        });
+    }
+    // The bounds stay as they were set, instead of fitting the content.
+    assert_eq!(shown[0], (window, BoundsSource::Explicit));
+    assert_eq!(shown[1], (window, BoundsSource::Memory));
+}
+
+#[test]
+fn test_cache_shapes() {
+    let ctx = Context::default();
+    let mut cached = Vec::new();
+    for frame in 0..3 {
+        let _ = ctx.run(RawInput::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                Plot::new("plot").cache_shapes(true).show(ui, |plot_ui| {
+                    let values = (0..100).map(|i| Value::new(i as f64, (i % 10) as f64));
+                    let values = Values::from_values_iter(values);
+                    plot_ui.line(Line::new(values).content_hash("data"));
+                    plot_ui.line(Line::new(Values::from_ys_f32(&[1.0, 2.0])));
+                    if frame == 2 {
+                        plot_ui.zoom(2.0);
+                    }
+                });
+                let memory = PlotMemory::load(ctx, ui.make_persistent_id(Id::new("plot"))).unwrap();
+                cached.push(memory.shape_cache.0.values().next().unwrap().shapes.clone());
+                assert_eq!(memory.shape_cache.0.len(), 1);
+            });
+        });
+    }
+    // The shapes are reused until the plot is zoomed.
+    assert!(Arc::ptr_eq(&cached[0], &cached[1]));
+    assert!(!Arc::ptr_eq(&cached[1], &cached[2]));
+
+    // Items with the same hash still get their own shapes.
+    let ctx = Context::default();
+    for _ in 0..2 {
+        let _ = ctx.run(RawInput::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let response = Plot::new("plot").cache_shapes(true).show(ui, |plot_ui| {
+                    for y in [0.0, 1.0] {
+                        let values =
+                            Values::from_values(vec![Value::new(0.0, y), Value::new(1.0, y)]);
+                        plot_ui.line(Line::new(values).content_hash("version 1"));
+                    }
+                });
+                let memory = PlotMemory::load(ctx, ui.make_persistent_id(Id::new("plot"))).unwrap();
+                assert_eq!(memory.shape_cache.0.len(), 2);
+                let line_y = |index: usize| {
+                    let key = (index, epaint::util::hash("version 1"));
+                    match &memory.shape_cache.0[&key].shapes[0] {
+                        Shape::Path(path) => path.points[0].y,
+                        _ => panic!("expected a path"),
+                    }
+                };
+                let transform = &response.transform;
+                assert_eq!(
+                    line_y(0),
+                    transform.position_from_value(&Value::new(0.0, 0.0)).y
+                );
+                assert_eq!(
+                    line_y(1),
+                    transform.position_from_value(&Value::new(0.0, 1.0)).y
+                );
+            });
+        });
+    }
+}
+
+#[test]
+fn test_warn_on_empty_items() {
+    let ctx = Context::default();
+    let mut plot_id = None;
+    let mut warned_after_frame = |line_values: Vec<Value>| {
+        let _ = ctx.run(RawInput::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                plot_id = Some(ui.make_persistent_id(Id::new("plot")));
+                Plot::new("plot")
+                    .warn_on_empty_items(true)
+                    .show(ui, |plot_ui| {
+                        plot_ui.line(Line::new(Values::from_values(line_values)).name("line"));
+                        plot_ui.line(
+                            Line::new(Values::from_values(vec![]))
+                                .name("hidden")
+                                .show_in_legend(false),
+                        );
+                        plot_ui.path_text(
+                            PathText::new(Values::from_values(vec![]), "text").name("path"),
+                        );
+                        plot_ui.points(Points::new(Values::from_values(vec![])));
+                    });
+            });
+        });
+        let memory = PlotMemory::load(&ctx, plot_id.unwrap()).unwrap();
+        let mut warned: Vec<_> = memory.warned_empty_items.into_iter().collect();
+        warned.sort();
+        warned
+    };
+    assert_eq!(warned_after_frame(vec![]), ["hidden", "line", "path"]);
+    // Still empty items are not logged again, and those with values again are forgotten.
+    assert_eq!(
+        warned_after_frame(vec![Value::new(0.0, 1.0)]),
+        ["hidden", "path"]
+    );
+}
+
+#[test]
+fn test_legend_outside() {
+    let ctx = Context::default();
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            for position in [LegendPosition::RightOutside, LegendPosition::BottomOutside] {
+                let response = Plot::new(format!("{:?}", position))
+                    .height(100.0)
+                    .legend(Legend::default().position(position))
+                    .show(ui, |plot_ui| {
+                        for i in 0..20 {
+                            let values = Values::from_ys_f32(&[0.0, i as f32]);
+                            plot_ui.line(Line::new(values).name(format!("line {}", i)));
+                        }
+                    });
+                let widget_rect = response.response.rect;
+                let frame = *response.transform.frame();
+                // The plot area makes room for the legend, but keeps some of its size.
+                if position == LegendPosition::RightOutside {
+                    assert!(frame.right() < widget_rect.right() - 50.0);
+                    assert_eq!(frame.bottom(), widget_rect.bottom());
+                } else {
+                    assert!(frame.bottom() < widget_rect.bottom() - 20.0);
+                    assert_eq!(frame.right(), widget_rect.right());
+                }
+                assert!(frame.width() > 100.0 && frame.height() > 0.0);
+            }
+        });
+    });
+}
+
+#[test]
+fn test_grid_spacer() {
+    let ctx = Context::default();
+    let inputs = Rc::new(RefCell::new(Vec::new()));
+    let marks = Rc::new(RefCell::new(Vec::new()));
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            let inputs = inputs.clone();
+            let marks = marks.clone();
+            Plot::new("plot")
+                .include_x(0.0)
+                .include_x(10.0)
+                .include_y(1.0)
+                .x_grid_spacer(move |input| {
+                    inputs.borrow_mut().push(input);
+                    [-1.0, 2.5, 7.0, 100.0]
+                        .iter()
+                        .map(|&value| GridMark {
+                            value,
+                            step_size: 1.0,
+                        })
+                        .collect()
+                })
+                .grid_renderer(move |context, _shapes| {
+                    if context.axis == Axis::X {
+                        marks
+                            .borrow_mut()
+                            .extend(context.marks.iter().map(|mark| mark.value));
+                    }
+                })
+                .show(ui, |_plot_ui| ());
+        });
+    });
+    let inputs = inputs.borrow();
+    assert_eq!(inputs.len(), 1);
+    assert!(inputs[0].bounds.0 < 0.0 && inputs[0].bounds.1 > 10.0);
+    assert!(inputs[0].base_step_size > 0.0);
+    // Only the marks within the bounds are used.
+    assert_eq!(*marks.borrow(), vec![2.5, 7.0]);
+}
+
+#[test]
+fn test_min_tick_spacing() {
+    let plot = Plot::new("plot").min_tick_spacing([0.0, f32::NAN]);
+    assert_eq!(plot.min_tick_spacing, [1.0, 1.0]);
+
+    // Used to hang, generating gridlines until running out of memory.
+    let mark_count = Rc::new(RefCell::new(0));
+    let ctx = Context::default();
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            let mark_count = mark_count.clone();
+            Plot::new("plot")
+                .min_tick_spacing([0.0, 0.0])
+                .grid_renderer(move |context, _shapes| {
+                    *mark_count.borrow_mut() += context.marks.len();
+                })
+                .show(ui, |plot_ui| {
+                    plot_ui.line(Line::new(Values::from_values(vec![
+                        Value::new(0.0, 0.0),
+                        Value::new(1.0, 1.0),
+                    ])));
+                });
+        });
+    });
+    assert!(*mark_count.borrow() > 0);
+}
+
+#[test]
+fn test_plot_response_conversions() {
+    let ctx = Context::default();
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            let response = Plot::new("plot").show(ui, |_plot_ui| 42);
+            assert_eq!(response.rect, response.response.rect);
+            let rect = response.rect;
+            let inner: InnerResponse<i32> = response.into();
+            assert_eq!((inner.inner, inner.response.rect), (42, rect));
+        });
+    });
+}
+
+#[test]
+fn test_zoom_step() {
+    assert_eq!(Plot::new("plot").zoom_step(2.0).zoom_step, Some(2.0));
+    assert_eq!(Plot::new("plot").zoom_step(1.0).zoom_step, None);
+    assert_eq!(Plot::new("plot").zoom_step(0.5).zoom_step, None);
+
+    // Zooming with the scroll wheel is quantized to the step.
+    let ctx = Context::default();
+    let width_after_frame = |events: Vec<Event>| {
+        let input = RawInput {
+            events,
+            ..Default::default()
+        };
+        let mut width = 0.0;
+        let _ = ctx.run(input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let response = Plot::new("plot").zoom_step(2.0).show(ui, |plot_ui| {
+                    plot_ui.line(Line::new(Values::from_ys_f32(&[0.0, 1.0])));
+                });
+                width = response.transform.bounds().width();
+            });
+        });
+        width
+    };
+    let width = width_after_frame(vec![Event::PointerMoved(pos2(500.0, 500.0))]);
+    let zoomed_width = width_after_frame(vec![Event::Zoom(1.01)]);
+    assert!((zoomed_width - 0.5 * width).abs() < 1e-9 * width);
+}
+
+#[test]
+fn test_selection() {
+    let ctx = Context::default();
+    let mut plot_id = None;
+    let mut run = |events: Vec<Event>| {
+        let input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(800.0, 600.0))),
+            events,
+            ..Default::default()
+        };
+        let mut output = None;
+        let _ = ctx.run(input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                plot_id = Some(ui.make_persistent_id(Id::new("plot")));
+                let response = Plot::new("plot")
+                    .width(400.0)
+                    .height(300.0)
+                    .allow_selection(true)
+                    .show(ui, |plot_ui| {
+                        plot_ui.line(Line::new(Values::from_ys_f32(&[0.0, 1.0])));
+                    });
+                output = Some((response.selection, *response.transform.bounds()));
+            });
+        });
+        let memory = PlotMemory::load(&ctx, plot_id.unwrap()).unwrap();
+        (output.unwrap(), memory.selection_start)
+    };
+    let button = |pos: Pos2, pressed: bool| Event::PointerButton {
+        pos,
+        button: PointerButton::Middle,
+        pressed,
+        modifiers: Default::default(),
+    };
+    let ((_, bounds), _) = run(vec![Event::PointerMoved(pos2(100.0, 100.0))]);
+
+    // Drag out a selection with the selection button.
+    run(vec![button(pos2(100.0, 100.0), true)]);
+    run(vec![Event::PointerMoved(pos2(200.0, 150.0))]);
+    let ((selection, _), selection_start) = run(vec![Event::PointerMoved(pos2(300.0, 200.0))]);
+    assert!(selection.is_none());
+    assert_eq!(selection_start, Some(pos2(100.0, 100.0)));
+    let ((selection, new_bounds), selection_start) = run(vec![button(pos2(300.0, 200.0), false)]);
+    let selection = selection.unwrap();
+    assert!(selection.width() > 0.0 && selection.height() > 0.0);
+    assert_eq!(new_bounds, bounds);
+    assert_eq!(selection_start, None);
+
+    // A drag that ends outside of the plot selects nothing and is forgotten.
+    run(vec![button(pos2(100.0, 100.0), true)]);
+    run(vec![Event::PointerMoved(pos2(200.0, 150.0))]);
+    run(vec![Event::PointerMoved(pos2(700.0, 500.0))]);
+    let ((selection, _), selection_start) = run(vec![button(pos2(700.0, 500.0), false)]);
+    assert!(selection.is_none());
+    assert_eq!(selection_start, None);
+}
+
+#[test]
+fn test_span_order() {
+    let ctx = Context::default();
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            let response = Plot::new("plot").show(ui, |plot_ui| {
+                plot_ui.line(Line::new(Values::from_ys_f32(&[0.0, 1.0])).name("line"));
+                plot_ui.hspan(HSpan::new(0.2, 0.4).name("span"));
+                plot_ui.export_shapes();
+                // The items keep the order in which they were added, e.g. for the legend.
+                let names: Vec<_> = plot_ui.items.iter().map(|item| item.name()).collect();
+                assert_eq!(names, ["line", "span"]);
+            });
+            // But the span is drawn behind the line.
+            let shapes = response.shapes.unwrap().shapes;
+            let position = |is_shape: fn(&Shape) -> bool| {
+                shapes
+                    .iter()
+                    .position(|ClippedShape(clip_rect, shape)| {
+                        *clip_rect == *response.transform.frame() && is_shape(shape)
+                    })
+                    .unwrap()
+            };
+            let span = position(|shape| matches!(shape, Shape::Rect(_)));
+            let line = position(|shape| matches!(shape, Shape::Path(_)));
+            assert!(span < line);
+        });
+    });
+}
+
+#[test]
+fn test_export_shapes() {
+    let ctx = Context::default();
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            let response = Plot::new("plot").title("Title").show(ui, |plot_ui| {
+                plot_ui.line(Line::new(Values::from_ys_f32(&[0.0, 1.0])));
+                plot_ui.export_shapes();
+            });
+            let exported = response.shapes.unwrap();
+            assert_eq!(exported.rect, response.response.rect);
+            assert_eq!(exported.pixels_per_point, ctx.pixels_per_point());
+            // The background, then the items clipped to the frame, then the title above it.
+            let frame = *response.transform.frame();
+            let ClippedShape(clip_rect, background) = &exported.shapes[0];
+            assert!(matches!(background, Shape::Rect(_)));
+            assert!(exported.rect.contains_rect(*clip_rect));
+            assert!(exported
+                .shapes
+                .iter()
+                .any(|ClippedShape(clip_rect, shape)| *clip_rect == frame
+                    && matches!(shape, Shape::Path(_))));
+            let ClippedShape(clip_rect, title) = exported.shapes.last().unwrap();
+            assert!(matches!(title, Shape::Text(_)));
+            assert!(clip_rect.top() < frame.top());
+        });
+    });
+
+    let ctx = Context::default();
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            let response = Plot::new("plot").show(ui, |_plot_ui| ());
+            assert!(response.shapes.is_none());
+        });
+    });
+}
+
+#[test]
+fn test_to_svg() {
+    let ctx = Context::default();
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            let svg = Plot::new("plot").title("Title").to_svg(
+                ui,
+                PlotBounds::from_min_max([0.0, 0.0], [1.0, 1.0]),
+                vec2(300.0, 200.0),
+                |plot_ui| plot_ui.line(Line::new(Values::from_ys_f32(&[0.0, 1.0]))),
+            );
+            assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="300""#));
+            assert!(svg.trim_end().ends_with("</svg>"));
+            assert!(svg.contains("<polyline "));
+            assert!(svg.contains(">Title</text>"));
+            // The export does not interact with the ui.
+            assert!(ui.ctx().frame_state().used_ids.is_empty());
+        });
+    });
+}
+
+#[test]
+fn test_to_svg_id() {
+    let ctx = Context::default();
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            let plot_id = ui.make_persistent_id(Id::new("plot"));
+            let response = Plot::new("plot").show(ui, |_plot_ui| ());
+            let _ = Plot::new("plot").to_svg(
+                ui,
+                PlotBounds::from_min_max([0.0, 0.0], [1.0, 1.0]),
+                vec2(300.0, 200.0),
+                |_plot_ui| (),
+            );
+            // The SVG must not claim the id of the plot shown above.
+            let used_rect = ui.ctx().frame_state().used_ids.get(&plot_id).copied();
+            assert_eq!(used_rect, Some(response.response.rect));
+        });
+    });
+}
+
+#[test]
+fn test_title() {
+    let ctx = Context::default();
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            let response = Plot::new("plot")
+                .title("Title")
+                .height(100.0)
+                .show(ui, |_plot_ui| ());
+            let widget_rect = response.response.rect;
+            let frame = *response.transform.frame();
+            // The title is above the plot area, which keeps the given height.
+            assert!(
+                frame.top()
+                    >= widget_rect.top()
+                        + ui.fonts()
+                            .row_height(&TextStyle::Heading.resolve(ui.style()))
+            );
+            assert_eq!(frame.bottom(), widget_rect.bottom());
+            assert_eq!(frame.height(), 100.0);
+        });
+    });
+}
+
+#[test]
+fn test_plot_grid_unbounded_height() {
+    let ctx = Context::default();
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            ui.allocate_ui(vec2(300.0, f32::INFINITY), |ui| {
+                let responses = PlotGrid::new("grid", 2, 2)
+                    .spacing(vec2(10.0, 10.0))
+                    .show(ui, |_, _, _| ());
+                // Without a bounded height, the cells are square.
+                for response in &responses {
+                    let rect = response.response.rect;
+                    assert!(rect.is_finite());
+                    assert!((rect.width() - rect.height()).abs() < 1e-3);
+                }
+            });
+            ui.horizontal(|ui| {
+                let left = ui.cursor().left();
+                let responses = PlotGrid::new("row", 2, 2)
+                    .height(100.0)
+                    .show(ui, |_, _, _| ());
+                // The grid is allocated as a whole.
+                assert!(ui.cursor().left() > responses[3].response.rect.right());
+                assert!(ui.min_rect().bottom() >= responses[3].response.rect.bottom());
+                assert!(ui.min_rect().left() <= left);
+            });
+        });
+    });
 }
@@ -0,0 +1,185 @@
+//! Serialization of plot shapes to SVG, see [`super::Plot::to_svg`].
+
+use std::fmt::Write as _;
+
+use epaint::{Color32, Mesh, Pos2, Shape, Stroke, TextShape, TextureId, Vec2};
+
+/// Serializes the shapes into an SVG document of the given size.
+///
+/// Supported are circles, line segments, paths, rectangles, text and untextured meshes.
+/// Bézier curves and textured meshes (e.g. images) are skipped.
+pub(super) fn shapes_to_svg(shapes: &[Shape], size: Vec2) -> String {
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = size.x,
+        h = size.y,
+    );
+    svg.push('\n');
+    for shape in shapes {
+        write_shape(&mut svg, shape);
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn write_shape(svg: &mut String, shape: &Shape) {
+    match shape {
+        Shape::Noop | Shape::QuadraticBezier(_) | Shape::CubicBezier(_) => {}
+        Shape::Vec(shapes) => {
+            for shape in shapes {
+                write_shape(svg, shape);
+            }
+        }
+        Shape::Circle(circle) => {
+            let _ = writeln!(
+                svg,
+                r#"<circle cx="{}" cy="{}" r="{}" {} {}/>"#,
+                circle.center.x,
+                circle.center.y,
+                circle.radius,
+                fill(circle.fill),
+                stroke(circle.stroke),
+            );
+        }
+        Shape::LineSegment { points, stroke: s } => {
+            let _ = writeln!(
+                svg,
+                r#"<line x1="{}" y1="{}" x2="{}" y2="{}" {}/>"#,
+                points[0].x,
+                points[0].y,
+                points[1].x,
+                points[1].y,
+                stroke(*s),
+            );
+        }
+        Shape::Path(path) => {
+            let element = if path.closed { "polygon" } else { "polyline" };
+            let path_fill = if path.closed {
+                fill(path.fill)
+            } else {
+                fill(Color32::TRANSPARENT)
+            };
+            let _ = writeln!(
+                svg,
+                r#"<{} points="{}" {} {}/>"#,
+                element,
+                points(&path.points),
+                path_fill,
+                stroke(path.stroke),
+            );
+        }
+        Shape::Rect(rect) => {
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" rx="{}" {} {}/>"#,
+                rect.rect.min.x,
+                rect.rect.min.y,
+                rect.rect.width(),
+                rect.rect.height(),
+                rect.corner_radius,
+                fill(rect.fill),
+                stroke(rect.stroke),
+            );
+        }
+        Shape::Text(text) => write_text(svg, text),
+        Shape::Mesh(mesh) => write_mesh(svg, mesh),
+    }
+}
+
+fn write_text(svg: &mut String, text: &TextShape) {
+    let galley = &text.galley;
+    for row in &galley.rows {
+        let first_glyph = match row.glyphs.first() {
+            Some(glyph) => glyph,
+            None => continue,
+        };
+        let format = &galley.job.sections[first_glyph.section_index as usize].format;
+        let color = text.override_text_color.unwrap_or(format.color);
+        let content: String = row.glyphs.iter().map(|glyph| glyph.chr).collect();
+        let pos = text.pos + row.rect.min.to_vec2();
+        let rotation = if text.angle == 0.0 {
+            String::new()
+        } else {
+            format!(
+                r#" transform="rotate({} {} {})""#,
+                text.angle.to_degrees(),
+                text.pos.x,
+                text.pos.y
+            )
+        };
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" font-size="{}" dominant-baseline="hanging" {}{}>{}</text>"#,
+            pos.x,
+            pos.y,
+            format.font_id.size,
+            fill(color),
+            rotation,
+            escape(&content),
+        );
+    }
+}
+
+fn write_mesh(svg: &mut String, mesh: &Mesh) {
+    if mesh.texture_id != TextureId::default() {
+        return;
+    }
+    for triangle in mesh.indices.chunks_exact(3) {
+        let vertices: Vec<_> = triangle
+            .iter()
+            .map(|&i| mesh.vertices[i as usize])
+            .collect();
+        let corners: Vec<_> = vertices.iter().map(|v| v.pos).collect();
+        let _ = writeln!(
+            svg,
+            r#"<polygon points="{}" {}/>"#,
+            points(&corners),
+            fill(vertices[0].color),
+        );
+    }
+}
+
+fn points(points: &[Pos2]) -> String {
+    points
+        .iter()
+        .map(|p| format!("{},{}", p.x, p.y))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn fill(color: Color32) -> String {
+    if color.a() == 0 {
+        r#"fill="none""#.to_owned()
+    } else {
+        let [r, g, b, a] = color.to_srgba_unmultiplied();
+        format!(
+            r#"fill="rgb({},{},{})" fill-opacity="{}""#,
+            r,
+            g,
+            b,
+            a as f32 / 255.0
+        )
+    }
+}
+
+fn stroke(stroke: Stroke) -> String {
+    if stroke.width <= 0.0 || stroke.color.a() == 0 {
+        r#"stroke="none""#.to_owned()
+    } else {
+        let [r, g, b, a] = stroke.color.to_srgba_unmultiplied();
+        format!(
+            r#"stroke="rgb({},{},{})" stroke-opacity="{}" stroke-width="{}""#,
+            r,
+            g,
+            b,
+            a as f32 / 255.0,
+            stroke.width
+        )
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
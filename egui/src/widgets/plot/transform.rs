@@ -18,6 +18,10 @@ impl PlotBounds {
         max: [-f64::INFINITY; 2],
     };
 
+    pub fn from_min_max(min: [f64; 2], max: [f64; 2]) -> Self {
+        Self { min, max }
+    }
+
     pub fn min(&self) -> [f64; 2] {
         self.min
     }
@@ -94,6 +98,44 @@ impl PlotBounds {
         self.max[1] = self.max[1].max(other.max[1]);
     }
 
+    /// Clamps each edge to `range`. An axis that would be left without extent is set to the
+    /// whole range instead.
+    pub(crate) fn clamp_edges(&mut self, range: &PlotBounds) {
+        for axis in 0..2 {
+            let (lo, hi) = (range.min[axis], range.max[axis]);
+            if lo.is_nan() || hi.is_nan() || lo >= hi {
+                continue;
+            }
+            self.min[axis] = self.min[axis].clamp(lo, hi);
+            self.max[axis] = self.max[axis].clamp(lo, hi);
+            if self.min[axis] >= self.max[axis] {
+                self.min[axis] = lo;
+                self.max[axis] = hi;
+            }
+        }
+    }
+
+    /// Moves the bounds along the given axes so that they lie within `range`. An axis that is
+    /// wider than the range is set to the whole range instead.
+    pub(crate) fn shift_into(&mut self, range: &PlotBounds, axes: [bool; 2]) {
+        for (axis, &clamp) in axes.iter().enumerate() {
+            let (lo, hi) = (range.min[axis], range.max[axis]);
+            if !clamp || !lo.is_finite() || !hi.is_finite() || lo >= hi {
+                continue;
+            }
+            if self.max[axis] - self.min[axis] >= hi - lo {
+                self.min[axis] = lo;
+                self.max[axis] = hi;
+            } else if self.min[axis] < lo {
+                self.max[axis] += lo - self.min[axis];
+                self.min[axis] = lo;
+            } else if self.max[axis] > hi {
+                self.min[axis] -= self.max[axis] - hi;
+                self.max[axis] = hi;
+            }
+        }
+    }
+
     pub(crate) fn translate_x(&mut self, delta: f64) {
         self.min[0] += delta;
         self.max[0] += delta;
@@ -109,6 +151,19 @@ impl PlotBounds {
         self.translate_y(delta.y as f64);
     }
 
+    /// Gives each axis along which the bounds are a single finite value an extent of `margin`
+    /// times the magnitude of that value (but at least `margin`) on both sides.
+    pub(crate) fn expand_degenerate(&mut self, margin: f64) {
+        for axis in 0..2 {
+            let value = self.min[axis];
+            if value.is_finite() && value == self.max[axis] {
+                let pad = margin * value.abs().max(1.0);
+                self.min[axis] -= pad;
+                self.max[axis] += pad;
+            }
+        }
+    }
+
     pub(crate) fn add_relative_margin(&mut self, margin_fraction: Vec2) {
         let width = self.width().max(0.0);
         let height = self.height().max(0.0);
@@ -116,6 +171,19 @@ impl PlotBounds {
         self.expand_y(margin_fraction.y as f64 * height);
     }
 
+    /// Maps each axis of finite bounds with the given function, e.g. [`AxisScale::forward`] to
+    /// get the bounds in the space in which they are drawn.
+    pub(crate) fn map_axes(&self, scales: [AxisScale; 2], f: fn(AxisScale, f64) -> f64) -> Self {
+        let mut bounds = *self;
+        if self.is_finite() {
+            for (axis, scale) in scales.iter().enumerate() {
+                bounds.min[axis] = f(*scale, self.min[axis]);
+                bounds.max[axis] = f(*scale, self.max[axis]);
+            }
+        }
+        bounds
+    }
+
     pub(crate) fn range_x(&self) -> RangeInclusive<f64> {
         self.min[0]..=self.max[0]
     }
@@ -133,10 +201,128 @@ impl PlotBounds {
     }
 }
 
+/// Expands `data` by the relative `margin` (like auto-bounds do) and then rounds each axis outwards
+/// to a multiple of a "nice" step size (1, 2 or 5 times a power of ten).
+///
+/// This is useful to precompute matching rounded extents, e.g. to align multiple plots.
+/// Axes that are not finite are returned unchanged.
+///
+/// ```
+/// # use egui::plot::{nice_bounds, PlotBounds};
+/// # use egui::Vec2;
+/// let bounds = PlotBounds::from_min_max([0.3, -0.02], [9.7, 1.07]);
+/// let nice = nice_bounds(&bounds, Vec2::ZERO);
+/// assert_eq!(nice.min(), [0.0, -0.2]);
+/// assert_eq!(nice.max(), [10.0, 1.2]);
+/// ```
+pub fn nice_bounds(data: &PlotBounds, margin: Vec2) -> PlotBounds {
+    let mut bounds = *data;
+    bounds.add_relative_margin(margin);
+    for axis in 0..2 {
+        let (min, max) = (bounds.min[axis], bounds.max[axis]);
+        if !min.is_finite() || !max.is_finite() || max < min {
+            continue;
+        }
+        let step = nice_step((max - min) / 10.0, min.abs().max(max.abs()));
+        let mut nice_min = (min / step).floor() * step;
+        let mut nice_max = (max / step).ceil() * step;
+        if nice_min == nice_max {
+            nice_min -= step;
+            nice_max += step;
+        }
+        // Round off floating point noise from the multiplication.
+        bounds.min[axis] = emath::round_to_decimals(nice_min, 12);
+        bounds.max[axis] = emath::round_to_decimals(nice_max, 12);
+    }
+    bounds
+}
+
+/// The smallest step of the form 1, 2 or 5 times a power of ten that is at least `min_step`.
+/// Falls back to the magnitude of `scale` (or `1.0`) if `min_step` is zero.
+fn nice_step(min_step: f64, scale: f64) -> f64 {
+    if min_step <= 0.0 {
+        return if scale > 0.0 {
+            10.0_f64.powi(scale.log10().floor() as i32)
+        } else {
+            1.0
+        };
+    }
+    let magnitude = 10.0_f64.powi(min_step.log10().floor() as i32);
+    [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| *step >= min_step)
+        .unwrap_or(10.0 * magnitude)
+}
+
+/// How the values along an axis are mapped to screen positions, see [`super::Plot::axis_scales`].
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AxisScale {
+    /// Equal differences of values have equal distances on screen.
+    Linear,
+
+    /// Symmetric logarithmic scale for data that crosses zero but spans many orders of magnitude.
+    ///
+    /// Values within `±linthresh` are mapped linearly, values beyond it logarithmically, with one
+    /// decade taking up as much space as the linear range on each side of zero. Gridlines are
+    /// placed at zero and at `±linthresh` times powers of ten, so a smaller `linthresh` shows more
+    /// decades. It must be positive.
+    SymLog { linthresh: f64 },
+
+    /// Base-10 logarithmic scale for positive data that spans many orders of magnitude, with each
+    /// decade taking up the same space. Gridlines are placed at powers of ten and, if there is
+    /// enough space, at two and five times them.
+    ///
+    /// Non-positive values can't be shown: they are mapped far below the smallest positive value,
+    /// and the automatic bounds leave them out.
+    Log,
+}
+
+impl Default for AxisScale {
+    fn default() -> Self {
+        Self::Linear
+    }
+}
+
+impl AxisScale {
+    /// Maps a value to the linear space in which it is drawn.
+    pub fn forward(self, value: f64) -> f64 {
+        match self {
+            Self::Linear => value,
+            Self::SymLog { linthresh } => {
+                let linthresh = linthresh.abs();
+                if value.abs() <= linthresh {
+                    value / linthresh
+                } else {
+                    value.signum() * (1.0 + (value.abs() / linthresh).log10())
+                }
+            }
+            Self::Log => value.max(f64::MIN_POSITIVE).log10(),
+        }
+    }
+
+    /// The inverse of [`Self::forward`].
+    pub fn inverse(self, scaled: f64) -> f64 {
+        match self {
+            Self::Linear => scaled,
+            Self::SymLog { linthresh } => {
+                let linthresh = linthresh.abs();
+                if scaled.abs() <= 1.0 {
+                    scaled * linthresh
+                } else {
+                    scaled.signum() * linthresh * 10.0_f64.powf(scaled.abs() - 1.0)
+                }
+            }
+            Self::Log => 10.0_f64.powf(scaled),
+        }
+    }
+}
+
 /// Contains the screen rectangle and the plot bounds and provides methods to transform them.
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-#[derive(Clone)]
-pub(crate) struct ScreenTransform {
+#[derive(Clone, PartialEq)]
+pub struct ScreenTransform {
     /// The screen rectangle.
     frame: Rect,
     /// The plot bounds.
@@ -145,6 +331,9 @@ pub(crate) struct ScreenTransform {
     x_centered: bool,
     /// Whether to always center the y-range of the bounds.
     y_centered: bool,
+    /// The scales of the x and y axes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    scales: [AxisScale; 2],
 }
 
 impl ScreenTransform {
@@ -167,9 +356,33 @@ impl ScreenTransform {
             bounds,
             x_centered,
             y_centered,
+            scales: [AxisScale::Linear; 2],
         }
     }
 
+    /// Use the given scales for the x and y axes.
+    pub fn with_scales(mut self, scales: [AxisScale; 2]) -> Self {
+        self.scales = scales;
+        self
+    }
+
+    pub fn scales(&self) -> [AxisScale; 2] {
+        self.scales
+    }
+
+    /// The bounds of the given axis, mapped by its scale.
+    fn scaled_range(&self, axis: usize) -> RangeInclusive<f64> {
+        let scale = self.scales[axis];
+        scale.forward(self.bounds.min[axis])..=scale.forward(self.bounds.max[axis])
+    }
+
+    /// Sets the bounds of the given axis from a range in the space mapped by its scale.
+    fn set_scaled_range(&mut self, axis: usize, min: f64, max: f64) {
+        let scale = self.scales[axis];
+        self.bounds.min[axis] = scale.inverse(min);
+        self.bounds.max[axis] = scale.inverse(max);
+    }
+
     pub fn frame(&self) -> &Rect {
         &self.frame
     }
@@ -189,13 +402,41 @@ impl ScreenTransform {
         if self.y_centered {
             delta_pos.y = 0.;
         }
-        delta_pos.x *= self.dvalue_dpos()[0] as f32;
-        delta_pos.y *= self.dvalue_dpos()[1] as f32;
-        self.bounds.translate(delta_pos);
+        if self.scales == [AxisScale::Linear; 2] {
+            delta_pos.x *= self.dvalue_dpos()[0] as f32;
+            delta_pos.y *= self.dvalue_dpos()[1] as f32;
+            self.bounds.translate(delta_pos);
+        } else {
+            let dvalue_dpos = self.dvalue_dpos();
+            for axis in 0..2 {
+                let delta = delta_pos[axis] as f64 * dvalue_dpos[axis];
+                let range = self.scaled_range(axis);
+                self.set_scaled_range(axis, range.start() + delta, range.end() + delta);
+            }
+        }
     }
 
     /// Zoom by a relative factor with the given screen position as center.
     pub fn zoom(&mut self, zoom_factor: Vec2, center: Pos2) {
+        if self.scales != [AxisScale::Linear; 2] {
+            let old_bounds = self.bounds;
+            let center = self.value_from_position(center);
+            for axis in 0..2 {
+                let factor = zoom_factor[axis] as f64;
+                let center = self.scales[axis].forward(if axis == 0 { center.x } else { center.y });
+                let range = self.scaled_range(axis);
+                self.set_scaled_range(
+                    axis,
+                    center + (range.start() - center) / factor,
+                    center + (range.end() - center) / factor,
+                );
+            }
+            if !self.bounds.is_valid() {
+                self.bounds = old_bounds;
+            }
+            return;
+        }
+
         let center = self.value_from_position(center);
 
         let mut new_bounds = self.bounds;
@@ -209,15 +450,123 @@ impl ScreenTransform {
         }
     }
 
+    /// Reduces the zoom factor of each axis so that zooming around `center` keeps the width and
+    /// height of the bounds, in data units, within `min_span` and `max_span`. Zooming towards a
+    /// limit that is already exceeded is not possible at all.
+    pub fn limit_zoom(
+        &self,
+        zoom_factor: Vec2,
+        center: Pos2,
+        min_span: Vec2,
+        max_span: Vec2,
+    ) -> Vec2 {
+        let center = self.value_from_position(center);
+        let mut limited = zoom_factor;
+        for axis in 0..2 {
+            let scale = self.scales[axis];
+            let center = scale.forward(if axis == 0 { center.x } else { center.y });
+            let range = self.scaled_range(axis);
+            // The span after zooming out by `t`, i.e. by the factor `1 / t`.
+            let span = |t: f64| {
+                scale.inverse(center + (range.end() - center) * t)
+                    - scale.inverse(center + (range.start() - center) * t)
+            };
+            let current = span(1.0);
+            let (lo, hi) = (
+                (min_span[axis] as f64).min(current),
+                (max_span[axis] as f64).max(current),
+            );
+            let t = 1.0 / zoom_factor[axis] as f64;
+            let target = span(t).clamp(lo, hi);
+            if !t.is_finite() || target == span(t) {
+                continue;
+            }
+            let t = if scale == AxisScale::Linear {
+                target / current
+            } else {
+                // The span grows monotonically with `t`.
+                let (mut a, mut b) = if t < 1.0 { (t, 1.0) } else { (1.0, t) };
+                for _ in 0..60 {
+                    let mid = 0.5 * (a + b);
+                    if span(mid) < target {
+                        a = mid;
+                    } else {
+                        b = mid;
+                    }
+                }
+                if t < 1.0 {
+                    b
+                } else {
+                    a
+                }
+            };
+            limited[axis] = (1.0 / t) as f32;
+        }
+        limited
+    }
+
+    /// Widens or narrows each axis of `bounds` around its center, in the space mapped by the scale
+    /// of the axis, so that the width and height in data units are within `min_span` and
+    /// `max_span`. Like [`Self::limit_zoom`], but for bounds that are set all at once.
+    pub fn limit_bounds(
+        &self,
+        mut bounds: PlotBounds,
+        min_span: Vec2,
+        max_span: Vec2,
+    ) -> PlotBounds {
+        for axis in 0..2 {
+            let scale = self.scales[axis];
+            let min = scale.forward(bounds.min[axis]);
+            let max = scale.forward(bounds.max[axis]);
+            let center = 0.5 * (min + max);
+            // The span for the half width `h` around the center.
+            let span = |h: f64| scale.inverse(center + h) - scale.inverse(center - h);
+            let half = 0.5 * (max - min);
+            let current = span(half);
+            let target = current
+                .max(min_span[axis] as f64)
+                .min(max_span[axis] as f64);
+            if !current.is_finite() || target == current {
+                continue;
+            }
+            let half = if scale == AxisScale::Linear {
+                0.5 * target
+            } else {
+                // The span grows monotonically with `h`.
+                let (mut a, mut b) = if target < current {
+                    (0.0, half)
+                } else {
+                    (half, half.max(1.0))
+                };
+                while span(b) < target && b.is_finite() {
+                    a = b;
+                    b *= 2.0;
+                }
+                for _ in 0..60 {
+                    let mid = 0.5 * (a + b);
+                    if span(mid) < target {
+                        a = mid;
+                    } else {
+                        b = mid;
+                    }
+                }
+                b
+            };
+            bounds.min[axis] = scale.inverse(center - half);
+            bounds.max[axis] = scale.inverse(center + half);
+        }
+        bounds
+    }
+
     pub fn position_from_value(&self, value: &Value) -> Pos2 {
         let x = remap(
-            value.x,
-            self.bounds.min[0]..=self.bounds.max[0],
+            self.scales[0].forward(value.x),
+            self.scaled_range(0),
             (self.frame.left() as f64)..=(self.frame.right() as f64),
         );
         let y = remap(
-            value.y,
-            self.bounds.min[1]..=self.bounds.max[1],
+            self.scales[1].forward(value.y),
+            self.scaled_range(1),
             (self.frame.bottom() as f64)..=(self.frame.top() as f64), // negated y axis!
         );
         pos2(x as f32, y as f32)
@@ -227,14 +576,14 @@ impl ScreenTransform {
         let x = remap(
             pos.x as f64,
             (self.frame.left() as f64)..=(self.frame.right() as f64),
-            self.bounds.min[0]..=self.bounds.max[0],
+            self.scaled_range(0),
         );
         let y = remap(
             pos.y as f64,
             (self.frame.bottom() as f64)..=(self.frame.top() as f64), // negated y axis!
-            self.bounds.min[1]..=self.bounds.max[1],
+            self.scaled_range(1),
         );
-        Value::new(x, y)
+        Value::new(self.scales[0].inverse(x), self.scales[1].inverse(y))
     }
 
     /// Transform a rectangle of plot values to a screen-coordinate rectangle.
@@ -252,13 +601,19 @@ impl ScreenTransform {
     }
 
     /// delta position / delta value
+    ///
+    /// For non-linear [`AxisScale`]s this is relative to the values mapped by the scale.
     pub fn dpos_dvalue_x(&self) -> f64 {
-        self.frame.width() as f64 / self.bounds.width()
+        let range = self.scaled_range(0);
+        self.frame.width() as f64 / (range.end() - range.start())
     }
 
     /// delta position / delta value
+    ///
+    /// For non-linear [`AxisScale`]s this is relative to the values mapped by the scale.
     pub fn dpos_dvalue_y(&self) -> f64 {
-        -self.frame.height() as f64 / self.bounds.height() // negated y axis!
+        let range = self.scaled_range(1);
+        -self.frame.height() as f64 / (range.end() - range.start()) // negated y axis!
     }
 
     /// delta position / delta value
@@ -2,8 +2,9 @@ use std::f64::consts::TAU;
 
 use egui::*;
 use plot::{
-    Arrows, Bar, BarChart, BoxElem, BoxPlot, BoxSpread, Corner, HLine, HoverLine, Legend, Line,
-    LineStyle, MarkerShape, Plot, PlotImage, Points, Polygon, Text, VLine, Value, Values,
+    Arrows, Bar, BarChart, BoxElem, BoxPlot, BoxSpread, Corner, HLine, HoverLine, Legend,
+    LegendPosition, Line, LineStyle, MarkerShape, Plot, PlotImage, PlotResponse, Points, Polygon,
+    Text, VLine, Value, Values,
 };
 
 #[derive(PartialEq)]
@@ -270,9 +271,16 @@ impl Widget for &mut LegendDemo {
 
             ui.label("Position:");
             ui.horizontal(|ui| {
-                Corner::all().for_each(|position| {
-                    ui.selectable_value(&mut config.position, position, format!("{:?}", position));
-                });
+                let positions = Corner::all()
+                    .map(LegendPosition::Inside)
+                    .chain([LegendPosition::RightOutside, LegendPosition::BottomOutside]);
+                for position in positions {
+                    let text = match position {
+                        LegendPosition::Inside(corner) => format!("{:?}", corner),
+                        _ => format!("{:?}", position),
+                    };
+                    ui.selectable_value(&mut config.position, position, text);
+                }
             });
             ui.end_row();
 
@@ -451,9 +459,10 @@ impl Widget for &mut InteractionDemo {
     fn ui(self, ui: &mut Ui) -> Response {
         let plot = Plot::new("interaction_demo").height(300.0);
 
-        let InnerResponse {
+        let PlotResponse {
             response,
             inner: (screen_pos, pointer_coordinate, pointer_coordinate_drag_delta, bounds, hovered),
+            ..
         } = plot.show(ui, |plot_ui| {
             (
                 plot_ui.screen_from_plot(Value::new(0.0, 0.0)),